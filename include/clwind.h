@@ -0,0 +1,201 @@
+/*
+ * C interface to clwind, available when the crate is built with the `capi`
+ * feature. Generated by cbindgen from src/capi.rs, do not edit.
+ */
+
+#ifndef CLWIND_H
+#define CLWIND_H
+
+#include <stdint.h>
+
+/* Colors: CLW_COLOR_NONE, a palette index 0..255 (0..15 are the base colors),
+ * or CLW_RGB(r, g, b) for truecolor. */
+#define CLW_RGB(r, g, b) \
+    (CLW_COLOR_RGB_FLAG | (((r) & 0xFF) << 16) | (((g) & 0xFF) << 8) | ((b) & 0xFF))
+
+/*
+ * Leaves the text or background color unset.
+ */
+#define CLW_COLOR_NONE -1
+
+/*
+ * Marks a color as a 24-bit `0xRRGGBB` value.
+ */
+#define CLW_COLOR_RGB_FLAG 16777216
+
+/*
+ * Palette index of [`Color::Black`].
+ */
+#define CLW_BLACK 0
+
+/*
+ * Palette index of [`Color::Red`].
+ */
+#define CLW_RED 1
+
+/*
+ * Palette index of [`Color::Green`].
+ */
+#define CLW_GREEN 2
+
+/*
+ * Palette index of [`Color::Yellow`].
+ */
+#define CLW_YELLOW 3
+
+/*
+ * Palette index of [`Color::Blue`].
+ */
+#define CLW_BLUE 4
+
+/*
+ * Palette index of [`Color::Magenta`].
+ */
+#define CLW_MAGENTA 5
+
+/*
+ * Palette index of [`Color::Cyan`].
+ */
+#define CLW_CYAN 6
+
+/*
+ * Palette index of [`Color::White`].
+ */
+#define CLW_WHITE 7
+
+/*
+ * Palette index of [`Color::BrightBlack`].
+ */
+#define CLW_BRIGHT_BLACK 8
+
+/*
+ * Palette index of [`Color::BrightRed`].
+ */
+#define CLW_BRIGHT_RED 9
+
+/*
+ * Palette index of [`Color::BrightGreen`].
+ */
+#define CLW_BRIGHT_GREEN 10
+
+/*
+ * Palette index of [`Color::BrightYellow`].
+ */
+#define CLW_BRIGHT_YELLOW 11
+
+/*
+ * Palette index of [`Color::BrightBlue`].
+ */
+#define CLW_BRIGHT_BLUE 12
+
+/*
+ * Palette index of [`Color::BrightMagenta`].
+ */
+#define CLW_BRIGHT_MAGENTA 13
+
+/*
+ * Palette index of [`Color::BrightCyan`].
+ */
+#define CLW_BRIGHT_CYAN 14
+
+/*
+ * Palette index of [`Color::BrightWhite`].
+ */
+#define CLW_BRIGHT_WHITE 15
+
+/*
+ * Flag for [`Style::Bold`].
+ */
+#define CLW_BOLD (1 << 0)
+
+/*
+ * Flag for [`Style::Dim`].
+ */
+#define CLW_DIM (1 << 1)
+
+/*
+ * Flag for [`Style::Italic`].
+ */
+#define CLW_ITALIC (1 << 2)
+
+/*
+ * Flag for [`Style::Underline`].
+ */
+#define CLW_UNDERLINE (1 << 3)
+
+/*
+ * Flag for [`Style::Blink`].
+ */
+#define CLW_BLINK (1 << 4)
+
+/*
+ * Flag for [`Style::Reverse`].
+ */
+#define CLW_REVERSE (1 << 5)
+
+/*
+ * Flag for [`Style::Hidden`].
+ */
+#define CLW_HIDDEN (1 << 6)
+
+/*
+ * Flag for [`Style::Strikethrough`].
+ */
+#define CLW_STRIKETHROUGH (1 << 7)
+
+/*
+ * Flag for [`Style::DoubleUnderline`].
+ */
+#define CLW_DOUBLE_UNDERLINE (1 << 8)
+
+/*
+ * Flag for [`Style::CurlyUnderline`].
+ */
+#define CLW_CURLY_UNDERLINE (1 << 9)
+
+/*
+ * Flag for [`Style::DottedUnderline`].
+ */
+#define CLW_DOTTED_UNDERLINE (1 << 10)
+
+/*
+ * Flag for [`Style::DashedUnderline`].
+ */
+#define CLW_DASHED_UNDERLINE (1 << 11)
+
+/*
+ * Flag for [`Style::Overline`].
+ */
+#define CLW_OVERLINE (1 << 12)
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/*
+ * ## Styles a NUL-terminated string, returning a newly allocated string.
+ *
+ * Returns `NULL` when `text` is `NULL`. Invalid UTF-8 is replaced with `U+FFFD`.
+ * The result must be released with [`clw_string_free`].
+ *
+ * # Safety
+ *
+ * `text` must be `NULL` or point to a valid NUL-terminated string.
+ */
+char *clw_style(const char *text, int32_t fg, int32_t bg, uint32_t flags);
+
+/*
+ * ## Releases a string returned by [`clw_style`].
+ *
+ * # Safety
+ *
+ * `text` must be `NULL` or a pointer returned by [`clw_style`] that has not
+ * been freed yet.
+ */
+void clw_string_free(char *text);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* CLWIND_H */
@@ -0,0 +1,243 @@
+//! Compile-time checked class syntax for clwind, enabled by its `macros` feature.
+//!
+//! The color tables are shared with clwind, so the macro accepts exactly the
+//! classes that `CLW::classes` understands at runtime.
+
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+/// Builds a table of the CSS color names and their components.
+macro_rules! named_colors {
+    ($($constant:ident = $name:literal ($r:literal, $g:literal, $b:literal),)*) => {
+        /// Every CSS named color, sorted by name.
+        const NAMED: &[(&str, (u8, u8, u8))] = &[$(($name, ($r, $g, $b))),*];
+    };
+}
+
+include!("../../src/data/css_names.rs");
+include!("../../src/data/tailwind.rs");
+
+/// The names of the 16 base colors, with their `Color` variants.
+const BASE_COLORS: [(&str, &str); 16] = [
+    ("black", "Black"),
+    ("red", "Red"),
+    ("green", "Green"),
+    ("yellow", "Yellow"),
+    ("blue", "Blue"),
+    ("magenta", "Magenta"),
+    ("cyan", "Cyan"),
+    ("white", "White"),
+    ("bright-black", "BrightBlack"),
+    ("bright-red", "BrightRed"),
+    ("bright-green", "BrightGreen"),
+    ("bright-yellow", "BrightYellow"),
+    ("bright-blue", "BrightBlue"),
+    ("bright-magenta", "BrightMagenta"),
+    ("bright-cyan", "BrightCyan"),
+    ("bright-white", "BrightWhite"),
+];
+
+/// The style names, with their `Style` variants.
+const STYLES: [(&str, &str); 13] = [
+    ("bold", "Bold"),
+    ("dim", "Dim"),
+    ("italic", "Italic"),
+    ("underline", "Underline"),
+    ("blink", "Blink"),
+    ("reverse", "Reverse"),
+    ("hidden", "Hidden"),
+    ("strikethrough", "Strikethrough"),
+    ("double-underline", "DoubleUnderline"),
+    ("curly-underline", "CurlyUnderline"),
+    ("dotted-underline", "DottedUnderline"),
+    ("dashed-underline", "DashedUnderline"),
+    ("overline", "Overline"),
+];
+
+/// Styles text with utility classes checked at compile time.
+///
+/// The first argument is a string literal of classes, as accepted by
+/// `CLW::classes`; the rest are passed to `format!`. A class that isn't
+/// understood is a build error instead of being ignored.
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{clw, RenderMode};
+///
+/// let name = "world";
+/// let hello = clw!("text-red font-bold", "hello {}", name);
+///
+/// assert_eq!(hello.to_string_for(RenderMode::Test), "[fg=Red,Bold]hello world[/]");
+/// ```
+///
+/// ```compile_fail
+/// let typo = clwind::clw!("text-redd", "oops");
+/// ```
+#[proc_macro]
+pub fn clw(input: TokenStream) -> TokenStream {
+    let mut tokens = input.into_iter();
+    let Some(TokenTree::Literal(literal)) = tokens.next() else {
+        return error(
+            Span::call_site(),
+            "expected a string literal of classes, e.g. clw!(\"text-red\", \"text\")",
+        );
+    };
+    let Some(classes) = string_value(&literal) else {
+        return error(literal.span(), "expected a string literal of classes");
+    };
+
+    let mut chain = String::new();
+    for class in classes.split_whitespace() {
+        match builder_call(class) {
+            Some(call) => chain.push_str(&call),
+            None => return error(literal.span(), &format!("unknown class `{}`", class)),
+        }
+    }
+
+    match tokens.next() {
+        Some(TokenTree::Punct(p)) if p.as_char() == ',' => {}
+        Some(other) => return error(other.span(), "expected `,` after the classes"),
+        None => {
+            return error(
+                literal.span(),
+                "expected the text to style after the classes",
+            )
+        }
+    }
+
+    let mut text: TokenStream = "::std::format!".parse().unwrap();
+    text.extend([TokenTree::Group(Group::new(
+        Delimiter::Parenthesis,
+        tokens.collect(),
+    ))]);
+    let mut call: TokenStream = "::clwind::clw".parse().unwrap();
+    call.extend([TokenTree::Group(Group::new(Delimiter::Parenthesis, text))]);
+    call.extend(chain.parse::<TokenStream>().unwrap());
+    call
+}
+
+/// ## Returns the builder call for a class, or `None` if it isn't understood.
+fn builder_call(class: &str) -> Option<String> {
+    let style = match class {
+        "line-through" => Some("Strikethrough"),
+        "invisible" => Some("Hidden"),
+        "decoration-double" => Some("DoubleUnderline"),
+        "decoration-wavy" => Some("CurlyUnderline"),
+        "decoration-dotted" => Some("DottedUnderline"),
+        "decoration-dashed" => Some("DashedUnderline"),
+        _ => {
+            let name = normalize(class.strip_prefix("font-").unwrap_or(class));
+            STYLES.iter().find(|(n, _)| *n == name).map(|&(_, v)| v)
+        }
+    };
+    if let Some(style) = style {
+        return Some(format!(".font(::clwind::Style::{})", style));
+    }
+
+    let (prefix, value) = class.split_once('-')?;
+    let value = match value.strip_prefix('[') {
+        Some(value) => value.strip_suffix(']')?,
+        None => value,
+    };
+    let color = color(value)?;
+    match prefix {
+        "text" => Some(format!(".text({})", color)),
+        "bg" => Some(format!(".bg({})", color)),
+        "decoration" => Some(format!(".underline_color({})", color)),
+        _ => None,
+    }
+}
+
+/// ## Returns the expression for a color, in the order `Color::from_str` tries them.
+fn color(value: &str) -> Option<String> {
+    let value = value.trim();
+    let base = normalize(value);
+    if let Some((_, variant)) = BASE_COLORS.iter().find(|(n, _)| *n == base) {
+        return Some(format!("::clwind::Color::{}", variant));
+    }
+
+    if base == "default" {
+        return Some("::clwind::Color::Default".to_string());
+    }
+
+    let lower = value.to_ascii_lowercase();
+    let rgb = NAMED
+        .iter()
+        .find(|(n, _)| *n == lower)
+        .map(|&(_, rgb)| rgb)
+        .or_else(|| tailwind(value))
+        .or_else(|| hex(value))?;
+    Some(format!(
+        "::clwind::Color::Rgb({}, {}, {})",
+        rgb.0, rgb.1, rgb.2
+    ))
+}
+
+/// ## Looks up a Tailwind color such as `red-500`.
+fn tailwind(value: &str) -> Option<(u8, u8, u8)> {
+    let (color, shade) = value.rsplit_once('-')?;
+    let shade = SHADES.iter().position(|&s| shade.parse() == Ok(s))?;
+    let (_, values) = PALETTE.iter().find(|(c, _)| *c == color)?;
+    let hex = values[shade];
+    Some(((hex >> 16) as u8, (hex >> 8) as u8, hex as u8))
+}
+
+/// ## Parses `#RGB`, `#RRGGBB` or `#RRGGBBAA`, with an optional `#`.
+fn hex(value: &str) -> Option<(u8, u8, u8)> {
+    let digits = value.strip_prefix('#').unwrap_or(value);
+    if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let channel = |i: usize, len: usize| {
+        let value = u8::from_str_radix(&digits[i * len..(i + 1) * len], 16).unwrap_or(0);
+        match len {
+            1 => value * 17,
+            _ => value,
+        }
+    };
+    match digits.len() {
+        3 => Some((channel(0, 1), channel(1, 1), channel(2, 1))),
+        6 | 8 => Some((channel(0, 2), channel(1, 2), channel(2, 2))),
+        _ => None,
+    }
+}
+
+/// ## Lowercases a name and accepts `_` in place of `-`.
+fn normalize(name: &str) -> String {
+    name.trim().to_ascii_lowercase().replace('_', "-")
+}
+
+/// ## Returns the contents of a plain or raw string literal without escapes.
+fn string_value(literal: &Literal) -> Option<String> {
+    let text = literal.to_string();
+    if let Some(raw) = text.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let inner = &raw[hashes..raw.len() - hashes];
+        return inner
+            .strip_prefix('"')?
+            .strip_suffix('"')
+            .map(str::to_string);
+    }
+
+    let inner = text.strip_prefix('"')?.strip_suffix('"')?;
+    match inner.contains('\\') {
+        true => None,
+        false => Some(inner.to_string()),
+    }
+}
+
+/// ## Emits a `compile_error!` pointing at `span`.
+fn error(span: Span, message: &str) -> TokenStream {
+    let mut literal = Literal::string(message);
+    literal.set_span(span);
+    let mut bang = Punct::new('!', Spacing::Alone);
+    bang.set_span(span);
+    let mut body = TokenStream::new();
+    body.extend([TokenTree::Literal(literal)]);
+    TokenStream::from_iter([
+        TokenTree::Ident(Ident::new("compile_error", span)),
+        TokenTree::Punct(bang),
+        TokenTree::Group(Group::new(Delimiter::Brace, body)),
+    ])
+}
@@ -0,0 +1,104 @@
+//! Padding styled text to a width, outside of its escape codes.
+
+use crate::{text_width, CLW};
+
+/// Where text sits when it's padded to a wider column.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Align {
+    /// Padding goes after the text.
+    #[default]
+    Left,
+    /// Padding goes before the text.
+    Right,
+    /// Padding is split on both sides, the extra column going after.
+    Center,
+}
+
+impl CLW<'_> {
+    /// ## Pads the text with spaces to at least `width` columns.
+    ///
+    /// The spaces are written outside the escape codes, so backgrounds and
+    /// underlines stop at the text and columns still line up. Text wider than
+    /// `width` is left as it is.
+    ///
+    /// ### Arguments
+    ///
+    /// * `width` - The minimum number of columns
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{clw, Align, ColorChoice};
+    ///
+    /// let cell = clw("ok").text_green().width(6).align(Align::Right);
+    ///
+    /// assert_eq!(cell.color_choice(ColorChoice::Always).to_string(), "    \x1b[32mok\x1b[0m");
+    /// ```
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// ## Sets where the text sits within the width set by [`CLW::width`].
+    ///
+    /// ### Arguments
+    ///
+    /// * `align` - The alignment, left by default
+    pub fn align(mut self, align: Align) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// ## Pads the text on the left to `width` columns, aligning it right.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{clw, RenderMode};
+    ///
+    /// assert_eq!(clw(42).pad_left(5).to_string_for(RenderMode::Plain), "   42");
+    /// ```
+    pub fn pad_left(self, width: usize) -> Self {
+        self.width(width).align(Align::Right)
+    }
+
+    /// ## Pads the text on the right to `width` columns, aligning it left.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{clw, RenderMode};
+    ///
+    /// assert_eq!(clw("name").text_blue().pad_right(6).to_string_for(RenderMode::Test), "[fg=Blue]name[/]  ");
+    /// ```
+    pub fn pad_right(self, width: usize) -> Self {
+        self.width(width).align(Align::Left)
+    }
+
+    /// ## Pads the text on both sides to `width` columns, centering it.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{clw, RenderMode};
+    ///
+    /// assert_eq!(clw("日本").center(7).to_string_for(RenderMode::Plain), " 日本  ");
+    /// ```
+    pub fn center(self, width: usize) -> Self {
+        self.width(width).align(Align::Center)
+    }
+
+    /// ## Returns the number of spaces to write before and after the text.
+    pub(crate) fn padding(&self) -> (usize, usize) {
+        let Some(width) = self.width else {
+            return (0, 0);
+        };
+
+        let extra = width.saturating_sub(text_width(&self.value));
+        match self.align {
+            Align::Left => (0, extra),
+            Align::Right => (extra, 0),
+            Align::Center => (extra / 2, extra - extra / 2),
+        }
+    }
+}
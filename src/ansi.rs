@@ -0,0 +1,551 @@
+//! Typed builders for raw ANSI escape sequences.
+//!
+//! These cover the sequences the high-level API doesn't, without having to
+//! hand-write byte strings. Every builder implements `Display`.
+//!
+//! ```
+//! use clwind::ansi::{Csi, Osc};
+//!
+//! // Move the cursor up two lines.
+//! assert_eq!(Csi::new('A').param(2).to_string(), "\x1b[2A");
+//! // Set the window title.
+//! assert_eq!(Osc::new(2).arg("build").to_string(), "\x1b]2;build\x1b\\");
+//! ```
+
+use std::fmt;
+
+use crate::{Color, Style, StyleSpec, BASE_COLORS, CLW};
+
+/// The escape character that starts every sequence.
+pub const ESC: char = '\x1b';
+
+/// How a string sequence (OSC, DCS) is terminated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Terminator {
+    /// The String Terminator, `ESC \`.
+    #[default]
+    St,
+    /// The BEL character, which older terminals expect for OSC.
+    Bel,
+}
+
+impl fmt::Display for Terminator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Terminator::St => write!(f, "{}\\", ESC),
+            Terminator::Bel => f.write_str("\x07"),
+        }
+    }
+}
+
+/// A Control Sequence Introducer sequence, e.g. `ESC [ 1 ; 31 m`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Csi {
+    private: Option<char>,
+    params: Vec<Vec<u16>>,
+    intermediates: String,
+    final_byte: char,
+}
+
+impl Csi {
+    /// ## Creates a sequence ending in the given final byte.
+    ///
+    /// ### Arguments
+    ///
+    /// * `final_byte` - The command character, in the `@`..=`~` range
+    ///
+    /// ### Panics
+    ///
+    /// Panics if `final_byte` is not a valid CSI final byte.
+    pub fn new(final_byte: char) -> Self {
+        assert!(
+            ('\x40'..='\x7e').contains(&final_byte),
+            "invalid CSI final byte {:?}",
+            final_byte
+        );
+        Csi {
+            private: None,
+            params: Vec::new(),
+            intermediates: String::new(),
+            final_byte,
+        }
+    }
+
+    /// ## Creates a Select Graphic Rendition (`m`) sequence.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::ansi::Csi;
+    ///
+    /// assert_eq!(Csi::sgr().param(1).param(31).to_string(), "\x1b[1;31m");
+    /// ```
+    pub fn sgr() -> Self {
+        Csi::new('m')
+    }
+
+    /// ## Sets the private marker, e.g. `?` in `ESC [ ? 25 l`.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if `marker` is not one of `<`, `=`, `>`, `?`.
+    pub fn private(mut self, marker: char) -> Self {
+        assert!(
+            ('\x3c'..='\x3f').contains(&marker),
+            "invalid CSI private marker {:?}",
+            marker
+        );
+        self.private = Some(marker);
+        self
+    }
+
+    /// ## Appends a `;`-separated numeric parameter.
+    pub fn param(mut self, value: u16) -> Self {
+        self.params.push(vec![value]);
+        self
+    }
+
+    /// ## Appends a parameter made of `:`-separated sub-parameters, e.g. `4:3`.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::ansi::Csi;
+    ///
+    /// assert_eq!(Csi::sgr().sub_params(&[4, 3]).to_string(), "\x1b[4:3m");
+    /// ```
+    pub fn sub_params(mut self, values: &[u16]) -> Self {
+        self.params.push(values.to_vec());
+        self
+    }
+
+    /// ## Appends an intermediate byte, e.g. the space in `ESC [ 2 SP q`.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if `byte` is not in the ` `..=`/` range.
+    pub fn intermediate(mut self, byte: char) -> Self {
+        assert!(
+            ('\x20'..='\x2f').contains(&byte),
+            "invalid CSI intermediate byte {:?}",
+            byte
+        );
+        self.intermediates.push(byte);
+        self
+    }
+}
+
+impl fmt::Display for Csi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}[", ESC)?;
+        if let Some(marker) = self.private {
+            write!(f, "{}", marker)?;
+        }
+
+        for (i, param) in self.params.iter().enumerate() {
+            if i > 0 {
+                f.write_str(";")?;
+            }
+            for (j, value) in param.iter().enumerate() {
+                if j > 0 {
+                    f.write_str(":")?;
+                }
+                write!(f, "{}", value)?;
+            }
+        }
+
+        write!(f, "{}{}", self.intermediates, self.final_byte)
+    }
+}
+
+/// An Operating System Command sequence, e.g. `ESC ] 2 ; title ESC \`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Osc {
+    command: u16,
+    args: Vec<String>,
+    terminator: Terminator,
+}
+
+impl Osc {
+    /// ## Creates a sequence for the given command number.
+    ///
+    /// ### Arguments
+    ///
+    /// * `command` - The OSC number, e.g. `8` for hyperlinks
+    pub fn new(command: u16) -> Self {
+        Osc {
+            command,
+            args: Vec::new(),
+            terminator: Terminator::default(),
+        }
+    }
+
+    /// ## Appends a `;`-separated argument.
+    ///
+    /// Control characters are removed so the argument can't end the sequence early.
+    pub fn arg<S: AsRef<str>>(mut self, arg: S) -> Self {
+        self.args.push(strip_controls(arg.as_ref()));
+        self
+    }
+
+    /// ## Sets how the sequence is terminated.
+    pub fn terminator(mut self, terminator: Terminator) -> Self {
+        self.terminator = terminator;
+        self
+    }
+}
+
+impl fmt::Display for Osc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}]{}", ESC, self.command)?;
+        for arg in &self.args {
+            write!(f, ";{}", arg)?;
+        }
+        write!(f, "{}", self.terminator)
+    }
+}
+
+/// A Device Control String sequence, e.g. `ESC P 1 $ r ... ESC \`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Dcs {
+    params: Vec<u16>,
+    intermediates: String,
+    final_byte: char,
+    data: String,
+}
+
+impl Dcs {
+    /// ## Creates a sequence ending its header in the given final byte.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if `final_byte` is not in the `@`..=`~` range.
+    pub fn new(final_byte: char) -> Self {
+        assert!(
+            ('\x40'..='\x7e').contains(&final_byte),
+            "invalid DCS final byte {:?}",
+            final_byte
+        );
+        Dcs {
+            params: Vec::new(),
+            intermediates: String::new(),
+            final_byte,
+            data: String::new(),
+        }
+    }
+
+    /// ## Appends a `;`-separated numeric parameter.
+    pub fn param(mut self, value: u16) -> Self {
+        self.params.push(value);
+        self
+    }
+
+    /// ## Appends an intermediate byte.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if `byte` is not in the ` `..=`/` range.
+    pub fn intermediate(mut self, byte: char) -> Self {
+        assert!(
+            ('\x20'..='\x2f').contains(&byte),
+            "invalid DCS intermediate byte {:?}",
+            byte
+        );
+        self.intermediates.push(byte);
+        self
+    }
+
+    /// ## Sets the data string carried by the sequence.
+    ///
+    /// Control characters are removed so the data can't end the sequence early.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::ansi::Dcs;
+    ///
+    /// let query = Dcs::new('q').intermediate('$').data("m");
+    ///
+    /// assert_eq!(query.to_string(), "\x1bP$qm\x1b\\");
+    /// ```
+    pub fn data<S: AsRef<str>>(mut self, data: S) -> Self {
+        self.data = strip_controls(data.as_ref());
+        self
+    }
+}
+
+impl fmt::Display for Dcs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}P", ESC)?;
+        for (i, param) in self.params.iter().enumerate() {
+            if i > 0 {
+                f.write_str(";")?;
+            }
+            write!(f, "{}", param)?;
+        }
+        write!(
+            f,
+            "{}{}{}{}",
+            self.intermediates,
+            self.final_byte,
+            self.data,
+            Terminator::St
+        )
+    }
+}
+
+/// ## Removes C0/C1 control characters from a string sequence argument.
+fn strip_controls(value: &str) -> String {
+    value.chars().filter(|c| !c.is_control()).collect()
+}
+
+/// ## Removes escape sequences from `text`, keeping only the visible characters.
+///
+/// Removes CSI sequences such as colors and cursor movement, string sequences
+/// such as OSC hyperlinks and titles, and two-character escapes. Other control
+/// characters, like `\n` and `\t`, are kept.
+///
+/// ### Arguments
+///
+/// * `text` - The text to clean, e.g. before writing it to a log file
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::strip_ansi;
+///
+/// assert_eq!(strip_ansi("\x1b[1;38;2;255;0;0merror\x1b[0m"), "error");
+/// assert_eq!(strip_ansi("\x1b]8;;https://example.com\x1b\\docs\x1b]8;;\x07"), "docs");
+/// assert_eq!(strip_ansi("[1/2]\x1b[2K\r\x1b[1A[2/2]\n"), "[1/2]\r[2/2]\n");
+/// assert_eq!(strip_ansi("\x1b7saved\x1b8 \x1b(Bascii"), "saved ascii");
+/// ```
+pub fn strip_ansi(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut start = 0;
+    while let Some(i) = text[start..].find(ESC) {
+        out.push_str(&text[start..start + i]);
+        start = sequence_end(text, start + i);
+    }
+    out.push_str(&text[start..]);
+    out
+}
+
+/// ## Returns the byte index just past the escape sequence starting at `start`.
+///
+/// Recognizes CSI sequences, string sequences (OSC, DCS, SOS, PM, APC)
+/// terminated by BEL or `ESC \`, escapes with intermediate bytes such as
+/// `ESC ( B`, and two-character escapes. Unterminated sequences run to the end.
+pub(crate) fn sequence_end(text: &str, start: usize) -> usize {
+    let mut chars = text[start..].char_indices().skip(1).peekable();
+    match chars.next() {
+        Some((_, '[')) => {
+            for (_, c) in chars.by_ref() {
+                if ('\x40'..='\x7e').contains(&c) {
+                    break;
+                }
+            }
+        }
+        Some((_, ']' | 'P' | 'X' | '^' | '_')) => {
+            while let Some((_, c)) = chars.next() {
+                if c == '\x07' {
+                    break;
+                }
+                if c == ESC && chars.peek().map(|&(_, c)| c) == Some('\\') {
+                    chars.next();
+                    break;
+                }
+            }
+        }
+        Some((_, ' '..='/')) => {
+            for (_, c) in chars.by_ref() {
+                if !(' '..='/').contains(&c) {
+                    break;
+                }
+            }
+        }
+        _ => {}
+    }
+    chars.peek().map_or(text.len(), |&(i, _)| start + i)
+}
+
+/// A run of text and the style it was written with, as decoded by [`parse_ansi`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Segment<'a> {
+    /// The visible text, borrowed from the parsed string.
+    pub text: &'a str,
+    /// The style active for the whole run.
+    pub style: StyleSpec,
+}
+
+impl<'a> From<Segment<'a>> for CLW<'a> {
+    fn from(segment: Segment<'a>) -> Self {
+        segment.style.apply(segment.text)
+    }
+}
+
+/// ## Decodes styled text back into runs of text and their styles.
+///
+/// SGR sequences update the style as a terminal would, including resets and
+/// 256 and RGB colors. Other escape sequences, such as cursor movement and
+/// hyperlinks, are dropped.
+///
+/// ### Arguments
+///
+/// * `text` - The text to decode, e.g. output captured from another program
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{parse_ansi, Color, Style, StyleSpec};
+///
+/// let segments = parse_ansi("\x1b[1;31merror\x1b[0m: disk full");
+///
+/// assert_eq!(segments[0].text, "error");
+/// assert_eq!(segments[0].style, StyleSpec::new().text(Color::Red).font(Style::Bold));
+/// assert_eq!(segments[1].text, ": disk full");
+/// assert_eq!(segments[1].style, StyleSpec::new());
+///
+/// let unknown = parse_ansi("\x1b[1;31mA\x1b[300;38;5;256mB\x1b[38:2::0:128:255mC");
+///
+/// assert_eq!(unknown[1].style, unknown[0].style);
+/// assert_eq!(unknown[2].style, StyleSpec::new().text(Color::Rgb(0, 128, 255)).font(Style::Bold));
+/// ```
+pub fn parse_ansi(text: &str) -> Vec<Segment<'_>> {
+    let mut segments = Vec::new();
+    let mut style = StyleSpec::new();
+    let mut start = 0;
+    while let Some(i) = text[start..].find(ESC) {
+        let escape = start + i;
+        if start < escape {
+            segments.push(Segment {
+                text: &text[start..escape],
+                style: style.clone(),
+            });
+        }
+
+        start = sequence_end(text, escape);
+        let sequence = &text[escape..start];
+        if let Some(params) = sequence
+            .strip_prefix("\x1b[")
+            .and_then(|s| s.strip_suffix('m'))
+        {
+            apply_sgr(&mut style, params);
+        }
+    }
+
+    if start < text.len() {
+        segments.push(Segment {
+            text: &text[start..],
+            style,
+        });
+    }
+    segments
+}
+
+/// ## Updates a style from the parameters of an SGR sequence, e.g. `1;31`.
+///
+/// Empty parameters mean `0`. Unknown codes and colors out of range are
+/// skipped, leaving the style as it was.
+pub(crate) fn apply_sgr(style: &mut StyleSpec, params: &str) {
+    let mut codes = params.split(';');
+    while let Some(code) = codes.next() {
+        let mut parts = code.split(':');
+        let first = match parts.next().unwrap_or("") {
+            "" => 0,
+            first => match first.parse::<u16>() {
+                Ok(first) => first,
+                Err(_) => continue,
+            },
+        };
+        match first {
+            0 => *style = StyleSpec::new(),
+            4 => {
+                let underline = match parts.next().map(str::parse::<u16>) {
+                    Some(Ok(0)) => None,
+                    Some(Ok(2)) => Some(Style::DoubleUnderline),
+                    Some(Ok(3)) => Some(Style::CurlyUnderline),
+                    Some(Ok(4)) => Some(Style::DottedUnderline),
+                    Some(Ok(5)) => Some(Style::DashedUnderline),
+                    _ => Some(Style::Underline),
+                };
+                remove_underlines(style);
+                style.font.extend(underline);
+            }
+            1 | 2 | 3 | 5 | 7 | 8 | 9 | 53 => style.font.push(match first {
+                1 => Style::Bold,
+                2 => Style::Dim,
+                3 => Style::Italic,
+                5 => Style::Blink,
+                7 => Style::Reverse,
+                8 => Style::Hidden,
+                9 => Style::Strikethrough,
+                _ => Style::Overline,
+            }),
+            22 => style.font.retain(|&s| s != Style::Bold && s != Style::Dim),
+            23 => style.font.retain(|&s| s != Style::Italic),
+            24 => remove_underlines(style),
+            25 => style.font.retain(|&s| s != Style::Blink),
+            27 => style.font.retain(|&s| s != Style::Reverse),
+            28 => style.font.retain(|&s| s != Style::Hidden),
+            29 => style.font.retain(|&s| s != Style::Strikethrough),
+            55 => style.font.retain(|&s| s != Style::Overline),
+            30..=37 => style.text = Some(BASE_COLORS[(first - 30) as usize]),
+            90..=97 => style.text = Some(BASE_COLORS[(first - 82) as usize]),
+            40..=47 => style.bg = Some(BASE_COLORS[(first - 40) as usize]),
+            100..=107 => style.bg = Some(BASE_COLORS[(first - 92) as usize]),
+            39 => style.text = None,
+            49 => style.bg = None,
+            59 => style.underline = None,
+            38 | 48 | 58 => {
+                let color = match parts.next() {
+                    Some(mode) => extended_color(mode, &parts.collect::<Vec<_>>()),
+                    None => {
+                        let mode = codes.next().unwrap_or("");
+                        let count = match mode {
+                            "2" => 3,
+                            "5" => 1,
+                            _ => 0,
+                        };
+                        extended_color(mode, &codes.by_ref().take(count).collect::<Vec<_>>())
+                    }
+                };
+                let Some(color) = color else {
+                    continue;
+                };
+                match first {
+                    38 => style.text = Some(color),
+                    48 => style.bg = Some(color),
+                    _ => style.underline = Some(color),
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// ## Decodes the arguments of a 256 or RGB color code.
+///
+/// RGB colors separated by colons may start with a color space id, possibly
+/// empty, as in `38:2::255:0:0`.
+fn extended_color(mode: &str, args: &[&str]) -> Option<Color> {
+    let byte = |arg: &str| arg.parse::<u8>().ok();
+    match (mode, args) {
+        ("5", [n]) => Some(Color::Color256(byte(n)?)),
+        ("2", [r, g, b] | [_, r, g, b, ..]) => Some(Color::Rgb(byte(r)?, byte(g)?, byte(b)?)),
+        _ => None,
+    }
+}
+
+/// ## Removes every kind of underline from a style.
+fn remove_underlines(style: &mut StyleSpec) {
+    style.font.retain(|s| {
+        !matches!(
+            s,
+            Style::Underline
+                | Style::DoubleUnderline
+                | Style::CurlyUnderline
+                | Style::DottedUnderline
+                | Style::DashedUnderline
+        )
+    });
+}
@@ -0,0 +1,205 @@
+//! Bordered notices for announcements that must not be missed.
+
+use std::fmt;
+
+use crate::link::hyperlinks_supported;
+use crate::osc;
+use crate::symbols::unicode_supported;
+use crate::text::wrap_words;
+use crate::{
+    colors_enabled, terminal_width, text_width, AnsiRenderer, Color, Renderer, Style, StyleSpec,
+};
+
+/// A notice drawn in a colored box, with its lines wrapped to fit.
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{banner::Banner, Color, PlainRenderer, StyleSpec};
+///
+/// let notice = Banner::new("Note", StyleSpec::new().bg(Color::Blue))
+///     .line("Builds are now cached.")
+///     .ascii(true)
+///     .width(30)
+///     .render_with(&PlainRenderer);
+///
+/// assert_eq!(
+///     notice,
+///     "+------------------------+\n\
+///      | Note                   |\n\
+///      | Builds are now cached. |\n\
+///      +------------------------+"
+/// );
+/// ```
+pub struct Banner {
+    title: String,
+    lines: Vec<String>,
+    link: Option<String>,
+    spec: StyleSpec,
+    width: Option<usize>,
+    ascii: Option<bool>,
+}
+
+impl Banner {
+    /// ## Creates a banner with a title and the style of the whole box.
+    ///
+    /// ### Arguments
+    ///
+    /// * `title` - The first line, shown in bold
+    /// * `spec` - The colors of the box, usually with a background
+    pub fn new<S: Into<String>>(title: S, spec: StyleSpec) -> Self {
+        Banner {
+            title: title.into(),
+            lines: Vec::new(),
+            link: None,
+            spec,
+            width: None,
+            ascii: None,
+        }
+    }
+
+    /// ## Adds a paragraph, wrapped to the width of the box.
+    pub fn line<S: Into<String>>(mut self, text: S) -> Self {
+        self.lines.push(text.into());
+        self
+    }
+
+    /// ## Adds a link shown as the last line, clickable where hyperlinks are supported.
+    pub fn link<S: Into<String>>(mut self, url: S) -> Self {
+        self.link = Some(url.into());
+        self
+    }
+
+    /// ## Sets the widest the box may be.
+    ///
+    /// Defaults to the width of the terminal.
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// ## Draws the border with ASCII characters instead of box-drawing ones.
+    ///
+    /// Defaults to ASCII when Unicode isn't supported.
+    pub fn ascii(mut self, ascii: bool) -> Self {
+        self.ascii = Some(ascii);
+        self
+    }
+
+    /// ## Renders the banner with ANSI escape sequences.
+    pub fn render(&self) -> String {
+        self.draw(&AnsiRenderer, colors_enabled() && hyperlinks_supported())
+    }
+
+    /// ## Renders the banner through the given renderer.
+    ///
+    /// ### Arguments
+    ///
+    /// * `renderer` - The backend used to style the box
+    pub fn render_with(&self, renderer: &dyn Renderer) -> String {
+        self.draw(renderer, false)
+    }
+
+    /// ## Draws the box, wrapping the link in an OSC 8 hyperlink if `hyperlink` is set.
+    fn draw(&self, renderer: &dyn Renderer, hyperlink: bool) -> String {
+        let (corners, horizontal, vertical) = match self.ascii.unwrap_or(!unicode_supported()) {
+            true => (["+", "+", "+", "+"], "-", "|"),
+            false => (["┌", "┐", "└", "┘"], "─", "│"),
+        };
+
+        let max_inner = self.width.unwrap_or_else(terminal_width).max(5) - 4;
+        let mut rows: Vec<(String, bool)> = vec![(self.title.clone(), true)];
+        for line in &self.lines {
+            rows.extend(wrap_words(line, max_inner).into_iter().map(|l| (l, false)));
+        }
+        if let Some(url) = &self.link {
+            rows.push((url.clone(), false));
+        }
+        let inner = rows.iter().map(|(r, _)| text_width(r)).max().unwrap_or(0);
+
+        let bold = self.spec.clone().font(Style::Bold);
+        let border = |left: &str, right: &str| {
+            let line = format!("{}{}{}", left, horizontal.repeat(inner + 2), right);
+            renderer.paint(&line, &self.spec)
+        };
+
+        let mut out = vec![border(corners[0], corners[1])];
+        let last = rows.len() - 1;
+        for (i, (row, is_title)) in rows.iter().enumerate() {
+            let padding = " ".repeat(inner - text_width(row));
+            let text = match is_title {
+                true => renderer.paint(row, &bold),
+                false => renderer.paint(row, &self.spec),
+            };
+            let text = match (hyperlink, &self.link) {
+                (true, Some(url)) if i == last => format!(
+                    "{}{}{}",
+                    osc::hyperlink(url, None),
+                    text,
+                    osc::hyperlink_end()
+                ),
+                _ => text,
+            };
+            out.push(format!(
+                "{}{}{}",
+                renderer.paint(&format!("{} ", vertical), &self.spec),
+                text,
+                renderer.paint(&format!("{} {}", padding, vertical), &self.spec)
+            ));
+        }
+        out.push(border(corners[2], corners[3]));
+        out.join("\n")
+    }
+}
+
+impl fmt::Display for Banner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render())
+    }
+}
+
+/// ## Creates a black-on-yellow warning banner with the given paragraphs.
+///
+/// ### Arguments
+///
+/// * `lines` - The paragraphs of the warning
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::banner;
+///
+/// println!("{}", banner::warning(&["The config format changes in 2.0.", "Run `tool migrate` to update it."]));
+/// ```
+pub fn warning(lines: &[&str]) -> Banner {
+    let spec = StyleSpec::new().text(Color::Black).bg(Color::Yellow);
+    lines
+        .iter()
+        .fold(Banner::new("Warning", spec), |banner, line| {
+            banner.line(*line)
+        })
+}
+
+/// ## Creates a white-on-red deprecation banner, with a link to more information.
+///
+/// ### Arguments
+///
+/// * `message` - What is deprecated and what to use instead
+/// * `link` - A URL with details, if any
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::banner;
+///
+/// let notice = banner::deprecation("`--legacy` will be removed.", Some("https://example.com/migrate"));
+/// println!("{}", notice);
+/// ```
+pub fn deprecation(message: &str, link: Option<&str>) -> Banner {
+    let spec = StyleSpec::new().text(Color::BrightWhite).bg(Color::Red);
+    let banner = Banner::new("Deprecated", spec).line(message);
+    match link {
+        Some(url) => banner.link(url),
+        None => banner,
+    }
+}
@@ -0,0 +1,179 @@
+//! C interface to the styling core, enabled by the `capi` feature.
+//!
+//! The matching declarations in `include/clwind.h` are generated from this file
+//! with `cbindgen --config cbindgen.toml --output include/clwind.h src/capi.rs`.
+//! Colors are passed as `int32_t`: [`CLW_COLOR_NONE`] for no color, `0..=255`
+//! for a palette index (the first 16 being the base colors), or
+//! `CLW_RGB(r, g, b)` for truecolor.
+//!
+//! ```
+//! use std::ffi::{CStr, CString};
+//! use clwind::capi::{clw_string_free, clw_style, CLW_BOLD, CLW_COLOR_NONE};
+//! use clwind::set_colors_enabled;
+//!
+//! set_colors_enabled(true);
+//! let text = CString::new("hi").unwrap();
+//!
+//! unsafe {
+//!     let plain = clw_style(text.as_ptr(), CLW_COLOR_NONE, CLW_COLOR_NONE, 0);
+//!     assert_eq!(CStr::from_ptr(plain).to_str().unwrap(), "hi");
+//!     clw_string_free(plain);
+//!
+//!     let bold = clw_style(text.as_ptr(), CLW_COLOR_NONE, CLW_COLOR_NONE, CLW_BOLD);
+//!     assert_eq!(CStr::from_ptr(bold).to_str().unwrap(), "\x1b[1mhi\x1b[0m");
+//!     clw_string_free(bold);
+//! }
+//! ```
+
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+
+use crate::{Color, Style, StyleSpec, BASE_COLORS};
+
+/// Leaves the text or background color unset.
+pub const CLW_COLOR_NONE: i32 = -1;
+/// Marks a color as a 24-bit `0xRRGGBB` value.
+pub const CLW_COLOR_RGB_FLAG: i32 = 0x0100_0000;
+
+/// Palette index of [`Color::Black`].
+pub const CLW_BLACK: i32 = 0;
+/// Palette index of [`Color::Red`].
+pub const CLW_RED: i32 = 1;
+/// Palette index of [`Color::Green`].
+pub const CLW_GREEN: i32 = 2;
+/// Palette index of [`Color::Yellow`].
+pub const CLW_YELLOW: i32 = 3;
+/// Palette index of [`Color::Blue`].
+pub const CLW_BLUE: i32 = 4;
+/// Palette index of [`Color::Magenta`].
+pub const CLW_MAGENTA: i32 = 5;
+/// Palette index of [`Color::Cyan`].
+pub const CLW_CYAN: i32 = 6;
+/// Palette index of [`Color::White`].
+pub const CLW_WHITE: i32 = 7;
+/// Palette index of [`Color::BrightBlack`].
+pub const CLW_BRIGHT_BLACK: i32 = 8;
+/// Palette index of [`Color::BrightRed`].
+pub const CLW_BRIGHT_RED: i32 = 9;
+/// Palette index of [`Color::BrightGreen`].
+pub const CLW_BRIGHT_GREEN: i32 = 10;
+/// Palette index of [`Color::BrightYellow`].
+pub const CLW_BRIGHT_YELLOW: i32 = 11;
+/// Palette index of [`Color::BrightBlue`].
+pub const CLW_BRIGHT_BLUE: i32 = 12;
+/// Palette index of [`Color::BrightMagenta`].
+pub const CLW_BRIGHT_MAGENTA: i32 = 13;
+/// Palette index of [`Color::BrightCyan`].
+pub const CLW_BRIGHT_CYAN: i32 = 14;
+/// Palette index of [`Color::BrightWhite`].
+pub const CLW_BRIGHT_WHITE: i32 = 15;
+
+/// Flag for [`Style::Bold`].
+pub const CLW_BOLD: u32 = 1 << 0;
+/// Flag for [`Style::Dim`].
+pub const CLW_DIM: u32 = 1 << 1;
+/// Flag for [`Style::Italic`].
+pub const CLW_ITALIC: u32 = 1 << 2;
+/// Flag for [`Style::Underline`].
+pub const CLW_UNDERLINE: u32 = 1 << 3;
+/// Flag for [`Style::Blink`].
+pub const CLW_BLINK: u32 = 1 << 4;
+/// Flag for [`Style::Reverse`].
+pub const CLW_REVERSE: u32 = 1 << 5;
+/// Flag for [`Style::Hidden`].
+pub const CLW_HIDDEN: u32 = 1 << 6;
+/// Flag for [`Style::Strikethrough`].
+pub const CLW_STRIKETHROUGH: u32 = 1 << 7;
+/// Flag for [`Style::DoubleUnderline`].
+pub const CLW_DOUBLE_UNDERLINE: u32 = 1 << 8;
+/// Flag for [`Style::CurlyUnderline`].
+pub const CLW_CURLY_UNDERLINE: u32 = 1 << 9;
+/// Flag for [`Style::DottedUnderline`].
+pub const CLW_DOTTED_UNDERLINE: u32 = 1 << 10;
+/// Flag for [`Style::DashedUnderline`].
+pub const CLW_DASHED_UNDERLINE: u32 = 1 << 11;
+/// Flag for [`Style::Overline`].
+pub const CLW_OVERLINE: u32 = 1 << 12;
+
+/// The style for each flag, in bit order.
+const FLAG_STYLES: [(u32, Style); 13] = [
+    (CLW_BOLD, Style::Bold),
+    (CLW_DIM, Style::Dim),
+    (CLW_ITALIC, Style::Italic),
+    (CLW_UNDERLINE, Style::Underline),
+    (CLW_BLINK, Style::Blink),
+    (CLW_REVERSE, Style::Reverse),
+    (CLW_HIDDEN, Style::Hidden),
+    (CLW_STRIKETHROUGH, Style::Strikethrough),
+    (CLW_DOUBLE_UNDERLINE, Style::DoubleUnderline),
+    (CLW_CURLY_UNDERLINE, Style::CurlyUnderline),
+    (CLW_DOTTED_UNDERLINE, Style::DottedUnderline),
+    (CLW_DASHED_UNDERLINE, Style::DashedUnderline),
+    (CLW_OVERLINE, Style::Overline),
+];
+
+/// ## Styles a NUL-terminated string, returning a newly allocated string.
+///
+/// Returns `NULL` when `text` is `NULL`. Invalid UTF-8 is replaced with `U+FFFD`.
+/// The result must be released with [`clw_string_free`].
+///
+/// # Safety
+///
+/// `text` must be `NULL` or point to a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn clw_style(
+    text: *const c_char,
+    fg: i32,
+    bg: i32,
+    flags: u32,
+) -> *mut c_char {
+    if text.is_null() {
+        return ptr::null_mut();
+    }
+
+    let text = CStr::from_ptr(text).to_string_lossy();
+    let mut spec = StyleSpec::new();
+    if let Some(color) = decode_color(fg) {
+        spec = spec.text(color);
+    }
+
+    if let Some(color) = decode_color(bg) {
+        spec = spec.bg(color);
+    }
+
+    for (flag, style) in FLAG_STYLES {
+        if flags & flag != 0 {
+            spec = spec.font(style);
+        }
+    }
+
+    CString::new(spec.apply(text).to_string())
+        .map(CString::into_raw)
+        .unwrap_or(ptr::null_mut())
+}
+
+/// ## Releases a string returned by [`clw_style`].
+///
+/// # Safety
+///
+/// `text` must be `NULL` or a pointer returned by [`clw_style`] that has not
+/// been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn clw_string_free(text: *mut c_char) {
+    if !text.is_null() {
+        drop(CString::from_raw(text));
+    }
+}
+
+/// ## Decodes the `int32_t` color representation used by the C interface.
+///
+/// Negative values, [`CLW_COLOR_NONE`] among them, and values with bits set
+/// above the RGB flag are no color.
+fn decode_color(color: i32) -> Option<Color> {
+    match color {
+        CLW_BLACK..=CLW_BRIGHT_WHITE => Some(BASE_COLORS[color as usize]),
+        16..=255 => Some(Color::Color256(color as u8)),
+        _ if color >> 24 == 1 => Some(Color::Hex((color & 0xFF_FFFF) as u32)),
+        _ => None,
+    }
+}
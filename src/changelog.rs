@@ -0,0 +1,306 @@
+//! Release notes rendering, for `mytool --changelog` style commands.
+
+use std::fmt;
+
+use crate::text::wrap_words;
+use crate::{terminal_width, theme, AnsiRenderer, Color, Renderer, Style, StyleSpec};
+
+/// The kind of a change, shown as a badge heading its group.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ChangeKind {
+    Breaking,
+    Added,
+    Changed,
+    Deprecated,
+    Removed,
+    Fixed,
+    Security,
+}
+
+/// Every kind, in the order groups are rendered.
+const KINDS: [ChangeKind; 7] = [
+    ChangeKind::Breaking,
+    ChangeKind::Added,
+    ChangeKind::Changed,
+    ChangeKind::Deprecated,
+    ChangeKind::Removed,
+    ChangeKind::Fixed,
+    ChangeKind::Security,
+];
+
+impl ChangeKind {
+    /// ## Returns the badge label of the kind.
+    pub fn label(self) -> &'static str {
+        match self {
+            ChangeKind::Breaking => "BREAKING",
+            ChangeKind::Added => "ADDED",
+            ChangeKind::Changed => "CHANGED",
+            ChangeKind::Deprecated => "DEPRECATED",
+            ChangeKind::Removed => "REMOVED",
+            ChangeKind::Fixed => "FIXED",
+            ChangeKind::Security => "SECURITY",
+        }
+    }
+
+    /// ## Returns the name of the theme style the badge takes its color from.
+    ///
+    /// Kinds without a matching semantic style return `None` and keep their
+    /// default badge.
+    pub fn theme_name(self) -> Option<&'static str> {
+        match self {
+            ChangeKind::Breaking => Some("error"),
+            ChangeKind::Added => Some("success"),
+            ChangeKind::Deprecated => Some("warning"),
+            ChangeKind::Fixed => Some("info"),
+            ChangeKind::Changed | ChangeKind::Removed | ChangeKind::Security => None,
+        }
+    }
+
+    /// ## Returns the badge style of the kind under the current theme.
+    ///
+    /// The text color of the theme style named by [`ChangeKind::theme_name`]
+    /// becomes the background of the badge. Without that style or its text
+    /// color, the [`ChangeKind::default_style`] is used.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{changelog::ChangeKind, theme::{set_theme, Theme}, Color, Style, StyleSpec};
+    ///
+    /// set_theme(Theme::default().success(StyleSpec::new().text(Color::Rgb(0, 200, 80))));
+    ///
+    /// assert_eq!(
+    ///     ChangeKind::Added.themed_style(),
+    ///     StyleSpec::new().text(Color::Black).bg(Color::Rgb(0, 200, 80)).font(Style::Bold)
+    /// );
+    /// assert_eq!(ChangeKind::Removed.themed_style(), ChangeKind::Removed.default_style());
+    /// ```
+    pub fn themed_style(self) -> StyleSpec {
+        let color = self
+            .theme_name()
+            .and_then(theme::current_style)
+            .and_then(|spec| spec.text_color());
+        match color {
+            Some(color) => StyleSpec::new()
+                .text(Color::Black)
+                .bg(color)
+                .font(Style::Bold),
+            None => self.default_style(),
+        }
+    }
+
+    /// ## Returns the badge style of the kind when the theme doesn't set one.
+    pub fn default_style(self) -> StyleSpec {
+        let bg = match self {
+            ChangeKind::Breaking => Color::Red,
+            ChangeKind::Added => Color::Green,
+            ChangeKind::Changed => Color::Blue,
+            ChangeKind::Deprecated => Color::Yellow,
+            ChangeKind::Removed => Color::Magenta,
+            ChangeKind::Fixed => Color::Cyan,
+            ChangeKind::Security => Color::BrightRed,
+        };
+        StyleSpec::new().text(Color::Black).bg(bg).font(Style::Bold)
+    }
+}
+
+/// A released version and its changes.
+pub struct Release {
+    version: String,
+    date: Option<String>,
+    changes: Vec<(ChangeKind, String)>,
+}
+
+impl Release {
+    /// ## Creates a release without changes.
+    ///
+    /// ### Arguments
+    ///
+    /// * `version` - The version, e.g. `1.2.0`
+    pub fn new(version: &str) -> Self {
+        Release {
+            version: version.to_string(),
+            date: None,
+            changes: Vec::new(),
+        }
+    }
+
+    /// ## Sets the release date.
+    pub fn date(mut self, date: &str) -> Self {
+        self.date = Some(date.to_string());
+        self
+    }
+
+    /// ## Adds a change of the given kind.
+    ///
+    /// ### Arguments
+    ///
+    /// * `kind` - The kind of the change
+    /// * `text` - The description of the change
+    pub fn change(mut self, kind: ChangeKind, text: &str) -> Self {
+        self.changes.push((kind, text.to_string()));
+        self
+    }
+
+    /// ## Adds a breaking change.
+    pub fn breaking(self, text: &str) -> Self {
+        self.change(ChangeKind::Breaking, text)
+    }
+
+    /// ## Adds a new feature.
+    pub fn added(self, text: &str) -> Self {
+        self.change(ChangeKind::Added, text)
+    }
+
+    /// ## Adds a change in existing behavior.
+    pub fn changed(self, text: &str) -> Self {
+        self.change(ChangeKind::Changed, text)
+    }
+
+    /// ## Adds a deprecation.
+    pub fn deprecated(self, text: &str) -> Self {
+        self.change(ChangeKind::Deprecated, text)
+    }
+
+    /// ## Adds a removal.
+    pub fn removed(self, text: &str) -> Self {
+        self.change(ChangeKind::Removed, text)
+    }
+
+    /// ## Adds a bug fix.
+    pub fn fixed(self, text: &str) -> Self {
+        self.change(ChangeKind::Fixed, text)
+    }
+
+    /// ## Adds a security fix.
+    pub fn security(self, text: &str) -> Self {
+        self.change(ChangeKind::Security, text)
+    }
+}
+
+/// Renders releases with badges per kind of change and wrapped bullet items.
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::changelog::{Changelog, Release};
+///
+/// let changelog = Changelog::new()
+///     .release(
+///         Release::new("0.2.0")
+///             .date("2024-05-01")
+///             .added("Tables with styled cells.")
+///             .fixed("Hex colors no longer print to stdout."),
+///     )
+///     .width(60);
+///
+/// println!("{}", changelog);
+/// ```
+#[derive(Default)]
+pub struct Changelog {
+    releases: Vec<Release>,
+    badges: Vec<(ChangeKind, StyleSpec)>,
+    width: Option<usize>,
+}
+
+impl Changelog {
+    /// ## Creates an empty changelog.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// ## Adds a release, rendered in insertion order.
+    pub fn release(mut self, release: Release) -> Self {
+        self.releases.push(release);
+        self
+    }
+
+    /// ## Overrides the badge style of a kind of change.
+    ///
+    /// ### Arguments
+    ///
+    /// * `kind` - The kind of change
+    /// * `spec` - The style of its badge
+    pub fn badge(mut self, kind: ChangeKind, spec: StyleSpec) -> Self {
+        self.badges.retain(|(k, _)| *k != kind);
+        self.badges.push((kind, spec));
+        self
+    }
+
+    /// ## Sets the width to wrap at, the terminal width by default.
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// ## Renders the changelog for the terminal.
+    pub fn render(&self) -> String {
+        self.render_with(&AnsiRenderer)
+    }
+
+    /// ## Renders the changelog through the given renderer.
+    ///
+    /// ### Arguments
+    ///
+    /// * `renderer` - The backend used to style the output
+    pub fn render_with(&self, renderer: &dyn Renderer) -> String {
+        let width = self.width.unwrap_or_else(terminal_width);
+        let plain = StyleSpec::new();
+        let mut blocks = Vec::new();
+
+        for release in &self.releases {
+            let mut lines = Vec::new();
+            let mut heading = renderer.paint(
+                &format!("v{}", release.version.trim_start_matches('v')),
+                &StyleSpec::new().font(Style::Bold),
+            );
+            if let Some(date) = &release.date {
+                heading.push_str("  ");
+                heading.push_str(&renderer.paint(date, &StyleSpec::new().font(Style::Dim)));
+            }
+            lines.push(heading);
+
+            for kind in KINDS {
+                let items: Vec<&String> = release
+                    .changes
+                    .iter()
+                    .filter(|(k, _)| *k == kind)
+                    .map(|(_, text)| text)
+                    .collect();
+                if items.is_empty() {
+                    continue;
+                }
+
+                lines.push(String::new());
+                lines.push(renderer.paint(&format!(" {} ", kind.label()), &self.badge_style(kind)));
+                for item in items {
+                    for (i, line) in wrap_words(item, width.saturating_sub(4).max(1))
+                        .iter()
+                        .enumerate()
+                    {
+                        let bullet = if i == 0 { "  • " } else { "    " };
+                        lines.push(format!("{}{}", bullet, renderer.paint(line, &plain)));
+                    }
+                }
+            }
+            blocks.push(lines.join("\n"));
+        }
+
+        blocks.join("\n\n")
+    }
+
+    /// ## Returns the badge style of a kind, honoring overrides and the theme.
+    fn badge_style(&self, kind: ChangeKind) -> StyleSpec {
+        self.badges
+            .iter()
+            .find(|(k, _)| *k == kind)
+            .map(|(_, spec)| spec.clone())
+            .unwrap_or_else(|| kind.themed_style())
+    }
+}
+
+impl fmt::Display for Changelog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render())
+    }
+}
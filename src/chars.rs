@@ -0,0 +1,96 @@
+//! Styling text character by character, for gradients and highlights.
+
+use crate::width::char_width;
+use crate::{StyleSpec, StyledString, CLW};
+
+impl CLW<'_> {
+    /// ## Styles each character with the spec returned by a callback.
+    ///
+    /// The callback gets the index and the character, and its spec is layered
+    /// over the style of the text, so it only needs to set what differs.
+    /// Combining marks and emoji joined by a zero-width joiner stay with the
+    /// character before them, and aren't passed to the callback. Consecutive
+    /// characters with the same style are kept in one segment.
+    ///
+    /// Each segment keeps the link of the text; set a [`CLW::link_id`] so
+    /// terminals treat them as one link. The width, alignment and wrapping of
+    /// the text are not kept, set them on each line of the result instead.
+    ///
+    /// ### Arguments
+    ///
+    /// * `f` - Returns the style of the character at an index
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{clw, Color, RenderMode, StyleSpec};
+    ///
+    /// let caret = 4;
+    /// let line = clw("let x = ;").map_chars(|i, _| match i == caret {
+    ///     true => StyleSpec::new().bg(Color::Red),
+    ///     false => StyleSpec::new(),
+    /// });
+    ///
+    /// assert_eq!(line.to_string_for(RenderMode::Test), "let [bg=Red]x[/] = ;");
+    /// ```
+    ///
+    /// ```
+    /// use clwind::{clw, link::set_hyperlinks, set_colors_enabled, StyleSpec};
+    ///
+    /// set_colors_enabled(true);
+    /// set_hyperlinks(true);
+    ///
+    /// let docs = clw("docs").link("https://example.com").link_id("docs");
+    /// let bold_d = docs.map_chars(|i, _| match i {
+    ///     0 => StyleSpec::new().font(clwind::Style::Bold),
+    ///     _ => StyleSpec::new(),
+    /// });
+    ///
+    /// assert_eq!(bold_d.to_string().matches("\x1b]8;id=docs;https://example.com").count(), 2);
+    /// ```
+    pub fn map_chars<F: FnMut(usize, char) -> StyleSpec>(&self, mut f: F) -> StyledString {
+        let mut out = StyledString::new();
+        let mut run = String::new();
+        let mut run_spec: Option<StyleSpec> = None;
+        let mut index = 0;
+        let mut joined = false;
+        for c in self.value.chars() {
+            let attached = joined || (char_width(c) == 0 && !c.is_control());
+            joined = c == '\u{200d}';
+            if attached && run_spec.is_some() {
+                run.push(c);
+                continue;
+            }
+
+            let spec = f(index, c).over(&self.spec);
+            index += 1;
+            if run_spec.as_ref() != Some(&spec) {
+                if let Some(previous) = run_spec.replace(spec) {
+                    out.push(self.segment(std::mem::take(&mut run), previous));
+                }
+            }
+            run.push(c);
+        }
+
+        if let Some(spec) = run_spec {
+            out.push(self.segment(run, spec));
+        }
+        out
+    }
+
+    /// ## Returns a segment of this text with its own style.
+    ///
+    /// The color choice, link and per-line codes are kept. The width, alignment
+    /// and wrapping apply to the whole text, so they are left out.
+    pub(crate) fn segment(&self, value: String, spec: StyleSpec) -> CLW<'static> {
+        CLW {
+            value: value.into(),
+            spec,
+            link: self.link.clone(),
+            link_id: self.link_id.clone(),
+            choice: self.choice,
+            line_codes: self.line_codes,
+            ..CLW::new(String::new())
+        }
+    }
+}
@@ -0,0 +1,120 @@
+//! Tailwind-style utility classes, parsed at runtime into styles.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Color, Style, StyleSpec, CLW};
+
+/// The error returned for a class that isn't understood.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseClassError {
+    class: String,
+}
+
+impl fmt::Display for ParseClassError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown class {:?}", self.class)
+    }
+}
+
+impl std::error::Error for ParseClassError {}
+
+impl StyleSpec {
+    /// ## Builds a spec from a space-separated string of utility classes.
+    ///
+    /// Supported classes:
+    ///
+    /// * `text-<color>`, `bg-<color>` and `decoration-<color>` set the text,
+    ///   background and underline colors. Colors are anything [`Color`] parses
+    ///   from a string, e.g. `red`, `red-500` or `rebeccapurple`, and arbitrary
+    ///   values in brackets, e.g. `text-[#ff8800]`.
+    /// * `font-<style>` and the bare style names add a style, e.g. `font-bold`,
+    ///   `italic` or `underline`.
+    /// * `line-through`, `invisible` and `decoration-double`, `-wavy`,
+    ///   `-dotted`, `-dashed` follow their Tailwind meaning.
+    ///
+    /// ### Arguments
+    ///
+    /// * `classes` - The classes, separated by whitespace
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{Color, Style, StyleSpec};
+    ///
+    /// let spec = StyleSpec::from_classes("text-red-500 bg-[#0f172a] font-bold").unwrap();
+    ///
+    /// assert_eq!(spec.text_color(), Color::tailwind("red-500"));
+    /// assert_eq!(spec.bg_color(), Some(Color::Rgb(0x0f, 0x17, 0x2a)));
+    /// assert_eq!(spec.fonts(), &[Style::Bold]);
+    /// assert!(StyleSpec::from_classes("text-redd").is_err());
+    /// ```
+    pub fn from_classes(classes: &str) -> Result<StyleSpec, ParseClassError> {
+        classes
+            .split_whitespace()
+            .try_fold(StyleSpec::new(), |spec, class| {
+                apply_class(spec, class).ok_or_else(|| ParseClassError {
+                    class: class.to_string(),
+                })
+            })
+    }
+}
+
+impl CLW<'_> {
+    /// ## Styles the text with a space-separated string of utility classes.
+    ///
+    /// See [`StyleSpec::from_classes`] for the supported classes. Classes that
+    /// aren't understood are ignored; use `from_classes` to report them.
+    ///
+    /// ### Arguments
+    ///
+    /// * `classes` - The classes, separated by whitespace
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{clw, RenderMode};
+    ///
+    /// let hi = clw("hi").classes("text-red bg-black font-bold underline");
+    ///
+    /// assert_eq!(hi.to_string_for(RenderMode::Test), "[fg=Red,bg=Black,Bold,Underline]hi[/]");
+    /// ```
+    pub fn classes(mut self, classes: &str) -> Self {
+        for class in classes.split_whitespace() {
+            if let Some(spec) = apply_class(self.spec.clone(), class) {
+                self.spec = spec;
+            }
+        }
+        self
+    }
+}
+
+/// ## Applies a single class to a spec, or returns `None` if it isn't understood.
+fn apply_class(mut spec: StyleSpec, class: &str) -> Option<StyleSpec> {
+    let style = match class {
+        "line-through" => Some(Style::Strikethrough),
+        "invisible" => Some(Style::Hidden),
+        "decoration-double" => Some(Style::DoubleUnderline),
+        "decoration-wavy" => Some(Style::CurlyUnderline),
+        "decoration-dotted" => Some(Style::DottedUnderline),
+        "decoration-dashed" => Some(Style::DashedUnderline),
+        _ => Style::from_str(class.strip_prefix("font-").unwrap_or(class)).ok(),
+    };
+    if let Some(style) = style {
+        return Some(spec.font(style));
+    }
+
+    let (prefix, value) = class.split_once('-')?;
+    let value = match value.strip_prefix('[') {
+        Some(value) => value.strip_suffix(']')?,
+        None => value,
+    };
+    let color = Color::from_str(value).ok()?;
+    match prefix {
+        "text" => spec.text = Some(color),
+        "bg" => spec.bg = Some(color),
+        "decoration" => spec.underline = Some(color),
+        _ => return None,
+    }
+    Some(spec)
+}
@@ -0,0 +1,118 @@
+//! Styling methods on strings directly, without wrapping them with [`clw`].
+
+use crate::{clw, Color, Style, CLW};
+
+/// Defines trait methods that forward to the [`CLW`] method of the same name.
+macro_rules! forward {
+    ($($name:ident),* $(,)?) => {
+        $(
+            #[doc = concat!("## Same as [`CLW::", stringify!($name), "`].")]
+            fn $name(&self) -> CLW<'_> {
+                self.clw().$name()
+            }
+        )*
+    };
+}
+
+/// Styling methods for anything that is a string, returning a [`CLW`].
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{clw, Colorize};
+///
+/// let owned = String::from("error");
+///
+/// assert_eq!(
+///     "error".text_red().font_bold().to_string(),
+///     clw("error").text_red().font_bold().to_string()
+/// );
+/// assert_eq!(owned.text_red().plain(), "error");
+/// ```
+pub trait Colorize {
+    /// ## Wraps the string in a [`CLW`] without styling it.
+    fn clw(&self) -> CLW<'_>;
+
+    /// ## Same as [`CLW::text`].
+    fn text(&self, color: Color) -> CLW<'_> {
+        self.clw().text(color)
+    }
+
+    /// ## Same as [`CLW::bg`].
+    fn bg(&self, color: Color) -> CLW<'_> {
+        self.clw().bg(color)
+    }
+
+    /// ## Same as [`CLW::font`].
+    fn font(&self, style: Style) -> CLW<'_> {
+        self.clw().font(style)
+    }
+
+    /// ## Same as [`CLW::classes`].
+    fn classes(&self, classes: &str) -> CLW<'_> {
+        self.clw().classes(classes)
+    }
+
+    /// ## Same as [`CLW::style`].
+    fn style(&self, name: &str) -> CLW<'_> {
+        self.clw().style(name)
+    }
+
+    forward!(
+        text_black,
+        text_red,
+        text_green,
+        text_yellow,
+        text_blue,
+        text_magenta,
+        text_cyan,
+        text_white,
+        text_bright_black,
+        text_bright_red,
+        text_bright_green,
+        text_bright_yellow,
+        text_bright_blue,
+        text_bright_magenta,
+        text_bright_cyan,
+        text_bright_white,
+        bg_black,
+        bg_red,
+        bg_green,
+        bg_yellow,
+        bg_blue,
+        bg_magenta,
+        bg_cyan,
+        bg_white,
+        bg_bright_black,
+        bg_bright_red,
+        bg_bright_green,
+        bg_bright_yellow,
+        bg_bright_blue,
+        bg_bright_magenta,
+        bg_bright_cyan,
+        bg_bright_white,
+        font_bold,
+        font_dim,
+        font_italic,
+        font_underline,
+        font_blink,
+        font_reverse,
+        font_hidden,
+        font_strikethrough,
+        font_double_underline,
+        font_curly_underline,
+        font_dotted_underline,
+        font_dashed_underline,
+        font_overline,
+        success,
+        error,
+        warning,
+        info,
+    );
+}
+
+impl<T: AsRef<str> + ?Sized> Colorize for T {
+    fn clw(&self) -> CLW<'_> {
+        clw(self.as_ref())
+    }
+}
@@ -0,0 +1,167 @@
+//! Shell completion scripts with described, and where possible styled, candidates.
+//!
+//! zsh shows each candidate in its own style through `list-colors`. fish and
+//! bash style completions themselves, so only the descriptions (fish) or the
+//! bare words (bash) are generated for them.
+
+use crate::{apply_transform, ColorLevel, StyleSpec};
+
+/// A shell to generate completions for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+/// A completion candidate.
+struct Candidate {
+    word: String,
+    description: String,
+    spec: StyleSpec,
+}
+
+/// Completion candidates for a command, rendered as a script for a shell.
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::completion::{Completions, Shell};
+/// use clwind::{Color, StyleSpec};
+///
+/// let completions = Completions::new("mytool")
+///     .candidate("build", "Compile the project", StyleSpec::new().text(Color::Green))
+///     .candidate("clean", "Remove build artifacts", StyleSpec::new().text(Color::Red));
+///
+/// let script = completions.render(Shell::Fish);
+///
+/// assert!(script.contains("complete -c mytool -f -a build -d 'Compile the project'"));
+/// ```
+pub struct Completions {
+    program: String,
+    candidates: Vec<Candidate>,
+}
+
+impl Completions {
+    /// ## Creates an empty set of completions for a command.
+    ///
+    /// ### Arguments
+    ///
+    /// * `program` - The name of the command being completed
+    pub fn new(program: &str) -> Self {
+        Completions {
+            program: program.to_string(),
+            candidates: Vec::new(),
+        }
+    }
+
+    /// ## Adds a candidate.
+    ///
+    /// ### Arguments
+    ///
+    /// * `word` - The completed word
+    /// * `description` - The hint shown next to the word
+    /// * `spec` - The style of the word, on shells that support it
+    pub fn candidate(mut self, word: &str, description: &str, spec: StyleSpec) -> Self {
+        self.candidates.push(Candidate {
+            word: word.to_string(),
+            description: description.to_string(),
+            spec,
+        });
+        self
+    }
+
+    /// ## Renders the script to be sourced by the given shell.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::completion::{Completions, Shell};
+    /// use clwind::{Color, StyleSpec};
+    ///
+    /// let script = Completions::new("mytool")
+    ///     .candidate("build", "Compile the project", StyleSpec::new().text(Color::Green))
+    ///     .render(Shell::Zsh);
+    ///
+    /// assert!(script.contains("'build:Compile the project'"));
+    /// assert!(script.contains("'=build( *|)=32'"));
+    /// ```
+    pub fn render(&self, shell: Shell) -> String {
+        match shell {
+            Shell::Bash => self.bash(),
+            Shell::Zsh => self.zsh(),
+            Shell::Fish => self.fish(),
+        }
+    }
+
+    /// ## Renders a `complete -W` line with the bare words.
+    fn bash(&self) -> String {
+        let words: Vec<&str> = self.candidates.iter().map(|c| c.word.as_str()).collect();
+        format!(
+            "complete -W {} {}\n",
+            quote(&words.join(" ")),
+            quote(&self.program)
+        )
+    }
+
+    /// ## Renders a `_describe` function and the `list-colors` styles of the words.
+    fn zsh(&self) -> String {
+        let function = format!(
+            "_{}",
+            self.program.replace(|c: char| !c.is_alphanumeric(), "_")
+        );
+        let mut out = format!("{}() {{\n  local -a candidates\n  candidates=(\n", function);
+        for c in &self.candidates {
+            let entry = format!("{}:{}", c.word.replace(':', "\\:"), c.description);
+            out.push_str(&format!("    {}\n", quote(&entry)));
+        }
+        out.push_str("  )\n  _describe 'command' candidates\n}\n");
+
+        let colors: Vec<String> = self
+            .candidates
+            .iter()
+            .filter_map(|c| {
+                let spec = apply_transform(&c.spec);
+                let codes = spec.sgr(ColorLevel::TrueColor);
+                match codes.is_empty() {
+                    true => None,
+                    false => Some(quote(&format!("={}( *|)={}", c.word, codes))),
+                }
+            })
+            .collect();
+        if !colors.is_empty() {
+            out.push_str(&format!(
+                "zstyle ':completion:*:*:{}:*' list-colors {}\n",
+                self.program,
+                colors.join(" ")
+            ));
+        }
+
+        out.push_str(&format!("compdef {} {}\n", function, quote(&self.program)));
+        out
+    }
+
+    /// ## Renders a `complete` line per word, with its description.
+    fn fish(&self) -> String {
+        self.candidates
+            .iter()
+            .map(|c| {
+                format!(
+                    "complete -c {} -f -a {} -d {}\n",
+                    quote(&self.program),
+                    quote(&c.word),
+                    quote(&c.description)
+                )
+            })
+            .collect()
+    }
+}
+
+/// ## Quotes a string for the shell with single quotes, unless it is a plain word.
+fn quote(value: &str) -> String {
+    let plain = |c: char| c.is_ascii_alphanumeric() || "-_./".contains(c);
+    if !value.is_empty() && value.chars().all(plain) {
+        return value.to_string();
+    }
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
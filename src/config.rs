@@ -0,0 +1,93 @@
+//! Reading and writing colors and styles in configuration files.
+//!
+//! Colors are written as strings, see [`Color::from_str`](std::str::FromStr),
+//! or as a 256-color palette index. Styles are written as their names.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Color, Style, Styles, BASE_COLORS, BASE_NAMES, STYLE_NAMES};
+
+impl Serialize for Color {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match *self {
+            Color::Color256(index) => serializer.serialize_u8(index),
+            Color::Default => serializer.serialize_str("default"),
+            Color::Rgb(..) | Color::Hex(_) => {
+                let (r, g, b) = self.to_rgb();
+                serializer.serialize_str(&format!("#{:02x}{:02x}{:02x}", r, g, b))
+            }
+            base => {
+                let index = BASE_COLORS.iter().position(|&c| c == base).unwrap_or(0);
+                serializer.serialize_str(BASE_NAMES[index])
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ColorVisitor)
+    }
+}
+
+/// Reads a color from a string or a palette index.
+struct ColorVisitor;
+
+impl Visitor<'_> for ColorVisitor {
+    type Value = Color;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a color name, a hex color or a palette index")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Color, E> {
+        Color::from_str(value).map_err(E::custom)
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Color, E> {
+        u8::try_from(value)
+            .map(Color::Color256)
+            .map_err(|_| E::custom(format!("invalid palette index {}, expected 0..=255", value)))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Color, E> {
+        u64::try_from(value)
+            .map_err(|_| E::custom(format!("invalid palette index {}, expected 0..=255", value)))
+            .and_then(|value| self.visit_u64(value))
+    }
+}
+
+impl Serialize for Style {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (_, name) = STYLE_NAMES
+            .iter()
+            .find(|&&(style, _)| style == *self)
+            .expect("every style has a name");
+        serializer.serialize_str(name)
+    }
+}
+
+impl<'de> Deserialize<'de> for Style {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Style::from_str(&name).map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for Styles {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de> Deserialize<'de> for Styles {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Vec::<Style>::deserialize(deserializer)?
+            .into_iter()
+            .collect())
+    }
+}
@@ -0,0 +1,151 @@
+//! Support for the Windows console, which doesn't always understand escape sequences.
+//!
+//! Windows 10 and later interpret escape sequences once virtual terminal
+//! processing is enabled, which happens when the capabilities are detected.
+//! Older consoles can't, so the print helpers set the console colors through
+//! `SetConsoleTextAttribute` instead, limited to the 16 base colors.
+
+use std::sync::OnceLock;
+
+/// ## Enables escape sequences in the Windows console.
+///
+/// Turns on virtual terminal processing for the standard output and error,
+/// which Windows 10 and later support. Returns whether the console interprets
+/// escape sequences; always `true` on other systems. It is called when the
+/// capabilities are detected, and only has an effect the first time.
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::enable_virtual_terminal;
+///
+/// if !enable_virtual_terminal() {
+///     eprintln!("legacy console, colors are limited to 16");
+/// }
+/// ```
+pub fn enable_virtual_terminal() -> bool {
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+    *ENABLED.get_or_init(enable)
+}
+
+#[cfg(not(windows))]
+fn enable() -> bool {
+    true
+}
+
+#[cfg(windows)]
+fn enable() -> bool {
+    use crate::sys::windows::{std_handle, GetConsoleMode, SetConsoleMode};
+    use crate::Stream;
+
+    const ENABLE_VIRTUAL_TERMINAL_PROCESSING: u32 = 0x0004;
+
+    let mut enabled = true;
+    for stream in [Stream::Stdout, Stream::Stderr] {
+        let Some(handle) = std_handle(stream) else {
+            continue;
+        };
+        let mut mode = 0;
+        // SAFETY: the handle is valid and `mode` outlives the call.
+        if unsafe { GetConsoleMode(handle, &mut mode) } == 0 {
+            // Not a console, e.g. a pipe or a file.
+            continue;
+        }
+        // SAFETY: the handle is a valid console handle.
+        let set = unsafe { SetConsoleMode(handle, mode | ENABLE_VIRTUAL_TERMINAL_PROCESSING) };
+        enabled &= set != 0;
+    }
+    enabled
+}
+
+/// ## Prints a value with console attributes when the console can't show escape sequences.
+///
+/// Returns `false`, printing nothing, when the console supports escape
+/// sequences, the stream isn't a console, or the value shouldn't be colorized.
+#[cfg(windows)]
+pub(crate) fn print_legacy(clw: &crate::CLW, stream: crate::Stream, newline: bool) -> bool {
+    use std::io::Write;
+
+    use crate::sys::windows::{
+        std_handle, GetConsoleScreenBufferInfo, ScreenBufferInfo, SetConsoleTextAttribute,
+    };
+    use crate::{color_choice, RenderMode, Stream};
+
+    let colorize = clw
+        .choice
+        .unwrap_or_else(color_choice)
+        .resolve(Some(stream));
+    if enable_virtual_terminal() || !colorize {
+        return false;
+    }
+    let Some(handle) = std_handle(stream) else {
+        return false;
+    };
+    let mut info = ScreenBufferInfo::default();
+    // SAFETY: the handle is valid and `info` outlives the call.
+    if unsafe { GetConsoleScreenBufferInfo(handle, &mut info) } == 0 {
+        return false;
+    }
+
+    crate::hook::notify(&clw.value, &clw.spec, Some(stream));
+    let (before, after) = clw.padding();
+    let text = clw.clone().width(0).to_string_for(RenderMode::Plain);
+    let end = if newline { "\n" } else { "" };
+    let mut out: Box<dyn Write> = match stream {
+        Stream::Stdout => Box::new(std::io::stdout().lock()),
+        Stream::Stderr => Box::new(std::io::stderr().lock()),
+    };
+    let _ = write!(out, "{:1$}", "", before);
+    let _ = out.flush();
+    // SAFETY: the handle is a valid console handle.
+    unsafe { SetConsoleTextAttribute(handle, attributes(&clw.spec, info.attributes)) };
+    let _ = write!(out, "{}", text);
+    let _ = out.flush();
+    // SAFETY: the handle is a valid console handle.
+    unsafe { SetConsoleTextAttribute(handle, info.attributes) };
+    let _ = write!(out, "{:1$}{2}", "", after, end);
+    let _ = out.flush();
+    true
+}
+
+/// ## Returns the console attributes showing a style, over the current ones.
+///
+/// Colors are converted to the nearest of the 16 base colors, bold makes the
+/// text color bright and underline uses the grid underscore.
+#[cfg(windows)]
+fn attributes(spec: &crate::StyleSpec, current: u16) -> u16 {
+    use crate::{Color, ColorLevel, Style, BASE_COLORS};
+
+    const INTENSITY: u16 = 0x0008;
+    const REVERSE_VIDEO: u16 = 0x4000;
+    const UNDERSCORE: u16 = 0x8000;
+
+    // Console colors have blue in the lowest bit, ANSI ones red.
+    let nibble = |color: Option<Color>, current: u16| {
+        let base = color
+            .filter(|&c| c != Color::Default)
+            .and_then(|c| c.downgrade(ColorLevel::Ansi16))
+            .and_then(|c| BASE_COLORS.iter().position(|&b| b == c));
+        match base {
+            Some(i) => {
+                let i = i as u16;
+                ((i & 1) << 2) | (i & 2) | ((i & 4) >> 2) | (i & 8)
+            }
+            None => current,
+        }
+    };
+
+    let mut foreground = nibble(spec.text, current & 0x0f);
+    if spec.font.contains(&Style::Bold) {
+        foreground |= INTENSITY;
+    }
+    let background = nibble(spec.bg, (current >> 4) & 0x0f);
+    let mut attributes = (current & !0xc0ff) | foreground | (background << 4);
+    if spec.font.contains(&Style::Reverse) {
+        attributes |= REVERSE_VIDEO;
+    }
+    if spec.font.contains(&Style::Underline) || spec.underline.is_some() {
+        attributes |= UNDERSCORE;
+    }
+    attributes
+}
@@ -0,0 +1,67 @@
+//! The CSS named colors.
+
+use crate::Color;
+
+/// Defines a `Color` constant for every named color, and the table of names.
+macro_rules! named_colors {
+    ($($constant:ident = $name:literal ($r:literal, $g:literal, $b:literal),)*) => {
+        impl Color {
+            $(
+                #[doc = concat!("The CSS `", $name, "` color, `rgb(", $r, ", ", $g, ", ", $b, ")`.")]
+                pub const $constant: Color = Color::Rgb($r, $g, $b);
+            )*
+        }
+
+        /// Every named color, sorted by name.
+        const NAMED: &[(&str, Color)] = &[$(($name, Color::$constant)),*];
+    };
+}
+
+include!("data/css_names.rs");
+
+impl Color {
+    /// ## Looks up a CSS named color, ignoring case.
+    ///
+    /// ### Arguments
+    ///
+    /// * `name` - The color name, e.g. `rebeccapurple`
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::Color;
+    ///
+    /// assert_eq!(Color::from_name("RebeccaPurple"), Some(Color::REBECCA_PURPLE));
+    /// assert_eq!(Color::from_name("not-a-color"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<Color> {
+        let name = name.to_ascii_lowercase();
+        NAMED
+            .binary_search_by(|&(n, _)| n.cmp(name.as_str()))
+            .ok()
+            .map(|i| NAMED[i].1)
+    }
+
+    /// ## Returns the name of the CSS named color closest to this color.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::Color;
+    ///
+    /// assert_eq!(Color::Rgb(250, 130, 110).nearest_name(), "salmon");
+    /// assert_eq!(Color::Red.nearest_name(), "red");
+    /// ```
+    pub fn nearest_name(self) -> &'static str {
+        let (r, g, b) = self.to_rgb();
+        let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+        NAMED
+            .iter()
+            .min_by_key(|(_, c)| {
+                let (cr, cg, cb) = c.to_rgb();
+                d(r, cr) + d(g, cg) + d(b, cb)
+            })
+            .map(|&(name, _)| name)
+            .unwrap_or("black")
+    }
+}
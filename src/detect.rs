@@ -0,0 +1,393 @@
+//! Deciding whether output should be styled, from the environment and overrides.
+
+use std::cell::Cell;
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+use crate::link::detect_hyperlinks;
+
+/// Whether styled output should contain escape codes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Decide from `NO_COLOR` and, when printing, whether the stream is a terminal.
+    #[default]
+    Auto = 0,
+    /// Always emit escape codes.
+    Always = 1,
+    /// Never emit escape codes.
+    Never = 2,
+}
+
+/// The choice set by [`set_color_choice`].
+static CHOICE: AtomicU8 = AtomicU8::new(ColorChoice::Auto as u8);
+
+/// The colors a terminal can show.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorLevel {
+    /// No colors, e.g. `TERM=dumb`.
+    None = 1,
+    /// The 16 base colors.
+    Ansi16 = 2,
+    /// The 256-color palette.
+    Ansi256 = 3,
+    /// 24-bit RGB colors.
+    TrueColor = 4,
+}
+
+/// `0` to detect the level, otherwise the level set by [`set_color_level`].
+static LEVEL: AtomicU8 = AtomicU8::new(0);
+
+/// An output stream that styled text can be printed to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stream {
+    Stdout,
+    Stderr,
+}
+
+impl Stream {
+    /// ## Checks whether the stream is a terminal, rather than a file or pipe.
+    ///
+    /// The result is part of the [`capabilities`], detected once.
+    pub fn is_terminal(self) -> bool {
+        let capabilities = capabilities();
+        match self {
+            Stream::Stdout => capabilities.stdout,
+            Stream::Stderr => capabilities.stderr,
+        }
+    }
+}
+
+/// What the terminal supports, as detected from the environment of the process.
+///
+/// Tests and embedders can build their own and render with them through
+/// [`with_capabilities`], instead of depending on the real environment.
+/// Explicit overrides such as [`set_color_level`] still take precedence.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Capabilities {
+    pub(crate) no_color: bool,
+    pub(crate) stdout: bool,
+    pub(crate) stderr: bool,
+    pub(crate) level: ColorLevel,
+    pub(crate) hyperlinks: bool,
+    pub(crate) unicode: bool,
+}
+
+impl Capabilities {
+    /// ## Detects the capabilities from the environment variables and the
+    /// standard streams of the process.
+    ///
+    /// On Windows, this also enables escape sequences in the console, see
+    /// [`enable_virtual_terminal`](crate::enable_virtual_terminal).
+    pub fn detect() -> Self {
+        if cfg!(windows) {
+            crate::enable_virtual_terminal();
+        }
+        Capabilities {
+            stdout: std::io::stdout().is_terminal(),
+            stderr: std::io::stderr().is_terminal(),
+            ..Self::from_env(|name| std::env::var(name).ok(), false)
+        }
+    }
+
+    /// ## Detects the capabilities from the given environment variables.
+    ///
+    /// ### Arguments
+    ///
+    /// * `var` - Looks up an environment variable, e.g. in a test fixture
+    /// * `terminal` - Whether the standard output and error are terminals
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{Capabilities, ColorLevel};
+    ///
+    /// let xterm = Capabilities::from_env(|name| (name == "TERM").then(|| "xterm-256color".into()), true);
+    ///
+    /// assert_eq!(xterm, Capabilities::from_env(|_| None, true).color_level(ColorLevel::Ansi256));
+    /// ```
+    pub fn from_env<F: Fn(&str) -> Option<String>>(var: F, terminal: bool) -> Self {
+        let var = |name: &str| var(name).unwrap_or_default();
+        Capabilities {
+            no_color: !var("NO_COLOR").is_empty(),
+            stdout: terminal,
+            stderr: terminal,
+            level: detect_color_level(&var("COLORTERM"), &var("TERM_PROGRAM"), &var("TERM")),
+            hyperlinks: detect_hyperlinks(&var),
+            unicode: detect_unicode(&var),
+        }
+    }
+
+    /// ## Sets whether `NO_COLOR` is set, disabling colors with `ColorChoice::Auto`.
+    pub fn no_color(mut self, no_color: bool) -> Self {
+        self.no_color = no_color;
+        self
+    }
+
+    /// ## Sets whether a stream is a terminal.
+    ///
+    /// ### Arguments
+    ///
+    /// * `stream` - The stream
+    /// * `terminal` - Whether it is a terminal rather than a file or pipe
+    pub fn terminal(mut self, stream: Stream, terminal: bool) -> Self {
+        match stream {
+            Stream::Stdout => self.stdout = terminal,
+            Stream::Stderr => self.stderr = terminal,
+        }
+        self
+    }
+
+    /// ## Sets the colors the terminal can show.
+    pub fn color_level(mut self, level: ColorLevel) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// ## Sets whether the terminal supports OSC 8 hyperlinks.
+    pub fn hyperlinks(mut self, hyperlinks: bool) -> Self {
+        self.hyperlinks = hyperlinks;
+        self
+    }
+
+    /// ## Sets whether the terminal can show Unicode symbols, rather than only ASCII.
+    pub fn unicode(mut self, unicode: bool) -> Self {
+        self.unicode = unicode;
+        self
+    }
+}
+
+/// ## Detects Unicode support from the locale, or from the terminal if no locale is set.
+fn detect_unicode(var: &dyn Fn(&str) -> String) -> bool {
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .into_iter()
+        .map(var)
+        .find(|v| !v.is_empty());
+    match locale {
+        Some(locale) => {
+            let locale = locale.to_ascii_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        }
+        None if cfg!(windows) => !var("WT_SESSION").is_empty(),
+        None => !matches!(var("TERM").as_str(), "linux" | "dumb"),
+    }
+}
+
+/// The capabilities detected for the process, or set by [`init_capabilities`].
+static DETECTED: OnceLock<Capabilities> = OnceLock::new();
+
+thread_local! {
+    /// The capabilities set by [`with_capabilities`] on this thread.
+    static OVERRIDE: Cell<Option<Capabilities>> = const { Cell::new(None) };
+}
+
+/// ## Returns the capabilities in use on this thread.
+///
+/// They are detected the first time they are needed, unless set before with
+/// [`init_capabilities`] or for a closure with [`with_capabilities`].
+pub fn capabilities() -> Capabilities {
+    OVERRIDE
+        .with(Cell::get)
+        .unwrap_or_else(|| *DETECTED.get_or_init(Capabilities::detect))
+}
+
+/// ## Sets the capabilities of the process instead of detecting them.
+///
+/// Returns `false`, leaving them unchanged, if they were already detected or set.
+///
+/// ### Arguments
+///
+/// * `capabilities` - The capabilities to use for the whole process
+pub fn init_capabilities(capabilities: Capabilities) -> bool {
+    DETECTED.set(capabilities).is_ok()
+}
+
+/// ## Runs a closure with the given capabilities, on the current thread only.
+///
+/// ### Arguments
+///
+/// * `capabilities` - The capabilities to simulate
+/// * `f` - The closure to run
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{clw, with_capabilities, Capabilities, Color};
+///
+/// let xterm = Capabilities::from_env(|name| (name == "TERM").then(|| "xterm".into()), true);
+/// let red = with_capabilities(xterm, || clw("hi").text(Color::Rgb(255, 0, 0)).to_string());
+///
+/// assert_eq!(red, "\x1b[91mhi\x1b[0m");
+/// ```
+pub fn with_capabilities<R>(capabilities: Capabilities, f: impl FnOnce() -> R) -> R {
+    /// Restores the previous capabilities, even if the closure panics.
+    struct Restore(Option<Capabilities>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            OVERRIDE.with(|o| o.set(self.0));
+        }
+    }
+
+    let _restore = Restore(OVERRIDE.with(|o| o.replace(Some(capabilities))));
+    f()
+}
+
+/// ## Sets whether styled output contains escape codes, for the whole process.
+///
+/// Individual values can still override it with
+/// [`CLW::color_choice`](crate::CLW::color_choice).
+///
+/// ### Arguments
+///
+/// * `choice` - `Always` or `Never` to force styling, `Auto` to detect it
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{clw, set_color_choice, ColorChoice};
+///
+/// set_color_choice(ColorChoice::Never);
+/// assert_eq!(clw("plain").text_red().to_string(), "plain");
+/// ```
+pub fn set_color_choice(choice: ColorChoice) {
+    CHOICE.store(choice as u8, Ordering::Relaxed);
+}
+
+/// ## Returns the choice set by [`set_color_choice`], `Auto` by default.
+pub fn color_choice() -> ColorChoice {
+    match CHOICE.load(Ordering::Relaxed) {
+        1 => ColorChoice::Always,
+        2 => ColorChoice::Never,
+        _ => ColorChoice::Auto,
+    }
+}
+
+/// ## Forces styled output on or off, overriding `NO_COLOR` and terminal detection.
+///
+/// Shorthand for [`set_color_choice`] with `Always` or `Never`.
+///
+/// ### Arguments
+///
+/// * `enabled` - Whether to emit escape codes
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{clw, set_colors_enabled};
+///
+/// set_colors_enabled(false);
+/// assert_eq!(clw("plain").text_red().to_string(), "plain");
+/// ```
+pub fn set_colors_enabled(enabled: bool) {
+    set_color_choice(match enabled {
+        true => ColorChoice::Always,
+        false => ColorChoice::Never,
+    });
+}
+
+/// ## Removes the override set by [`set_colors_enabled`], following `NO_COLOR` again.
+pub fn reset_colors_enabled() {
+    set_color_choice(ColorChoice::Auto);
+}
+
+/// ## Checks whether styled output emits escape codes.
+///
+/// With `ColorChoice::Auto`, colors are disabled when the `NO_COLOR`
+/// environment variable is set to a non-empty value. The variable is read once.
+pub fn colors_enabled() -> bool {
+    color_choice().resolve(None)
+}
+
+/// ## Checks whether output written to a stream should contain escape codes.
+///
+/// With `ColorChoice::Auto`, colors are used when the stream is a terminal and
+/// `NO_COLOR` isn't set.
+///
+/// ### Arguments
+///
+/// * `stream` - The stream the output is written to
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{set_colors_enabled, should_colorize, Stream};
+///
+/// set_colors_enabled(true);
+/// assert!(should_colorize(Stream::Stdout));
+/// ```
+pub fn should_colorize(stream: Stream) -> bool {
+    color_choice().resolve(Some(stream))
+}
+
+/// ## Returns the colors the terminal can show, which RGB colors are downgraded to.
+///
+/// Detected once from `COLORTERM`, `TERM_PROGRAM` and `TERM` as part of the
+/// [`capabilities`], unless set with [`set_color_level`]. When none of them
+/// tell, colors are left as written.
+pub fn color_level() -> ColorLevel {
+    match LEVEL.load(Ordering::Relaxed) {
+        1 => ColorLevel::None,
+        2 => ColorLevel::Ansi16,
+        3 => ColorLevel::Ansi256,
+        4 => ColorLevel::TrueColor,
+        _ => capabilities().level,
+    }
+}
+
+/// ## Overrides the detected color level.
+///
+/// ### Arguments
+///
+/// * `level` - The colors to assume the terminal can show
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{clw, set_color_level, set_colors_enabled, Color, ColorLevel};
+///
+/// set_colors_enabled(true);
+/// set_color_level(ColorLevel::Ansi16);
+///
+/// assert_eq!(clw("hi").text(Color::Rgb(250, 0, 0)).to_string(), "\x1b[91mhi\x1b[0m");
+/// ```
+pub fn set_color_level(level: ColorLevel) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+/// ## Removes the override set by [`set_color_level`], detecting the level again.
+pub fn reset_color_level() {
+    LEVEL.store(0, Ordering::Relaxed);
+}
+
+/// ## Classifies a terminal from its environment variables.
+fn detect_color_level(colorterm: &str, term_program: &str, term: &str) -> ColorLevel {
+    if matches!(colorterm, "truecolor" | "24bit") {
+        return ColorLevel::TrueColor;
+    }
+
+    match term_program {
+        "iTerm.app" | "WezTerm" | "vscode" | "ghostty" => return ColorLevel::TrueColor,
+        "Apple_Terminal" => return ColorLevel::Ansi256,
+        _ => {}
+    }
+
+    match term {
+        "" => ColorLevel::TrueColor,
+        "dumb" => ColorLevel::None,
+        _ if term.ends_with("-direct") => ColorLevel::TrueColor,
+        _ if term.contains("256color") => ColorLevel::Ansi256,
+        _ => ColorLevel::Ansi16,
+    }
+}
+
+impl ColorChoice {
+    /// ## Decides whether to emit escape codes, for a stream if one is known.
+    pub(crate) fn resolve(self, stream: Option<Stream>) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => !capabilities().no_color && stream.is_none_or(Stream::is_terminal),
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! Documents whose links and long details are deferred to numbered footnotes.
+
+use std::fmt;
+
+use crate::{clw, AnsiRenderer, Color, Renderer, Style, StyleSpec, CLW};
+
+/// Builds man-page-like output where links and details become footnotes.
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{clw, document::Document};
+///
+/// let doc = Document::new()
+///     .text("Install with ")
+///     .push(clw("cargo add clwind").text_green())
+///     .text(", see the ")
+///     .link("guide", "https://example.com/guide")
+///     .text(".")
+///     .newline()
+///     .note("Colors", "16, 256 and truecolor palettes are supported.");
+///
+/// println!("{}", doc);
+/// ```
+pub struct Document {
+    segments: Vec<CLW<'static>>,
+    footnotes: Vec<String>,
+    marker: StyleSpec,
+}
+
+impl Default for Document {
+    fn default() -> Self {
+        Document {
+            segments: Vec::new(),
+            footnotes: Vec::new(),
+            marker: StyleSpec::new().text(Color::Cyan),
+        }
+    }
+}
+
+impl Document {
+    /// ## Creates an empty document.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// ## Sets the style of the inline `[n]` footnote markers.
+    ///
+    /// ### Arguments
+    ///
+    /// * `spec` - The style for the markers, cyan by default
+    pub fn marker_style(mut self, spec: StyleSpec) -> Self {
+        self.marker = spec;
+        self
+    }
+
+    /// ## Appends unstyled text.
+    pub fn text(self, text: &str) -> Self {
+        self.push(clw(text))
+    }
+
+    /// ## Appends styled text.
+    pub fn push(mut self, segment: CLW<'_>) -> Self {
+        self.segments.push(segment.into_owned());
+        self
+    }
+
+    /// ## Starts a new line.
+    pub fn newline(self) -> Self {
+        self.text("\n")
+    }
+
+    /// ## Appends underlined link text, deferring the URL to a footnote.
+    ///
+    /// ### Arguments
+    ///
+    /// * `text` - The link text shown inline
+    /// * `url` - The URL listed in the footnotes
+    pub fn link(self, text: &str, url: &str) -> Self {
+        self.push(clw(text).font(Style::Underline)).footnote(url)
+    }
+
+    /// ## Appends text, deferring a longer explanation to a footnote.
+    ///
+    /// ### Arguments
+    ///
+    /// * `text` - The text shown inline
+    /// * `detail` - The explanation listed in the footnotes
+    pub fn note(self, text: &str, detail: &str) -> Self {
+        self.text(text).footnote(detail)
+    }
+
+    /// ## Appends a marker referencing `content`, reusing its number if already listed.
+    fn footnote(mut self, content: &str) -> Self {
+        let number = match self.footnotes.iter().position(|f| f == content) {
+            Some(i) => i + 1,
+            None => {
+                self.footnotes.push(content.to_string());
+                self.footnotes.len()
+            }
+        };
+        let marker = self.marker.apply(format!("[{}]", number));
+        self.push(marker)
+    }
+
+    /// ## Renders the document followed by its footnotes.
+    pub fn render(&self) -> String {
+        self.render_with(&AnsiRenderer)
+    }
+
+    /// ## Renders the document through the given renderer.
+    ///
+    /// ### Arguments
+    ///
+    /// * `renderer` - The backend used to style the output
+    pub fn render_with(&self, renderer: &dyn Renderer) -> String {
+        let mut out: String = self
+            .segments
+            .iter()
+            .map(|segment| segment.render_with(renderer))
+            .collect();
+
+        if !self.footnotes.is_empty() {
+            out.push_str("\n\n");
+            let notes: Vec<String> = self
+                .footnotes
+                .iter()
+                .enumerate()
+                .map(|(i, note)| {
+                    format!(
+                        "{} {}",
+                        renderer.paint(&format!("[{}]", i + 1), &self.marker),
+                        renderer.paint(note, &StyleSpec::new())
+                    )
+                })
+                .collect();
+            out.push_str(&notes.join("\n"));
+        }
+
+        out
+    }
+}
+
+impl fmt::Display for Document {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render())
+    }
+}
@@ -0,0 +1,158 @@
+//! Human-readable quantities: counts with plural nouns, byte sizes and durations.
+//!
+//! The components share these rules, so sizes and counts read the same everywhere.
+
+use std::time::Duration;
+
+use crate::{AnsiRenderer, Renderer, Style, StyleSpec};
+
+/// ## Formats a count followed by its noun, pluralized unless the count is 1.
+///
+/// Nouns ending in `s`, `x`, `ch` or `sh` take `es`, and those ending in a
+/// consonant followed by `y` take `ies`.
+///
+/// ### Arguments
+///
+/// * `count` - The number of things
+/// * `noun` - The singular noun, e.g. `"file"`
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::format::plural;
+///
+/// assert_eq!(plural(1, "file"), "1 file");
+/// assert_eq!(plural(3, "file"), "3 files");
+/// assert_eq!(plural(2, "match"), "2 matches");
+/// assert_eq!(plural(0, "entry"), "0 entries");
+/// ```
+pub fn plural(count: usize, noun: &str) -> String {
+    format!("{} {}", count, noun_for(count, noun))
+}
+
+/// ## Returns the noun as it reads after a count.
+fn noun_for(count: usize, noun: &str) -> String {
+    if count == 1 {
+        return noun.to_string();
+    }
+
+    let vowel = |c: char| "aeiou".contains(c);
+    match noun.strip_suffix('y') {
+        Some(stem) if stem.chars().last().is_some_and(|c| !vowel(c)) => format!("{}ies", stem),
+        _ if ["s", "x", "ch", "sh"].iter().any(|end| noun.ends_with(end)) => format!("{}es", noun),
+        _ => format!("{}s", noun),
+    }
+}
+
+/// ## Formats a size in bytes with binary units, e.g. `4.2 KiB`.
+///
+/// ### Arguments
+///
+/// * `bytes` - The size in bytes
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::format::bytes;
+///
+/// assert_eq!(bytes(512), "512 B");
+/// assert_eq!(bytes(4300), "4.2 KiB");
+/// assert_eq!(bytes(3 * 1024 * 1024), "3.0 MiB");
+/// ```
+pub fn bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+/// ## Formats a duration with a unit suited to its magnitude.
+///
+/// ### Arguments
+///
+/// * `duration` - The duration to format
+///
+/// ### Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use clwind::format::duration;
+///
+/// assert_eq!(duration(Duration::from_millis(1500)), "1.50s");
+/// assert_eq!(duration(Duration::from_secs(125)), "2m05s");
+/// ```
+pub fn duration(duration: Duration) -> String {
+    let secs = duration.as_secs_f64();
+    if secs >= 60.0 {
+        format!(
+            "{}m{:02}s",
+            duration.as_secs() / 60,
+            duration.as_secs() % 60
+        )
+    } else if secs >= 1.0 {
+        format!("{:.2}s", secs)
+    } else if duration.as_millis() > 0 {
+        format!("{}ms", duration.as_millis())
+    } else {
+        format!("{}µs", duration.as_micros())
+    }
+}
+
+/// ## Summarizes the lines, words and characters of a text, with the numbers in bold.
+///
+/// ### Arguments
+///
+/// * `text` - The text to count
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::format::counts;
+///
+/// println!("{}", counts("one two\nthree"));
+/// ```
+pub fn counts(text: &str) -> String {
+    counts_with(&AnsiRenderer, text)
+}
+
+/// ## Same as [`counts`], styling the numbers through the given renderer.
+///
+/// ### Arguments
+///
+/// * `renderer` - The backend used to style the numbers
+/// * `text` - The text to count
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{format::counts_with, PlainRenderer, TestRenderer};
+///
+/// assert_eq!(counts_with(&PlainRenderer, "one two\nthree"), "2 lines, 3 words, 13 characters");
+/// assert_eq!(counts_with(&TestRenderer, "hi"), "[Bold]1[/] line, [Bold]1[/] word, [Bold]2[/] characters");
+/// ```
+pub fn counts_with(renderer: &dyn Renderer, text: &str) -> String {
+    let bold = StyleSpec::new().font(Style::Bold);
+    [
+        (text.lines().count(), "line"),
+        (text.split_whitespace().count(), "word"),
+        (text.chars().count(), "character"),
+    ]
+    .iter()
+    .map(|&(count, noun)| {
+        format!(
+            "{} {}",
+            renderer.paint(&count.to_string(), &bold),
+            noun_for(count, noun)
+        )
+    })
+    .collect::<Vec<_>>()
+    .join(", ")
+}
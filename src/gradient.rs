@@ -0,0 +1,101 @@
+//! Blending colors, and background gradients across padded text.
+
+use crate::width::char_width;
+use crate::{text_width, Align, Color, StyleSpec, StyledString, CLW};
+
+impl Color {
+    /// ## Blends two colors in RGB.
+    ///
+    /// ### Arguments
+    ///
+    /// * `other` - The color to blend towards
+    /// * `t` - How far to go, clamped to `0.0..=1.0`, from this color to `other`
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::Color;
+    ///
+    /// assert_eq!(Color::Rgb(0, 0, 0).mix(Color::Rgb(255, 100, 50), 0.5), Color::Rgb(128, 50, 25));
+    /// ```
+    pub fn mix(self, other: Color, t: f32) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        let (r1, g1, b1) = self.to_rgb();
+        let (r2, g2, b2) = other.to_rgb();
+        let channel = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+        Color::Rgb(channel(r1, r2), channel(g1, g2), channel(b1, b2))
+    }
+}
+
+impl CLW<'_> {
+    /// ## Fills the background with a gradient, from the first column to the last.
+    ///
+    /// The gradient spans the padding set by [`CLW::width`], so a padded line
+    /// becomes a bar that fades from one color to the other. Each line of
+    /// multi-line text is padded to the same width, as set by its alignment,
+    /// and gets the same gradient.
+    ///
+    /// ### Arguments
+    ///
+    /// * `from` - The color of the first column
+    /// * `to` - The color of the last column
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{clw, Align, Color, RenderMode};
+    ///
+    /// let bar = clw("ok").width(3).bg_gradient(Color::Rgb(0, 0, 0), Color::Rgb(200, 0, 0));
+    ///
+    /// assert_eq!(
+    ///     bar.to_string_for(RenderMode::Test),
+    ///     "[bg=Rgb(0, 0, 0)]o[/][bg=Rgb(100, 0, 0)]k[/][bg=Rgb(200, 0, 0)] [/]"
+    /// );
+    ///
+    /// let lines = clw("ab\nc").align(Align::Right).bg_gradient(Color::Rgb(0, 0, 0), Color::Rgb(200, 0, 0));
+    ///
+    /// assert_eq!(
+    ///     lines.to_string_for(RenderMode::Test),
+    ///     "[bg=Rgb(0, 0, 0)]a[/][bg=Rgb(200, 0, 0)]b[/]\n[bg=Rgb(0, 0, 0)] [/][bg=Rgb(200, 0, 0)]c[/]"
+    /// );
+    ///
+    /// println!("{}", clw(" Release").text_white().font_bold().width(40).bg_gradient(Color::Blue, Color::Magenta));
+    /// ```
+    pub fn bg_gradient(&self, from: Color, to: Color) -> StyledString {
+        let widest = self.value.split('\n').map(text_width).max().unwrap_or(0);
+        let columns = widest.max(self.width.unwrap_or(0));
+        let lines: Vec<String> = self
+            .value
+            .split('\n')
+            .map(|line| {
+                let extra = columns - text_width(line);
+                let (before, after) = match self.align {
+                    Align::Left => (0, extra),
+                    Align::Right => (extra, 0),
+                    Align::Center => (extra / 2, extra - extra / 2),
+                };
+                format!("{:3$}{}{:4$}", "", line, "", before, after)
+            })
+            .collect();
+        let padded = CLW {
+            value: lines.join("\n").into(),
+            width: None,
+            ..self.clone()
+        };
+
+        let mut column = 0;
+        padded.map_chars(|_, c| {
+            if c == '\n' {
+                column = 0;
+                return StyleSpec::new();
+            }
+
+            let t = match columns {
+                0 | 1 => 0.0,
+                n => column as f32 / (n - 1) as f32,
+            };
+            column += char_width(c);
+            StyleSpec::new().bg(from.mix(to, t))
+        })
+    }
+}
@@ -0,0 +1,64 @@
+//! Line-number gutters in front of blocks of text, such as code frames and diffs.
+
+use crate::{AnsiRenderer, Renderer, Style, StyleSpec};
+
+/// The separator between the line numbers and the lines.
+const SEPARATOR: &str = " │ ";
+
+/// ## Prefixes each line of a block with a dim, right-aligned line number.
+///
+/// The block may already be styled; its lines are left as they are.
+///
+/// ### Arguments
+///
+/// * `block` - The text to number, one line per `\n`
+/// * `start` - The number of the first line
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::with_line_numbers;
+///
+/// println!("{}", with_line_numbers("fn main() {\n    run();\n}", 1));
+/// ```
+pub fn with_line_numbers(block: &str, start: usize) -> String {
+    with_line_numbers_with(&AnsiRenderer, block, start)
+}
+
+/// ## Same as [`with_line_numbers`], styling the gutter through the given renderer.
+///
+/// ### Arguments
+///
+/// * `renderer` - The backend used to style the gutter
+/// * `block` - The text to number, one line per `\n`
+/// * `start` - The number of the first line
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{gutter::with_line_numbers_with, PlainRenderer};
+///
+/// let numbered = with_line_numbers_with(&PlainRenderer, "a\nb\nc", 9);
+///
+/// assert_eq!(numbered, " 9 │ a\n10 │ b\n11 │ c");
+/// ```
+pub fn with_line_numbers_with(renderer: &dyn Renderer, block: &str, start: usize) -> String {
+    if block.is_empty() {
+        return String::new();
+    }
+
+    let dim = StyleSpec::new().font(Style::Dim);
+    let lines: Vec<&str> = block.split('\n').collect();
+    let width = (start + lines.len() - 1).to_string().len();
+    let separator = renderer.paint(SEPARATOR, &dim);
+
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            let number = format!("{:>width$}", start + i, width = width);
+            format!("{}{}{}", renderer.paint(&number, &dim), separator, line)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
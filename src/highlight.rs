@@ -0,0 +1,265 @@
+//! Keyword highlighting over whole documents, for log output at scale.
+//!
+//! All words are matched in a single pass with an Aho-Corasick automaton.
+//! When matches overlap, the word with the highest priority wins, then the
+//! longest, then the leftmost.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::ops::Range;
+use std::sync::OnceLock;
+
+use crate::{AnsiRenderer, Renderer, StyleSpec};
+
+/// A word to highlight.
+struct Word {
+    text: String,
+    spec: StyleSpec,
+    priority: i32,
+}
+
+/// Highlights dictionary words in text, each with its own style.
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{highlight::Highlighter, Color, Style, StyleSpec};
+///
+/// let highlighter = Highlighter::new()
+///     .word("ERROR", StyleSpec::new().text(Color::Red).font(Style::Bold), 10)
+///     .word("WARN", StyleSpec::new().text(Color::Yellow), 5)
+///     .whole_words(true);
+///
+/// println!("{}", highlighter.highlight("12:00 WARN disk\n12:01 ERROR full"));
+/// ```
+#[derive(Default)]
+pub struct Highlighter {
+    words: Vec<Word>,
+    case_insensitive: bool,
+    whole_words: bool,
+    automaton: OnceLock<Automaton>,
+}
+
+/// A highlighted occurrence of a word.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Match {
+    /// The byte range of the occurrence in the text.
+    pub range: Range<usize>,
+    /// The index of the word, in the order words were added.
+    pub word: usize,
+}
+
+impl Highlighter {
+    /// ## Creates a highlighter without words.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// ## Adds a word to highlight.
+    ///
+    /// ### Arguments
+    ///
+    /// * `word` - The text to look for; empty words are ignored
+    /// * `spec` - The style applied to each occurrence
+    /// * `priority` - Decides which word wins when occurrences overlap
+    pub fn word(mut self, word: &str, spec: StyleSpec, priority: i32) -> Self {
+        self.words.push(Word {
+            text: word.to_string(),
+            spec,
+            priority,
+        });
+        self.automaton = OnceLock::new();
+        self
+    }
+
+    /// ## Sets whether ASCII letters match regardless of case.
+    pub fn case_insensitive(mut self, enabled: bool) -> Self {
+        self.case_insensitive = enabled;
+        self.automaton = OnceLock::new();
+        self
+    }
+
+    /// ## Sets whether words only match when not surrounded by letters, digits or `_`.
+    pub fn whole_words(mut self, enabled: bool) -> Self {
+        self.whole_words = enabled;
+        self
+    }
+
+    /// ## Finds the non-overlapping occurrences to highlight, in text order.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{highlight::Highlighter, StyleSpec};
+    ///
+    /// let highlighter = Highlighter::new()
+    ///     .word("time", StyleSpec::new(), 1)
+    ///     .word("timeout", StyleSpec::new(), 1);
+    ///
+    /// let matches = highlighter.find("timeout");
+    ///
+    /// assert_eq!(matches.len(), 1);
+    /// assert_eq!(matches[0].word, 1);
+    /// ```
+    pub fn find(&self, text: &str) -> Vec<Match> {
+        let automaton = self.automaton.get_or_init(|| {
+            Automaton::new(self.words.iter().map(|w| self.fold(w.text.as_bytes())))
+        });
+
+        let bytes = text.as_bytes();
+        let mut found: Vec<Match> = automaton
+            .find_all(bytes.iter().map(|&b| self.fold_byte(b)))
+            .filter(|m| !self.whole_words || is_whole_word(bytes, &m.range))
+            .collect();
+
+        found.sort_by(|a, b| {
+            let (wa, wb) = (&self.words[a.word], &self.words[b.word]);
+            wb.priority
+                .cmp(&wa.priority)
+                .then(b.range.len().cmp(&a.range.len()))
+                .then(a.range.start.cmp(&b.range.start))
+        });
+
+        let mut taken = vec![false; bytes.len()];
+        let mut chosen = Vec::new();
+        for m in found {
+            if taken[m.range.clone()].iter().any(|&t| t) {
+                continue;
+            }
+            taken[m.range.clone()].iter_mut().for_each(|t| *t = true);
+            chosen.push(m);
+        }
+
+        chosen.sort_by_key(|m| m.range.start);
+        chosen
+    }
+
+    /// ## Renders the text with every occurrence styled.
+    pub fn highlight(&self, text: &str) -> String {
+        self.highlight_with(&AnsiRenderer, text)
+    }
+
+    /// ## Renders the text through the given renderer with every occurrence styled.
+    ///
+    /// ### Arguments
+    ///
+    /// * `renderer` - The backend used to style the output
+    /// * `text` - The text to highlight
+    pub fn highlight_with(&self, renderer: &dyn Renderer, text: &str) -> String {
+        let plain = StyleSpec::new();
+        let mut out = String::with_capacity(text.len());
+        let mut last = 0;
+        for m in self.find(text) {
+            if m.range.start > last {
+                out.push_str(&renderer.paint(&text[last..m.range.start], &plain));
+            }
+            out.push_str(&renderer.paint(&text[m.range.clone()], &self.words[m.word].spec));
+            last = m.range.end;
+        }
+
+        if last < text.len() {
+            out.push_str(&renderer.paint(&text[last..], &plain));
+        }
+        out
+    }
+
+    /// ## Folds the case of a pattern as configured.
+    fn fold(&self, bytes: &[u8]) -> Vec<u8> {
+        bytes.iter().map(|&b| self.fold_byte(b)).collect()
+    }
+
+    /// ## Folds the case of a byte as configured.
+    fn fold_byte(&self, byte: u8) -> u8 {
+        match self.case_insensitive {
+            true => byte.to_ascii_lowercase(),
+            false => byte,
+        }
+    }
+}
+
+/// ## Checks that a match isn't preceded or followed by a word character.
+fn is_whole_word(bytes: &[u8], range: &Range<usize>) -> bool {
+    let is_word = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+    let before = range.start == 0 || !is_word(bytes[range.start - 1]);
+    let after = range.end == bytes.len() || !is_word(bytes[range.end]);
+    before && after
+}
+
+/// A state of the automaton.
+#[derive(Default)]
+struct Node {
+    next: BTreeMap<u8, usize>,
+    fail: usize,
+    /// The patterns ending in this state, as `(index, length)`.
+    out: Vec<(usize, usize)>,
+}
+
+/// An Aho-Corasick automaton over bytes.
+struct Automaton {
+    nodes: Vec<Node>,
+}
+
+impl Automaton {
+    /// ## Builds the automaton for the given patterns.
+    fn new(patterns: impl Iterator<Item = Vec<u8>>) -> Self {
+        let mut nodes = vec![Node::default()];
+        for (index, pattern) in patterns.enumerate() {
+            if pattern.is_empty() {
+                continue;
+            }
+
+            let mut state = 0;
+            for &byte in &pattern {
+                state = match nodes[state].next.get(&byte) {
+                    Some(&next) => next,
+                    None => {
+                        nodes.push(Node::default());
+                        let next = nodes.len() - 1;
+                        nodes[state].next.insert(byte, next);
+                        next
+                    }
+                };
+            }
+            nodes[state].out.push((index, pattern.len()));
+        }
+
+        let mut queue: VecDeque<usize> = nodes[0].next.values().copied().collect();
+        while let Some(state) = queue.pop_front() {
+            let children: Vec<(u8, usize)> =
+                nodes[state].next.iter().map(|(&b, &n)| (b, n)).collect();
+            for (byte, child) in children {
+                let mut fail = nodes[state].fail;
+                while fail != 0 && !nodes[fail].next.contains_key(&byte) {
+                    fail = nodes[fail].fail;
+                }
+                let target = match nodes[fail].next.get(&byte) {
+                    Some(&t) if t != child => t,
+                    _ => 0,
+                };
+                nodes[child].fail = target;
+                let inherited = nodes[target].out.clone();
+                nodes[child].out.extend(inherited);
+                queue.push_back(child);
+            }
+        }
+
+        Automaton { nodes }
+    }
+
+    /// ## Reports every occurrence of every pattern, including overlapping ones.
+    fn find_all<'a>(
+        &'a self,
+        bytes: impl Iterator<Item = u8> + 'a,
+    ) -> impl Iterator<Item = Match> + 'a {
+        let mut state = 0;
+        bytes.enumerate().flat_map(move |(i, byte)| {
+            while state != 0 && !self.nodes[state].next.contains_key(&byte) {
+                state = self.nodes[state].fail;
+            }
+            state = self.nodes[state].next.get(&byte).copied().unwrap_or(0);
+            self.nodes[state].out.iter().map(move |&(word, len)| Match {
+                range: i + 1 - len..i + 1,
+                word,
+            })
+        })
+    }
+}
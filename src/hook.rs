@@ -0,0 +1,117 @@
+//! A callback that sees every styled value as it is rendered.
+//!
+//! Applications can mirror terminal output into logs or telemetry with the
+//! text and style of each value, without wrapping every print call.
+
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+
+use crate::{Stream, StyleSpec};
+
+/// The callback type, called with the text, its style and its stream.
+type Hook = Arc<dyn Fn(&str, &StyleSpec, Option<Stream>) + Send + Sync>;
+
+/// Whether a hook is set, checked before taking the lock.
+static HOOKED: AtomicBool = AtomicBool::new(false);
+/// The hook set by [`set_output_hook`].
+static HOOK: RwLock<Option<Hook>> = RwLock::new(None);
+
+thread_local! {
+    /// Whether the hook is running on this thread, so values it renders don't call it again.
+    static IN_HOOK: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Marks the hook as running on this thread until dropped, even if it panics.
+struct Running;
+
+impl Running {
+    /// ## Marks the hook as running.
+    fn enter() -> Self {
+        IN_HOOK.with(|running| running.set(true));
+        Running
+    }
+}
+
+impl Drop for Running {
+    fn drop(&mut self) {
+        IN_HOOK.with(|running| running.set(false));
+    }
+}
+
+/// ## Sets a callback called with every styled value clwind renders.
+///
+/// The callback gets the plain text, its style, and the stream it is printed
+/// to, or `None` when rendered with `Display` or `to_string`. Components
+/// rendered with [`AnsiRenderer`](crate::AnsiRenderer), such as tables and
+/// panels, call it for each styled piece, with `None` as the stream. Values
+/// rendered inside the callback don't call it again.
+///
+/// ### Arguments
+///
+/// * `hook` - The callback, replacing any previous one
+///
+/// ### Examples
+///
+/// ```
+/// use std::sync::{Arc, Mutex};
+/// use clwind::{clear_output_hook, clw, set_output_hook, Color};
+///
+/// let seen = Arc::new(Mutex::new(Vec::new()));
+/// let log = Arc::clone(&seen);
+/// set_output_hook(move |text, spec, _stream| {
+///     log.lock().unwrap().push((text.to_string(), spec.text_color()));
+/// });
+///
+/// let _ = clw("disk full").text_red().to_string();
+/// clear_output_hook();
+///
+/// assert_eq!(*seen.lock().unwrap(), [("disk full".to_string(), Some(Color::Red))]);
+/// ```
+///
+/// A hook that panics is still called for the values rendered after it:
+///
+/// ```
+/// use std::panic;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use clwind::{clear_output_hook, clw, set_output_hook};
+///
+/// static CALLS: AtomicUsize = AtomicUsize::new(0);
+/// set_output_hook(|_, _, _| {
+///     if CALLS.fetch_add(1, Ordering::Relaxed) == 0 {
+///         panic!("first call");
+///     }
+/// });
+///
+/// assert!(panic::catch_unwind(|| clw("one").to_string()).is_err());
+/// let _ = clw("two").to_string();
+/// clear_output_hook();
+///
+/// assert_eq!(CALLS.load(Ordering::Relaxed), 2);
+/// ```
+pub fn set_output_hook<F>(hook: F)
+where
+    F: Fn(&str, &StyleSpec, Option<Stream>) + Send + Sync + 'static,
+{
+    *HOOK.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(hook));
+    HOOKED.store(true, Ordering::Relaxed);
+}
+
+/// ## Removes the callback set by [`set_output_hook`].
+pub fn clear_output_hook() {
+    HOOKED.store(false, Ordering::Relaxed);
+    *HOOK.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// ## Calls the hook, if one is set and it isn't already running on this thread.
+pub(crate) fn notify(text: &str, spec: &StyleSpec, stream: Option<Stream>) {
+    if !HOOKED.load(Ordering::Relaxed) || IN_HOOK.with(Cell::get) {
+        return;
+    }
+
+    let hook = HOOK.read().unwrap_or_else(|e| e.into_inner()).clone();
+    if let Some(hook) = hook {
+        let _running = Running::enter();
+        hook(text, spec, stream);
+    }
+}
@@ -0,0 +1,114 @@
+//! Converting colors to and from hue, saturation and lightness, and comparing
+//! their luminance.
+
+use crate::Color;
+
+impl Color {
+    /// ## Creates an RGB color from hue, saturation and lightness.
+    ///
+    /// ### Arguments
+    ///
+    /// * `h` - The hue in degrees, wrapped into `0.0..360.0`
+    /// * `s` - The saturation, clamped to `0.0..=1.0`
+    /// * `l` - The lightness, clamped to `0.0..=1.0`
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::Color;
+    ///
+    /// assert_eq!(Color::hsl(0.0, 1.0, 0.5), Color::Rgb(255, 0, 0));
+    /// assert_eq!(Color::hsl(480.0, 1.0, 0.25), Color::Rgb(0, 128, 0));
+    /// ```
+    pub fn hsl(h: f32, s: f32, l: f32) -> Color {
+        let h = h.rem_euclid(360.0) / 60.0;
+        let s = s.clamp(0.0, 1.0);
+        let l = l.clamp(0.0, 1.0);
+
+        let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = match h as u8 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let m = l - chroma / 2.0;
+        let channel = |v: f32| ((v + m) * 255.0).round() as u8;
+        Color::Rgb(channel(r), channel(g), channel(b))
+    }
+
+    /// ## Returns the hue in degrees, the saturation and the lightness of the color.
+    ///
+    /// Named colors use the xterm default palette.
+    ///
+    /// ### Examples
+    ///
+    /// Rotating the hue of a color:
+    ///
+    /// ```
+    /// use clwind::Color;
+    ///
+    /// let (h, s, l) = Color::Rgb(255, 0, 0).to_hsl();
+    ///
+    /// assert_eq!(Color::hsl(h + 120.0, s, l), Color::Rgb(0, 255, 0));
+    /// ```
+    pub fn to_hsl(self) -> (f32, f32, f32) {
+        let (r, g, b) = self.to_rgb();
+        let (r, g, b) = (r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0);
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let l = (max + min) / 2.0;
+        let delta = max - min;
+        if delta == 0.0 {
+            return (0.0, 0.0, l);
+        }
+
+        let s = delta / (1.0 - (2.0 * l - 1.0).abs());
+        let h = if max == r {
+            ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            (b - r) / delta + 2.0
+        } else {
+            (r - g) / delta + 4.0
+        };
+        (h * 60.0, s, l)
+    }
+
+    /// ## Returns the WCAG contrast ratio between two colors, from 1 to 21.
+    ///
+    /// Text is commonly considered readable from a ratio of 4.5, or 3 for
+    /// bold and large text.
+    ///
+    /// ### Arguments
+    ///
+    /// * `other` - The color to compare with, e.g. the background
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::Color;
+    ///
+    /// assert!(Color::Black.contrast(Color::BrightWhite) > 20.9);
+    /// assert!(Color::Blue.contrast(Color::Black) < 3.0);
+    /// ```
+    pub fn contrast(self, other: Color) -> f32 {
+        let (a, b) = (self.luminance(), other.luminance());
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
+    /// ## Returns the relative luminance of the color, from 0 for black to 1 for white.
+    fn luminance(self) -> f32 {
+        let linear = |c: u8| {
+            let c = c as f32 / 255.0;
+            match c <= 0.03928 {
+                true => c / 12.92,
+                false => ((c + 0.055) / 1.055).powf(2.4),
+            }
+        };
+        let (r, g, b) = self.to_rgb();
+        0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b)
+    }
+}
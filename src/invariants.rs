@@ -0,0 +1,299 @@
+//! Guarantees every clwind renderer upholds, exposed for property testing.
+//!
+//! Component authors can check their own output against the same rules:
+//!
+//! * every opened style or hyperlink is closed again,
+//! * stripping the escapes gives back the plain text,
+//! * the visible width doesn't depend on the escapes.
+//!
+//! With the `proptest` feature, [`strategies`] provides generators for colors,
+//! styles and specs.
+//!
+//! ```
+//! use clwind::{clw, invariants};
+//!
+//! assert!(invariants::check_clw(&clw("ok").text_green().font_bold()).is_ok());
+//! ```
+
+use crate::ansi::{sequence_end, strip_ansi, ESC};
+use crate::{char_width, text_width, RenderMode, CLW};
+
+/// ## Checks that every SGR style and OSC 8 hyperlink opened in `rendered` is closed.
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::invariants::escapes_closed;
+///
+/// assert!(escapes_closed("\x1b[31mred\x1b[0m"));
+/// assert!(!escapes_closed("\x1b[31mred"));
+/// ```
+pub fn escapes_closed(rendered: &str) -> bool {
+    let mut style_open = false;
+    let mut link_open = false;
+    let mut rest = rendered;
+    while let Some(start) = rest.find(ESC) {
+        rest = &rest[start + ESC.len_utf8()..];
+        if let Some(body) = rest.strip_prefix('[') {
+            let Some(end) = body.find(|c| ('\x40'..='\x7e').contains(&c)) else {
+                return false;
+            };
+            if body[end..].starts_with('m') {
+                style_open = !body[..end]
+                    .split([';', ':'])
+                    .all(|p| p.is_empty() || p == "0");
+            }
+            rest = &body[end + 1..];
+        } else if let Some(body) = rest.strip_prefix("]8;") {
+            let Some(end) = body.find(['\x07', ESC]) else {
+                return false;
+            };
+            let uri = body[..end].split_once(';').map_or("", |(_, uri)| uri);
+            link_open = !uri.is_empty();
+            rest = &body[end..];
+        }
+    }
+    !style_open && !link_open
+}
+
+/// ## Checks that stripping the escapes from `rendered` yields `plain`.
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::invariants::round_trips;
+///
+/// assert!(round_trips("\x1b[1mbold\x1b[0m", "bold"));
+/// ```
+pub fn round_trips(rendered: &str, plain: &str) -> bool {
+    strip_ansi(rendered) == plain
+}
+
+/// ## Checks that `rendered` takes as many columns as `plain`.
+///
+/// The columns of `plain` are counted without parsing escapes, each escape
+/// character taking one, so a sequence that leaks into the visible text or
+/// swallows part of it changes the width.
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::invariants::width_preserved;
+///
+/// assert!(width_preserved("\x1b[1mbold\x1b[0m", "bold"));
+/// assert!(!width_preserved("\x1b[1bold\x1b[0m", "bold"));
+/// ```
+pub fn width_preserved(rendered: &str, plain: &str) -> bool {
+    text_width(rendered) == naive_width(plain)
+}
+
+/// ## Returns the columns of `text` with escape characters counted as visible.
+fn naive_width(text: &str) -> usize {
+    text.chars()
+        .map(|c| match c {
+            ESC => 1,
+            _ => char_width(c),
+        })
+        .sum()
+}
+
+/// ## Checks all invariants, describing the first one that doesn't hold.
+///
+/// ### Arguments
+///
+/// * `rendered` - The output of a renderer
+/// * `plain` - The text that was rendered, without styling
+pub fn check(rendered: &str, plain: &str) -> Result<(), String> {
+    if !escapes_closed(rendered) {
+        return Err(format!("unclosed escape sequence in {:?}", rendered));
+    }
+
+    if !round_trips(rendered, plain) {
+        return Err(format!(
+            "{:?} strips to {:?}, expected {:?}",
+            rendered,
+            strip_ansi(rendered),
+            plain
+        ));
+    }
+
+    if !width_preserved(rendered, plain) {
+        return Err(format!(
+            "{:?} takes {} columns, expected {}",
+            rendered,
+            text_width(rendered),
+            naive_width(plain)
+        ));
+    }
+
+    Ok(())
+}
+
+/// ## Checks all invariants for the rendered form of a `CLW` value.
+pub fn check_clw(value: &CLW) -> Result<(), String> {
+    check(&value.to_string(), &strip_ansi(value.plain()))
+}
+
+/// ## Checks the invariants that hold for a `CLW` value rendered in `mode`.
+///
+/// The ANSI and plain output is checked with [`check`], and the prompt output
+/// too once the markers around its escape sequences are removed. HTML and test
+/// markup must not add escape sequences to the text.
+///
+/// ### Arguments
+///
+/// * `value` - The value to render
+/// * `mode` - The output format
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{clw, invariants, set_colors_enabled, RenderMode};
+///
+/// set_colors_enabled(true);
+/// let value = clw("ok").text_green().font_bold();
+///
+/// assert!(invariants::check_clw_for(&value, RenderMode::BashPrompt).is_ok());
+/// ```
+pub fn check_clw_for(value: &CLW, mode: RenderMode) -> Result<(), String> {
+    let rendered = value.to_string_for(mode);
+    let markers = match mode {
+        RenderMode::Ansi | RenderMode::Plain => ("", ""),
+        RenderMode::BashPrompt => ("\\[", "\\]"),
+        RenderMode::ZshPrompt => ("%{", "%}"),
+        RenderMode::Html | RenderMode::Test => {
+            return match rendered.matches(ESC).count() > value.plain().matches(ESC).count() {
+                true => Err(format!("escape sequence added to {:?}", rendered)),
+                false => Ok(()),
+            };
+        }
+    };
+    check(&unmark(&rendered, markers), &strip_ansi(value.plain()))
+}
+
+/// ## Removes the prompt markers placed around each escape sequence.
+fn unmark(rendered: &str, (open, close): (&str, &str)) -> String {
+    if open.is_empty() {
+        return rendered.to_string();
+    }
+
+    let mut out = String::with_capacity(rendered.len());
+    let mut rest = rendered;
+    while let Some(i) = rest.find(open) {
+        let after = &rest[i + open.len()..];
+        let end = match after.starts_with(ESC) {
+            true => sequence_end(after, 0),
+            false => 0,
+        };
+        match after[end..].strip_prefix(close) {
+            Some(tail) if end > 0 => {
+                out.push_str(&rest[..i]);
+                out.push_str(&after[..end]);
+                rest = tail;
+            }
+            _ => {
+                out.push_str(&rest[..i + open.len()]);
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Proptest strategies for clwind's styling types.
+///
+/// Every built-in renderer holds the invariants for any generated value,
+/// including values nested in another style:
+///
+/// ```
+/// use clwind::invariants::{self, strategies};
+/// use clwind::{set_colors_enabled, RenderMode, SvgRenderer};
+/// use proptest::prelude::*;
+///
+/// set_colors_enabled(true);
+/// let modes = [
+///     RenderMode::Ansi,
+///     RenderMode::Html,
+///     RenderMode::Plain,
+///     RenderMode::Test,
+///     RenderMode::BashPrompt,
+///     RenderMode::ZshPrompt,
+/// ];
+///
+/// let config = ProptestConfig {
+///     failure_persistence: None,
+///     ..ProptestConfig::default()
+/// };
+/// proptest!(config, |(inner in strategies::clw(), spec in strategies::style_spec())| {
+///     let outer = spec.apply(format!("[{}]", inner));
+///     for value in [&inner, &outer] {
+///         prop_assert_eq!(invariants::check_clw(value), Ok(()));
+///         for mode in modes {
+///             prop_assert_eq!(invariants::check_clw_for(value, mode), Ok(()));
+///         }
+///         let svg = value.render_with(&SvgRenderer::new());
+///         prop_assert!(!svg.contains('\x1b'));
+///     }
+/// });
+/// ```
+#[cfg(feature = "proptest")]
+pub mod strategies {
+    use proptest::prelude::*;
+
+    use crate::{Color, Style, StyleSpec, BASE_COLORS, CLW};
+
+    /// ## Generates any color, including the RGB, 256 and hex forms.
+    pub fn color() -> impl Strategy<Value = Color> {
+        prop_oneof![
+            prop::sample::select(BASE_COLORS.to_vec()),
+            any::<(u8, u8, u8)>().prop_map(|(r, g, b)| Color::Rgb(r, g, b)),
+            any::<u8>().prop_map(Color::Color256),
+            (0u32..=0xFF_FFFF).prop_map(Color::Hex),
+            Just(Color::Default),
+        ]
+    }
+
+    /// ## Generates any style.
+    pub fn style() -> impl Strategy<Value = Style> {
+        prop::sample::select(vec![
+            Style::Bold,
+            Style::Dim,
+            Style::Italic,
+            Style::Underline,
+            Style::Blink,
+            Style::Reverse,
+            Style::Hidden,
+            Style::Strikethrough,
+            Style::DoubleUnderline,
+            Style::CurlyUnderline,
+            Style::DottedUnderline,
+            Style::DashedUnderline,
+            Style::Overline,
+        ])
+    }
+
+    /// ## Generates a spec with optional colors and up to four styles.
+    pub fn style_spec() -> impl Strategy<Value = StyleSpec> {
+        (
+            prop::option::of(color()),
+            prop::option::of(color()),
+            prop::collection::vec(style(), 0..4),
+        )
+            .prop_map(|(text, bg, font)| {
+                let mut spec = StyleSpec::new();
+                if let Some(color) = text {
+                    spec = spec.text(color);
+                }
+                if let Some(color) = bg {
+                    spec = spec.bg(color);
+                }
+                font.into_iter().fold(spec, StyleSpec::font)
+            })
+    }
+
+    /// ## Generates a `CLW` value with printable text and any spec.
+    pub fn clw() -> impl Strategy<Value = CLW<'static>> {
+        ("[^\\x00-\\x1f\\x7f]*", style_spec()).prop_map(|(text, spec)| spec.apply(text))
+    }
+}
@@ -0,0 +1,72 @@
+//! Key-hint bars, the standard footer of semi-interactive CLIs.
+
+use crate::{terminal_width, text_width, AnsiRenderer, Renderer, Style, StyleSpec};
+
+/// The gap between two entries of the legend.
+const GAP: &str = "   ";
+
+/// ## Renders a key-hint bar wrapped to the terminal width.
+///
+/// Keys are shown in reverse video and their descriptions dim.
+///
+/// ### Arguments
+///
+/// * `items` - The `(key, description)` pairs, in display order
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::legend;
+///
+/// println!("{}", legend(&[("q", "quit"), ("↑/↓", "navigate"), ("enter", "open")]));
+/// ```
+pub fn legend(items: &[(&str, &str)]) -> String {
+    legend_with(&AnsiRenderer, items, terminal_width())
+}
+
+/// ## Renders a key-hint bar through the given renderer, wrapped to `width` columns.
+///
+/// ### Arguments
+///
+/// * `renderer` - The backend used to style the output
+/// * `items` - The `(key, description)` pairs, in display order
+/// * `width` - The number of columns to wrap at
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{legend::legend_with, PlainRenderer};
+///
+/// let bar = legend_with(&PlainRenderer, &[("q", "quit"), ("?", "help")], 20);
+///
+/// assert_eq!(bar, " q  quit    ?  help");
+/// ```
+pub fn legend_with(renderer: &dyn Renderer, items: &[(&str, &str)], width: usize) -> String {
+    let key = StyleSpec::new().font(Style::Reverse);
+    let description = StyleSpec::new().font(Style::Dim);
+
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut line_width = 0;
+    for (k, d) in items {
+        let entry_width = text_width(k) + 2 + 1 + text_width(d);
+        if line_width > 0 && line_width + GAP.len() + entry_width > width {
+            lines.push(std::mem::take(&mut line));
+            line_width = 0;
+        }
+
+        if line_width > 0 {
+            line.push_str(GAP);
+            line_width += GAP.len();
+        }
+        line.push_str(&renderer.paint(&format!(" {} ", k), &key));
+        line.push(' ');
+        line.push_str(&renderer.paint(d, &description));
+        line_width += entry_width;
+    }
+
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines.join("\n")
+}
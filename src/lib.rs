@@ -1,7 +1,99 @@
+use std::borrow::Cow;
 use std::fmt;
+use std::ops::Deref;
+use std::sync::{Arc, OnceLock, RwLock};
+
+mod align;
+pub mod ansi;
+pub mod banner;
+#[cfg(feature = "capi")]
+pub mod capi;
+pub mod changelog;
+mod chars;
+mod classes;
+mod colorize;
+pub mod completion;
+#[cfg(feature = "serde")]
+mod config;
+mod console;
+mod css;
+mod detect;
+pub mod document;
+pub mod format;
+mod gradient;
+pub mod gutter;
+pub mod highlight;
+mod hook;
+mod hsl;
+pub mod invariants;
+pub mod legend;
+pub mod link;
+#[cfg(feature = "log")]
+pub mod logger;
+pub mod manpage;
+pub mod osc;
+pub mod panel;
+pub mod powerline;
+mod print;
+#[cfg(feature = "python")]
+mod python;
+mod rainbow;
+pub mod region;
+pub mod render;
+pub mod rule;
+mod size;
+pub mod spinner;
+mod stats;
+pub mod status;
+mod style_set;
+mod styled_string;
+pub mod svg;
+pub mod symbols;
+mod sys;
+pub mod table;
+mod tailwind;
+mod text;
+pub mod theme;
+pub mod timeline;
+pub mod tips;
+pub mod vt;
+mod width;
+
+pub use align::Align;
+pub use ansi::{parse_ansi, strip_ansi, Segment};
+pub use classes::ParseClassError;
+#[cfg(feature = "macros")]
+pub use clwind_macros::clw;
+pub use colorize::Colorize;
+pub use console::enable_virtual_terminal;
+pub use detect::{
+    capabilities, color_choice, color_level, colors_enabled, init_capabilities, reset_color_level,
+    reset_colors_enabled, set_color_choice, set_color_level, set_colors_enabled, should_colorize,
+    with_capabilities, Capabilities, ColorChoice, ColorLevel, Stream,
+};
+pub use gutter::with_line_numbers;
+pub use hook::{clear_output_hook, set_output_hook};
+pub use legend::legend;
+pub use rainbow::Rainbow;
+pub use region::highlight_region;
+pub use render::{
+    AnsiRenderer, HtmlRenderer, PlainRenderer, PromptRenderer, RenderMode, Renderer, TestRenderer,
+};
+pub use rule::{hr, section};
+pub use size::terminal_size;
+pub use stats::{reset_stats, set_escape_budget, stats, track_stats, OutputStats, StreamStats};
+pub use style_set::StyleSet;
+pub use styled_string::{join_styled, StyledString, TextMatch};
+pub use svg::SvgRenderer;
+pub use text::{visible_width, wrap_ansi};
+pub use theme::{clw_code, clw_key};
+pub use width::{
+    ambiguous_wide, char_width, clear_char_widths, set_ambiguous_wide, set_char_width,
+    width_tables, WidthTables, UNICODE_WIDTH_VERSION,
+};
 
 /// Represents a color for terminal output.
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Color {
     Black,
     Red,
@@ -25,73 +117,356 @@ pub enum Color {
     Color256(u8),
     /// Hexadecimal color code.
     Hex(u32),
+    /// The terminal's own default color, resetting only the text or background.
+    ///
+    /// ```
+    /// use clwind::{clw, Color, ColorChoice};
+    ///
+    /// let reset = clw("x").text(Color::Default).color_choice(ColorChoice::Always);
+    ///
+    /// assert_eq!(reset.to_string(), "\x1b[39mx\x1b[0m");
+    /// ```
+    Default,
 }
 
+/// The names of the 16 base colors, in palette order.
+const BASE_NAMES: [&str; 16] = [
+    "black",
+    "red",
+    "green",
+    "yellow",
+    "blue",
+    "magenta",
+    "cyan",
+    "white",
+    "bright-black",
+    "bright-red",
+    "bright-green",
+    "bright-yellow",
+    "bright-blue",
+    "bright-magenta",
+    "bright-cyan",
+    "bright-white",
+];
+
+/// The 16 base colors, in palette order.
+const BASE_COLORS: [Color; 16] = [
+    Color::Black,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::White,
+    Color::BrightBlack,
+    Color::BrightRed,
+    Color::BrightGreen,
+    Color::BrightYellow,
+    Color::BrightBlue,
+    Color::BrightMagenta,
+    Color::BrightCyan,
+    Color::BrightWhite,
+];
+
 impl Color {
-    /// ## Builds the ANSI escape code for the specified color.
-    fn to_ansi_code(self) -> String {
+    /// ## Writes the ANSI escape code for the specified color.
+    fn write_ansi_code(self, out: &mut dyn fmt::Write) -> fmt::Result {
+        match self {
+            Color::Rgb(..) | Color::Hex(_) => {
+                let (r, g, b) = self.to_rgb();
+                write!(out, "38;2;{};{};{}", r, g, b)
+            }
+            Color::Color256(c) => write!(out, "38;5;{}", c),
+            _ => write!(out, "{}", self.base_ansi_code()),
+        }
+    }
+
+    /// ## Returns the text color code of a named color or `Default`.
+    ///
+    /// Background codes are 10 higher.
+    fn base_ansi_code(self) -> u8 {
+        match self {
+            Color::Black => 30,
+            Color::Red => 31,
+            Color::Green => 32,
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Magenta => 35,
+            Color::Cyan => 36,
+            Color::White => 37,
+            Color::BrightBlack => 90,
+            Color::BrightRed => 91,
+            Color::BrightGreen => 92,
+            Color::BrightYellow => 93,
+            Color::BrightBlue => 94,
+            Color::BrightMagenta => 95,
+            Color::BrightCyan => 96,
+            Color::BrightWhite => 97,
+            _ => 39,
+        }
+    }
+
+    /// ## Returns the red, green, and blue components of the color.
+    ///
+    /// Named colors use the xterm default palette, `Default` is taken to be white.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::Color;
+    ///
+    /// assert_eq!(Color::Hex(0xff8800).to_rgb(), (255, 136, 0));
+    /// assert_eq!(Color::Red.to_rgb(), (205, 0, 0));
+    /// ```
+    pub fn to_rgb(self) -> (u8, u8, u8) {
+        match self {
+            Color::Black => (0, 0, 0),
+            Color::Red => (205, 0, 0),
+            Color::Green => (0, 205, 0),
+            Color::Yellow => (205, 205, 0),
+            Color::Blue => (0, 0, 238),
+            Color::Magenta => (205, 0, 205),
+            Color::Cyan => (0, 205, 205),
+            Color::White => (229, 229, 229),
+            Color::BrightBlack => (127, 127, 127),
+            Color::BrightRed => (255, 0, 0),
+            Color::BrightGreen => (0, 255, 0),
+            Color::BrightYellow => (255, 255, 0),
+            Color::BrightBlue => (92, 92, 255),
+            Color::BrightMagenta => (255, 0, 255),
+            Color::BrightCyan => (0, 255, 255),
+            Color::BrightWhite => (255, 255, 255),
+            Color::Default => Color::White.to_rgb(),
+            Color::Rgb(r, g, b) => (r, g, b),
+            Color::Hex(h) => ((h >> 16) as u8, ((h >> 8) & 0xFF) as u8, (h & 0xFF) as u8),
+            Color::Color256(c) => match c {
+                0..=15 => BASE_COLORS[c as usize].to_rgb(),
+                16..=231 => {
+                    let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+                    let c = c - 16;
+                    (level(c / 36), level((c / 6) % 6), level(c % 6))
+                }
+                _ => {
+                    let gray = 8 + (c - 232) * 10;
+                    (gray, gray, gray)
+                }
+            },
+        }
+    }
+
+    /// ## Converts the color to the nearest one a terminal with the given level can show.
+    ///
+    /// Returns `None` for `ColorLevel::None`, where no color can be shown.
+    ///
+    /// ### Arguments
+    ///
+    /// * `level` - The colors supported by the terminal
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{Color, ColorLevel};
+    ///
+    /// let orange = Color::Hex(0xff8700);
+    ///
+    /// assert_eq!(orange.downgrade(ColorLevel::Ansi256), Some(Color::Color256(208)));
+    /// assert_eq!(orange.downgrade(ColorLevel::Ansi16), Some(Color::Yellow));
+    /// ```
+    pub fn downgrade(self, level: ColorLevel) -> Option<Color> {
+        let nearest = |candidates: &mut dyn Iterator<Item = Color>| {
+            let (r, g, b) = self.to_rgb();
+            candidates.min_by_key(|c| {
+                let (cr, cg, cb) = c.to_rgb();
+                let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+                d(r, cr) + d(g, cg) + d(b, cb)
+            })
+        };
+
+        match (level, self) {
+            (ColorLevel::None, _) => None,
+            (ColorLevel::TrueColor, _) => Some(self),
+            (ColorLevel::Ansi256, Color::Rgb(..) | Color::Hex(_)) => {
+                nearest(&mut (16..=255).map(Color::Color256))
+            }
+            (ColorLevel::Ansi16, Color::Rgb(..) | Color::Hex(_) | Color::Color256(16..)) => {
+                nearest(&mut BASE_COLORS.into_iter())
+            }
+            (_, Color::Color256(c @ 0..=15)) => Some(BASE_COLORS[c as usize]),
+            _ => Some(self),
+        }
+    }
+
+    /// ## Parses a CSS-style hex color: `#RGB`, `#RRGGBB` or `#RRGGBBAA`.
+    ///
+    /// The leading `#` is optional. Terminals can't blend colors, so the alpha
+    /// channel of `#RRGGBBAA` is ignored.
+    ///
+    /// ### Arguments
+    ///
+    /// * `hex` - The color to parse
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::Color;
+    ///
+    /// assert_eq!(Color::from_hex_str("#ff8800"), Ok(Color::Rgb(255, 136, 0)));
+    /// assert_eq!(Color::from_hex_str("f80"), Ok(Color::Rgb(255, 136, 0)));
+    /// assert!(Color::from_hex_str("#ff88").is_err());
+    /// ```
+    pub fn from_hex_str(hex: &str) -> Result<Color, ParseColorError> {
+        let error = || ParseColorError {
+            input: hex.to_string(),
+        };
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+        if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(error());
+        }
+
+        let channel = |i: usize, len: usize| {
+            let value = u8::from_str_radix(&digits[i * len..(i + 1) * len], 16).unwrap_or(0);
+            match len {
+                1 => value * 17,
+                _ => value,
+            }
+        };
+        match digits.len() {
+            3 => Ok(Color::Rgb(channel(0, 1), channel(1, 1), channel(2, 1))),
+            6 | 8 => Ok(Color::Rgb(channel(0, 2), channel(1, 2), channel(2, 2))),
+            _ => Err(error()),
+        }
+    }
+
+    /// ## Writes the ANSI escape code for the specified background color.
+    fn write_bg_ansi_code(self, out: &mut dyn fmt::Write) -> fmt::Result {
         match self {
-            Color::Black => "30",
-            Color::Red => "31",
-            Color::Green => "32",
-            Color::Yellow => "33",
-            Color::Blue => "34",
-            Color::Magenta => "35",
-            Color::Cyan => "36",
-            Color::White => "37",
-            Color::BrightBlack => "90",
-            Color::BrightRed => "91",
-            Color::BrightGreen => "92",
-            Color::BrightYellow => "93",
-            Color::BrightBlue => "94",
-            Color::BrightMagenta => "95",
-            Color::BrightCyan => "96",
-            Color::BrightWhite => "97",
-            Color::Rgb(r, g, b) => return format!("38;2;{};{};{}", r, g, b),
-            Color::Color256(c) => return format!("38;5;{}", c),
-            Color::Hex(h) => {
-                let r = (h >> 16) as u8;
-                let g = ((h >> 8) & 0xFF) as u8;
-                let b = (h & 0xFF) as u8;
-                println!("{} {} {}", r, g, b);
-                return format!("38;2;{};{};{}", r, g, b);
+            Color::Rgb(..) | Color::Hex(_) => {
+                let (r, g, b) = self.to_rgb();
+                write!(out, "48;2;{};{};{}", r, g, b)
             }
+            Color::Color256(c) => write!(out, "48;5;{}", c),
+            _ => write!(out, "{}", self.base_ansi_code() + 10),
         }
-        .to_string()
     }
 
-    /// ## Builds the ANSI escape code for the specified background color.
-    fn to_bg_ansi_code(self) -> String {
+    /// ## Writes the ANSI escape code for the specified underline color.
+    fn write_underline_ansi_code(self, out: &mut dyn fmt::Write) -> fmt::Result {
         match self {
-            Color::Rgb(r, g, b) => format!("48;2;{};{};{}", r, g, b),
-            Color::Color256(c) => format!("48;5;{}", c),
-            Color::Hex(h) => {
-                let r = (h >> 16) as u8;
-                let g = ((h >> 8) & 0xFF) as u8;
-                let b = (h & 0xFF) as u8;
-                format!("48;2;{};{};{}", r, g, b)
+            Color::Rgb(..) | Color::Hex(_) => {
+                let (r, g, b) = self.to_rgb();
+                write!(out, "58;2;{};{};{}", r, g, b)
             }
-            _ => (self.to_ansi_code().parse::<u8>().unwrap_or(30) + 10).to_string(),
+            Color::Color256(c) => write!(out, "58;5;{}", c),
+            Color::Default => out.write_str("59"),
+            _ => {
+                let index = BASE_COLORS.iter().position(|&c| c == self).unwrap_or(0);
+                write!(out, "58;5;{}", index)
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for Color {
+    type Err = ParseColorError;
+
+    /// ## Parses a color from its textual form.
+    ///
+    /// Accepts, in order: base color names such as `red` or `bright-red`
+    /// (using the terminal palette), `default`, CSS color names, Tailwind
+    /// colors such as `red-500`, and hex colors, see [`Color::from_hex_str`].
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::Color;
+    ///
+    /// assert_eq!("bright_red".parse(), Ok(Color::BrightRed));
+    /// assert_eq!("rebeccapurple".parse(), Ok(Color::REBECCA_PURPLE));
+    /// assert!("redd".parse::<Color>().unwrap_err().to_string().contains("redd"));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let base = s.to_ascii_lowercase().replace('_', "-");
+        if let Some(i) = BASE_NAMES.iter().position(|&n| n == base) {
+            return Ok(BASE_COLORS[i]);
+        }
+
+        if base == "default" {
+            return Ok(Color::Default);
+        }
+
+        match Color::from_name(s).or_else(|| Color::tailwind(s)) {
+            Some(color) => Ok(color),
+            None => Color::from_hex_str(s),
         }
     }
 }
 
+/// The error returned when a string isn't a valid color.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseColorError {
+    input: String,
+}
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid color {:?}, expected a name such as `red` or `rebeccapurple`, \
+             a Tailwind color such as `red-500`, or a hex color such as `#ff8800`",
+            self.input
+        )
+    }
+}
+
+impl std::error::Error for ParseColorError {}
+
+/// The name of every style, as accepted by `Style::from_str`.
+const STYLE_NAMES: [(Style, &str); 13] = [
+    (Style::Bold, "bold"),
+    (Style::Dim, "dim"),
+    (Style::Italic, "italic"),
+    (Style::Underline, "underline"),
+    (Style::Blink, "blink"),
+    (Style::Reverse, "reverse"),
+    (Style::Hidden, "hidden"),
+    (Style::Strikethrough, "strikethrough"),
+    (Style::DoubleUnderline, "double-underline"),
+    (Style::CurlyUnderline, "curly-underline"),
+    (Style::DottedUnderline, "dotted-underline"),
+    (Style::DashedUnderline, "dashed-underline"),
+    (Style::Overline, "overline"),
+];
+
 /// Represents a text style for terminal output.
-#[derive(Clone, Copy)]
+///
+/// The variants carry no number: underline variants are written as SGR
+/// sub-parameters such as `4:3`, which don't fit a single code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Style {
-    Bold = 1,
-    Dim = 2,
-    Italic = 3,
-    Underline = 4,
-    Blink = 5,
-    Reverse = 7,
-    Hidden = 8,
-    Strikethrough = 9,
+    Bold,
+    Dim,
+    Italic,
+    Underline,
+    Blink,
+    Reverse,
+    Hidden,
+    Strikethrough,
+    /// Double underline, `4:2`.
+    DoubleUnderline,
+    /// Curly underline, `4:3`, as used for errors by editors.
+    CurlyUnderline,
+    /// Dotted underline, `4:4`.
+    DottedUnderline,
+    /// Dashed underline, `4:5`.
+    DashedUnderline,
+    Overline,
 }
 
 impl Style {
-    /// ## Builds the ANSI escape code for the specified style.
-    fn to_ansi_code(self) -> String {
+    /// ## Returns the ANSI escape code for the specified style.
+    fn ansi_code(self) -> &'static str {
         match self {
             Style::Bold => "1",
             Style::Dim => "2",
@@ -101,20 +476,486 @@ impl Style {
             Style::Reverse => "7",
             Style::Hidden => "8",
             Style::Strikethrough => "9",
+            Style::DoubleUnderline => "4:2",
+            Style::CurlyUnderline => "4:3",
+            Style::DottedUnderline => "4:4",
+            Style::DashedUnderline => "4:5",
+            Style::Overline => "53",
         }
-        .to_string()
     }
 }
 
-/// Represents a colored and styled text for terminal output.
-pub struct CLW {
-    value: String,
-    bg: Option<Color>,
+impl std::str::FromStr for Style {
+    type Err = ParseStyleError;
+
+    /// ## Parses a style from its name, e.g. `bold` or `curly-underline`.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::Style;
+    ///
+    /// assert_eq!("curly_underline".parse(), Ok(Style::CurlyUnderline));
+    /// assert!("bolder".parse::<Style>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_ascii_lowercase().replace('_', "-");
+        STYLE_NAMES
+            .iter()
+            .find(|&&(_, n)| n == name)
+            .map(|&(style, _)| style)
+            .ok_or_else(|| ParseStyleError {
+                input: s.to_string(),
+            })
+    }
+}
+
+/// The error returned when a string isn't a valid style name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseStyleError {
+    input: String,
+}
+
+impl fmt::Display for ParseStyleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<&str> = STYLE_NAMES.iter().map(|&(_, n)| n).collect();
+        write!(
+            f,
+            "invalid style {:?}, expected one of {}",
+            self.input,
+            names.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for ParseStyleError {}
+
+/// Represents a reusable combination of colors and styles, detached from any text.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct StyleSpec {
     text: Option<Color>,
-    font: Vec<Style>,
+    bg: Option<Color>,
+    underline: Option<Color>,
+    font: Styles,
+}
+
+/// The styles of a spec in the order they were added, stored inline without duplicates.
+#[derive(Clone, Copy)]
+pub(crate) struct Styles {
+    styles: [Style; STYLE_NAMES.len()],
+    len: u8,
+}
+
+impl Styles {
+    /// ## Adds a style, unless it was already added.
+    pub(crate) fn push(&mut self, style: Style) {
+        if !self.contains(&style) {
+            self.styles[self.len as usize] = style;
+            self.len += 1;
+        }
+    }
+
+    /// ## Keeps only the styles for which `keep` returns true.
+    pub(crate) fn retain(&mut self, mut keep: impl FnMut(&Style) -> bool) {
+        *self = self.iter().copied().filter(|s| keep(s)).collect();
+    }
+}
+
+impl Default for Styles {
+    fn default() -> Self {
+        Styles {
+            styles: [Style::Bold; STYLE_NAMES.len()],
+            len: 0,
+        }
+    }
+}
+
+impl std::ops::Deref for Styles {
+    type Target = [Style];
+
+    fn deref(&self) -> &[Style] {
+        &self.styles[..self.len as usize]
+    }
+}
+
+impl Extend<Style> for Styles {
+    fn extend<I: IntoIterator<Item = Style>>(&mut self, styles: I) {
+        for style in styles {
+            self.push(style);
+        }
+    }
+}
+
+impl FromIterator<Style> for Styles {
+    fn from_iter<I: IntoIterator<Item = Style>>(styles: I) -> Self {
+        let mut set = Styles::default();
+        set.extend(styles);
+        set
+    }
+}
+
+impl PartialEq for Styles {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl Eq for Styles {}
+
+impl fmt::Debug for Styles {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl StyleSpec {
+    /// ## Creates an empty `StyleSpec` that leaves text unstyled.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{Color, Style, StyleSpec};
+    ///
+    /// let spec = StyleSpec::new().text(Color::Red).font(Style::Bold);
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// ## Sets the text color.
+    ///
+    /// ### Arguments
+    ///
+    /// * `color` - The color to set for the text
+    pub fn text(mut self, color: Color) -> Self {
+        self.text = Some(color);
+        self
+    }
+
+    /// ## Sets the background color.
+    ///
+    /// ### Arguments
+    ///
+    /// * `color` - The color to set for the background
+    pub fn bg(mut self, color: Color) -> Self {
+        self.bg = Some(color);
+        self
+    }
+
+    /// ## Sets the underline color, leaving the text color as is.
+    ///
+    /// ### Arguments
+    ///
+    /// * `color` - The color to set for underlines
+    pub fn underline(mut self, color: Color) -> Self {
+        self.underline = Some(color);
+        self
+    }
+
+    /// ## Adds a style to the text.
+    ///
+    /// Adding a style twice has no further effect.
+    ///
+    /// ### Arguments
+    ///
+    /// * `style` - The style to add to the text
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{Style, StyleSpec};
+    ///
+    /// let spec = StyleSpec::new().font(Style::Bold).font(Style::Italic).font(Style::Bold);
+    ///
+    /// assert_eq!(spec.fonts(), &[Style::Bold, Style::Italic]);
+    /// ```
+    pub fn font(mut self, style: Style) -> Self {
+        self.font.push(style);
+        self
+    }
+
+    /// ## Removes a style, if it was added.
+    ///
+    /// ### Arguments
+    ///
+    /// * `style` - The style to remove
+    pub fn without_font(mut self, style: Style) -> Self {
+        self.font.retain(|&s| s != style);
+        self
+    }
+
+    /// ## Returns the text color, if any.
+    pub fn text_color(&self) -> Option<Color> {
+        self.text
+    }
+
+    /// ## Returns the background color, if any.
+    pub fn bg_color(&self) -> Option<Color> {
+        self.bg
+    }
+
+    /// ## Returns the underline color, if any.
+    pub fn underline_color(&self) -> Option<Color> {
+        self.underline
+    }
+
+    /// ## Returns the styles, in the order they were added.
+    pub fn fonts(&self) -> &[Style] {
+        &self.font
+    }
+
+    /// ## Creates a `CLW` instance with the given text styled by this spec.
+    ///
+    /// ### Arguments
+    ///
+    /// * `value` - The text to be styled
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{clw, Color, StyleSpec};
+    ///
+    /// let spec = StyleSpec::new().text(Color::Green);
+    ///
+    /// assert_eq!(spec.apply("ok").to_string(), clw("ok").text_green().to_string());
+    /// ```
+    pub fn apply<'a>(&self, value: impl IntoText<'a>) -> CLW<'a> {
+        CLW {
+            spec: self.clone(),
+            ..CLW::new(value.into_text())
+        }
+    }
+
+    /// ## Returns the ANSI codes for this spec, with colors converted to ones a
+    /// terminal with the given level can show.
+    pub(crate) fn sgr(&self, level: ColorLevel) -> Sgr<'_> {
+        Sgr { spec: self, level }
+    }
+
+    /// ## Layers this spec over a base, which fills in the colors it doesn't set.
+    ///
+    /// The colors of this spec win, and the styles of both are combined.
+    pub(crate) fn over(mut self, base: &StyleSpec) -> StyleSpec {
+        self.text = self.text.or(base.text);
+        self.bg = self.bg.or(base.bg);
+        self.underline = self.underline.or(base.underline);
+        self.font.extend(base.font.iter().copied());
+        self
+    }
+}
+
+/// The ANSI codes of a spec, displayed separated by `;` without allocating.
+pub(crate) struct Sgr<'a> {
+    spec: &'a StyleSpec,
+    level: ColorLevel,
+}
+
+impl Sgr<'_> {
+    /// ## Returns the colors that are shown at the level, in text, background, underline order.
+    fn colors(&self) -> [Option<Color>; 3] {
+        let downgrade = |c: Option<Color>| c.and_then(|c| c.downgrade(self.level));
+        [
+            downgrade(self.spec.text),
+            downgrade(self.spec.bg),
+            downgrade(self.spec.underline),
+        ]
+    }
+
+    /// ## Returns true if there are no codes, so the text can be written as it is.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.spec.font.is_empty() && self.colors().iter().all(Option::is_none)
+    }
+}
+
+impl fmt::Display for Sgr<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [text, bg, underline] = self.colors();
+        let mut separator = "";
+        let mut next =
+            |f: &mut fmt::Formatter<'_>| f.write_str(std::mem::replace(&mut separator, ";"));
+        if let Some(color) = text {
+            next(f)?;
+            color.write_ansi_code(f)?;
+        }
+
+        if let Some(color) = bg {
+            next(f)?;
+            color.write_bg_ansi_code(f)?;
+        }
+
+        if let Some(color) = underline {
+            next(f)?;
+            color.write_underline_ansi_code(f)?;
+        }
+
+        for style in self.spec.font.iter() {
+            next(f)?;
+            f.write_str(style.ansi_code())?;
+        }
+        Ok(())
+    }
+}
+
+/// A transformation applied to every spec before rendering, see [`set_transform`].
+pub type Transform = fn(StyleSpec) -> StyleSpec;
+
+/// The transformation applied to every spec before rendering.
+static TRANSFORM: RwLock<Option<Transform>> = RwLock::new(None);
+
+/// ## Sets a transformation applied to every style before it is rendered.
+///
+/// This is a cross-cutting hook for applications, e.g. dimming everything in a
+/// "focus mode", stripping blink globally, or mapping colors for screenshots.
+/// It replaces any previously set transformation.
+///
+/// ### Arguments
+///
+/// * `transform` - Maps the spec about to be rendered to the spec to use instead
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{clear_transform, clw, set_transform, Style};
+///
+/// set_transform(|spec| spec.without_font(Style::Blink));
+/// assert_eq!(clw("calm").font_blink().to_string(), "calm");
+/// clear_transform();
+/// ```
+pub fn set_transform(transform: Transform) {
+    *TRANSFORM.write().unwrap_or_else(|e| e.into_inner()) = Some(transform);
+}
+
+/// ## Removes the transformation set by [`set_transform`].
+pub fn clear_transform() {
+    *TRANSFORM.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// ## Applies the transformation set by [`set_transform`] to a spec.
+///
+/// Built-in renderers call this; custom [`Renderer`]s should too.
+pub fn apply_transform(spec: &StyleSpec) -> Cow<'_, StyleSpec> {
+    match *TRANSFORM.read().unwrap_or_else(|e| e.into_inner()) {
+        Some(transform) => Cow::Owned(transform(spec.clone())),
+        None => Cow::Borrowed(spec),
+    }
+}
+
+/// The text of a [`CLW`], either a string or a `Display` value formatted the
+/// first time it is read.
+#[derive(Clone)]
+enum Text<'a> {
+    Str(Cow<'a, str>),
+    Lazy(Arc<dyn fmt::Display + Send + Sync + 'a>, OnceLock<String>),
+}
+
+impl Text<'_> {
+    /// ## Returns the text as an owned string, formatting it if needed.
+    fn into_owned(self) -> String {
+        match self {
+            Text::Str(text) => text.into_owned(),
+            Text::Lazy(value, text) => text.into_inner().unwrap_or_else(|| value.to_string()),
+        }
+    }
+}
+
+impl Deref for Text<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        match self {
+            Text::Str(text) => text,
+            Text::Lazy(value, text) => text.get_or_init(|| value.to_string()),
+        }
+    }
+}
+
+impl fmt::Debug for Text<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<'a> From<Cow<'a, str>> for Text<'a> {
+    fn from(text: Cow<'a, str>) -> Self {
+        Text::Str(text)
+    }
+}
+
+impl From<String> for Text<'_> {
+    fn from(text: String) -> Self {
+        Text::Str(Cow::Owned(text))
+    }
+}
+
+/// ## Converts a value into the text of a [`CLW`], see [`clw`].
+///
+/// String slices are borrowed and a `String` is moved in, so the text is never
+/// copied. Numbers, characters and booleans are formatted. Other `Display`
+/// values go through [`clw_display`].
+pub trait IntoText<'a> {
+    /// ## Returns the text of the value.
+    fn into_text(self) -> Cow<'a, str>;
+}
+
+impl<'a> IntoText<'a> for &'a str {
+    fn into_text(self) -> Cow<'a, str> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl<'a> IntoText<'a> for &'a String {
+    fn into_text(self) -> Cow<'a, str> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl<'a> IntoText<'a> for String {
+    fn into_text(self) -> Cow<'a, str> {
+        Cow::Owned(self)
+    }
+}
+
+impl<'a> IntoText<'a> for Cow<'a, str> {
+    fn into_text(self) -> Cow<'a, str> {
+        self
+    }
+}
+
+/// Implements [`IntoText`] by formatting the value.
+macro_rules! into_text_by_display {
+    ($($ty:ty),*) => {$(
+        impl<'a> IntoText<'a> for $ty {
+            fn into_text(self) -> Cow<'a, str> {
+                Cow::Owned(self.to_string())
+            }
+        }
+    )*};
+}
+
+into_text_by_display!(
+    char, bool, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64
+);
+
+/// Represents a colored and styled text for terminal output.
+#[derive(Clone, Debug)]
+pub struct CLW<'a> {
+    value: Text<'a>,
+    spec: StyleSpec,
+    link: Option<String>,
+    link_id: Option<String>,
+    choice: Option<ColorChoice>,
+    width: Option<usize>,
+    align: Align,
+    wrap: Option<usize>,
+    line_codes: bool,
 }
 
-impl CLW {
+impl<'a> CLW<'a> {
     /// ## Creates a new `CLW` instance with the given text.
     ///
     /// ### Arguments
@@ -128,12 +969,17 @@ impl CLW {
     ///
     /// let styled_text = clw("Hello, world!");
     /// ```
-    fn new<S: Into<String>>(value: S) -> Self {
+    fn new<S: Into<Cow<'a, str>>>(value: S) -> Self {
         CLW {
-            value: value.into(),
-            text: None,
-            bg: None,
-            font: Vec::new(),
+            value: Text::Str(value.into()),
+            spec: StyleSpec::new(),
+            link: None,
+            link_id: None,
+            choice: None,
+            width: None,
+            align: Align::Left,
+            wrap: None,
+            line_codes: false,
         }
     }
 
@@ -153,7 +999,7 @@ impl CLW {
     /// let color256_text = clw("256-color text").text(Color::Color256(118));
     /// ```
     pub fn text(mut self, color: Color) -> Self {
-        self.text = Some(color);
+        self.spec.text = Some(color);
         self
     }
 
@@ -173,7 +1019,7 @@ impl CLW {
     /// let color256_bg = clw("256-color background").bg(Color::Color256(118));
     /// ```
     pub fn bg(mut self, color: Color) -> Self {
-        self.bg = Some(color);
+        self.spec.bg = Some(color);
         self
     }
 
@@ -188,13 +1034,76 @@ impl CLW {
     /// ```
     /// use clwind::{clw, Style};
     ///
-    /// let bold_text = clw("Bold text").font(Style::Bold);
-    /// let italic_text = clw("Italic text").font(Style::Italic);
-    /// let underline_text = clw("Underlined text").font(Style::Underline);
+    /// let bold_text = clw("Bold text").font(Style::Bold);
+    /// let italic_text = clw("Italic text").font(Style::Italic);
+    /// let underline_text = clw("Underlined text").font(Style::Underline);
+    /// ```
+    pub fn font(mut self, style: Style) -> Self {
+        self.spec.font.push(style);
+        self
+    }
+
+    /// ## Sets the underline color, so underlines can differ from the text color.
+    ///
+    /// Terminals without support for underline colors ignore it.
+    ///
+    /// ### Arguments
+    ///
+    /// * `color` - The color to set for underlines
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{clw, Color};
+    ///
+    /// let typo = clw("teh").font_underline().underline_color(Color::Rgb(255, 0, 0));
+    ///
+    /// println!("{}", typo);
+    /// ```
+    pub fn underline_color(mut self, color: Color) -> Self {
+        self.spec.underline = Some(color);
+        self
+    }
+
+    /// ## Returns the text without any styling.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::clw;
+    ///
+    /// assert_eq!(clw("Hello").text_red().plain(), "Hello");
+    /// ```
+    pub fn plain(&self) -> &str {
+        &self.value
+    }
+
+    /// ## Copies borrowed text into the value, so it can outlive the text.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{clw, CLW};
+    ///
+    /// let label: CLW<'static> = {
+    ///     let name = String::from("build");
+    ///     clw(name.as_str()).font_bold().into_owned()
+    /// };
+    ///
+    /// assert_eq!(label.plain(), "build");
     /// ```
-    pub fn font(mut self, style: Style) -> Self {
-        self.font.push(style);
-        self
+    pub fn into_owned(self) -> CLW<'static> {
+        CLW {
+            value: self.value.into_owned().into(),
+            spec: self.spec,
+            link: self.link,
+            link_id: self.link_id,
+            choice: self.choice,
+            width: self.width,
+            align: self.align,
+            wrap: self.wrap,
+            line_codes: self.line_codes,
+        }
     }
 
     /// ## Sets the text color to black.
@@ -800,56 +1709,528 @@ impl CLW {
         self.font(Style::Strikethrough)
     }
 
+    /// ## Makes the text double underlined.
+    ///
+    /// Terminals without support for the style show a plain underline.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::clw;
+    ///
+    /// let text = clw("Styled text").font_double_underline();
+    ///
+    /// println!("{}", text);
+    /// ```
+    pub fn font_double_underline(self) -> Self {
+        self.font(Style::DoubleUnderline)
+    }
+
+    /// ## Makes the text underlined with a curly line.
+    ///
+    /// Terminals without support for the style show a plain underline.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::clw;
+    ///
+    /// let text = clw("Styled text").font_curly_underline();
+    ///
+    /// println!("{}", text);
+    /// ```
+    pub fn font_curly_underline(self) -> Self {
+        self.font(Style::CurlyUnderline)
+    }
+
+    /// ## Makes the text underlined with a dotted line.
+    ///
+    /// Terminals without support for the style show a plain underline.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::clw;
+    ///
+    /// let text = clw("Styled text").font_dotted_underline();
+    ///
+    /// println!("{}", text);
+    /// ```
+    pub fn font_dotted_underline(self) -> Self {
+        self.font(Style::DottedUnderline)
+    }
+
+    /// ## Makes the text underlined with a dashed line.
+    ///
+    /// Terminals without support for the style show a plain underline.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::clw;
+    ///
+    /// let text = clw("Styled text").font_dashed_underline();
+    ///
+    /// println!("{}", text);
+    /// ```
+    pub fn font_dashed_underline(self) -> Self {
+        self.font(Style::DashedUnderline)
+    }
+
+    /// ## Draws a line over the text.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{clw, RenderMode};
+    ///
+    /// let overlined = clw("Overlined text").font_overline();
+    ///
+    /// assert_eq!(overlined.to_string_for(RenderMode::Test), "[Overline]Overlined text[/]");
+    /// ```
+    pub fn font_overline(self) -> Self {
+        self.font(Style::Overline)
+    }
+
+    /// ## Turns the text into a hyperlink to the given URL.
+    ///
+    /// Terminals that support OSC 8 show the text as a clickable link. Elsewhere
+    /// the configured [`LinkFallback`](link::LinkFallback) is used.
+    ///
+    /// ### Arguments
+    ///
+    /// * `url` - The target of the link
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{clw, link::{set_hyperlinks, set_link_fallback, LinkFallback}};
+    ///
+    /// set_hyperlinks(false);
+    /// set_link_fallback(LinkFallback::Parens);
+    ///
+    /// let docs = clw("docs").link("https://example.com");
+    ///
+    /// assert!(docs.to_string().contains("(https://example.com)"));
+    /// ```
+    pub fn link<S: Into<String>>(mut self, url: S) -> Self {
+        self.link = Some(url.into());
+        self
+    }
+
+    /// ## Groups the link with other segments that share the same id.
+    ///
+    /// Terminals then treat a link split over several styled segments, or over
+    /// wrapped lines, as a single hover and click target. `:` and `;` are
+    /// removed from the id, as they would end it early.
+    ///
+    /// ### Arguments
+    ///
+    /// * `id` - An id shared by every segment of the link
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{clw, link::set_hyperlinks, set_colors_enabled};
+    ///
+    /// set_colors_enabled(true);
+    /// set_hyperlinks(true);
+    ///
+    /// let url = "https://example.com/guide";
+    /// let first = clw("the ").link(url).link_id("guide");
+    /// let second = clw("guide").font_bold().link(url).link_id("guide");
+    ///
+    /// assert!(first.to_string().starts_with("\x1b]8;id=guide;https://example.com/guide"));
+    /// println!("Read {}{}.", first, second);
+    /// ```
+    pub fn link_id<S: Into<String>>(mut self, id: S) -> Self {
+        self.link_id = Some(id.into().replace([':', ';'], ""));
+        self
+    }
+
+    /// ## Shortens a path or URL to at most `max` columns by eliding its middle.
+    ///
+    /// The scheme and host of URLs, or the first component of paths, are kept
+    /// together with the file name. The styling is left untouched.
+    ///
+    /// ### Arguments
+    ///
+    /// * `max` - The maximum width of the text
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::clw;
+    ///
+    /// let url = clw("https://example.com/docs/guide/install.html").truncate_path(34);
+    /// let path = clw("/home/user/projects/clwind/src/lib.rs").truncate_path(20);
+    ///
+    /// assert_eq!(url.plain(), "https://example.com/…/install.html");
+    /// assert_eq!(path.plain(), "/home/…/src/lib.rs");
+    /// ```
+    pub fn truncate_path(mut self, max: usize) -> Self {
+        self.value = text::shorten_path(&self.value, max).into();
+        self
+    }
+
+    /// ## Wraps the text to `width` columns when rendered.
+    ///
+    /// Each line gets its own escape codes, so backgrounds end with the text
+    /// of every line instead of leaking to the edge of the screen.
+    ///
+    /// ### Arguments
+    ///
+    /// * `width` - The maximum number of columns of a line
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{clw, set_colors_enabled, RenderMode};
+    ///
+    /// let notice = clw("the cache is almost full").bg_yellow().wrap(12);
+    ///
+    /// assert_eq!(
+    ///     notice.to_string_for(RenderMode::Test),
+    ///     "[bg=Yellow]the cache is[/]\n[bg=Yellow]almost full[/]"
+    /// );
+    ///
+    /// set_colors_enabled(true);
+    /// let padded = notice.width(30);
+    ///
+    /// assert_eq!(padded.render(), padded.to_string());
+    /// assert!(padded.render().contains("\x1b[0m\n\x1b[43malmost full"));
+    /// ```
+    pub fn wrap(mut self, width: usize) -> Self {
+        self.wrap = Some(width);
+        self
+    }
+
+    /// ## Closes the style before each line break and opens it again after it.
+    ///
+    /// Without it, a background of multi-line text runs to the edge of the
+    /// screen on some terminals and stops at the text on others, and pagers
+    /// that show lines separately lose the style after the first.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{clw, set_colors_enabled, RenderMode};
+    ///
+    /// set_colors_enabled(true);
+    /// let block = clw("one\ntwo").bg_blue();
+    ///
+    /// assert_eq!(block.to_string(), "\x1b[44mone\ntwo\x1b[0m");
+    /// assert_eq!(
+    ///     block.clone().per_line_codes().to_string(),
+    ///     "\x1b[44mone\x1b[0m\n\x1b[44mtwo\x1b[0m"
+    /// );
+    /// assert_eq!(
+    ///     block.per_line_codes().to_string_for(RenderMode::Test),
+    ///     "[bg=Blue]one[/]\n[bg=Blue]two[/]"
+    /// );
+    /// ```
+    pub fn per_line_codes(mut self) -> Self {
+        self.line_codes = true;
+        self
+    }
+
+    /// ## Cuts the text to at most `max` columns, ending it with `…`.
+    ///
+    /// Wide characters count as two columns. Escape sequences already in the
+    /// text are kept, so styles and links opened before the cut still close.
+    ///
+    /// ### Arguments
+    ///
+    /// * `max` - The maximum width of the text, ellipsis included
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{clw, ColorChoice};
+    ///
+    /// assert_eq!(clw("build succeeded").truncate(8).plain(), "build s…");
+    /// assert_eq!(clw("日本語のテキスト").truncate(7).plain(), "日本語…");
+    ///
+    /// let nested = format!("see {}", clw("the docs").font_bold().color_choice(ColorChoice::Always));
+    /// assert_eq!(clw(nested).truncate(7).plain(), "see \x1b[1mth…\x1b[0m");
+    /// ```
+    pub fn truncate(mut self, max: usize) -> Self {
+        self.value = text::truncate(&self.value, max).into();
+        self
+    }
+
+    /// ## Renders the styled text through the given renderer.
+    ///
+    /// ### Arguments
+    ///
+    /// * `renderer` - The backend to render with
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{clw, HtmlRenderer};
+    ///
+    /// let html = clw("<b>").text_red().render_with(&HtmlRenderer);
+    ///
+    /// assert_eq!(html, "<span style=\"color:#cd0000\">&lt;b&gt;</span>");
+    ///
+    /// let docs = clw("docs").font_underline().font_curly_underline().link("https://example.com/?a&b");
+    ///
+    /// assert_eq!(
+    ///     docs.render_with(&HtmlRenderer),
+    ///     "<a href=\"https://example.com/?a&amp;b\"><span style=\"text-decoration:underline wavy\">docs</span></a>"
+    /// );
+    /// ```
+    pub fn render_with(&self, renderer: &dyn Renderer) -> String {
+        let (before, after) = self.padding();
+        let body = match self.wrap {
+            Some(width) => text::wrap_words(&self.value, width)
+                .iter()
+                .map(|line| renderer.paint(line, &self.spec))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            None if self.line_codes => self
+                .value
+                .split('\n')
+                .map(|line| renderer.paint(line, &self.spec))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            None => renderer.paint(&self.value, &self.spec),
+        };
+        let body = match &self.link {
+            Some(url) => {
+                let mut linked = String::new();
+                renderer
+                    .render_link(&mut linked, url, self.link_id.as_deref(), &body)
+                    .expect("writing to a String cannot fail");
+                linked
+            }
+            None => body,
+        };
+        format!(
+            "{:before$}{}{:after$}",
+            "",
+            body,
+            "",
+            before = before,
+            after = after
+        )
+    }
+
+    /// ## Renders the styled text in the given output format.
+    ///
+    /// The same value can be rendered for the terminal, an HTML report, and a
+    /// plain log in one program run.
+    ///
+    /// ### Arguments
+    ///
+    /// * `mode` - The output format
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{clw, RenderMode};
+    ///
+    /// let status = clw("passed").text_green();
+    ///
+    /// assert_eq!(status.to_string_for(RenderMode::Plain), "passed");
+    /// assert_eq!(status.to_string_for(RenderMode::Test), "[fg=Green]passed[/]");
+    /// ```
+    pub fn to_string_for(&self, mode: RenderMode) -> String {
+        self.render_with(mode.renderer())
+    }
+
+    /// ## Renders the text wrapped in its escape codes, whatever the color choice.
+    ///
+    /// Colors are downgraded to the [`color_level`] of the terminal, and the text
+    /// is padded and wrapped like the `Display` output. Nothing is printed, so
+    /// the result can be written wherever it is needed.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{clw, set_color_level, Color, ColorLevel};
+    ///
+    /// set_color_level(ColorLevel::TrueColor);
+    ///
+    /// assert_eq!(clw("hi").text(Color::Hex(0xff8800)).render(), "\x1b[38;2;255;136;0mhi\x1b[0m");
+    /// assert_eq!(clw("ok").text_red().width(4).render(), "\x1b[31mok\x1b[0m  ");
+    /// ```
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        self.write_colorized(&mut out, true)
+            .expect("writing to a String cannot fail");
+        out
+    }
+
+    /// ## Returns the escape sequence that starts the style, or an empty string if
+    /// there is no style.
+    ///
+    /// Together with [`CLW::ansi_suffix`], this styles text written piece by
+    /// piece. Links are left out.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::clw;
+    ///
+    /// let error = clw("").text_red().font_bold();
+    ///
+    /// assert_eq!(error.ansi_prefix(), "\x1b[31;1m");
+    /// assert_eq!(error.ansi_suffix(), "\x1b[0m");
+    /// assert_eq!(clw("").ansi_prefix(), "");
+    /// ```
+    pub fn ansi_prefix(&self) -> String {
+        let spec = apply_transform(&self.spec);
+        let codes = spec.sgr(color_level());
+        match codes.is_empty() {
+            true => String::new(),
+            false => format!("\x1b[{}m", codes),
+        }
+    }
+
+    /// ## Returns the escape sequence that ends the style, or an empty string if
+    /// there is no style.
+    pub fn ansi_suffix(&self) -> String {
+        match apply_transform(&self.spec).sgr(color_level()).is_empty() {
+            true => String::new(),
+            false => "\x1b[0m".to_string(),
+        }
+    }
+
+    /// ## Overrides the global [`ColorChoice`] for this value.
+    ///
+    /// ### Arguments
+    ///
+    /// * `choice` - Whether this value emits escape codes
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{clw, ColorChoice};
+    ///
+    /// let forced = clw("always").text_red().color_choice(ColorChoice::Always);
+    ///
+    /// assert_eq!(forced.to_string(), "\x1b[31malways\x1b[0m");
+    /// ```
+    pub fn color_choice(mut self, choice: ColorChoice) -> Self {
+        self.choice = Some(choice);
+        self
+    }
+
+    /// ## Renders the text for a stream, without escape codes if it shouldn't be colorized.
+    fn for_stream(&self, stream: Stream) -> String {
+        let mut out = String::new();
+        self.write_styled(&mut out, Some(stream))
+            .expect("writing to a String cannot fail");
+        out
+    }
+
+    /// ## Writes the styled text, leaving out escape codes as the color choice decides.
+    ///
+    /// With [`track_stats`] on, the bytes written are counted for the stream.
+    /// The output hook, if any, sees the value first.
+    fn write_styled(&self, out: &mut dyn fmt::Write, stream: Option<Stream>) -> fmt::Result {
+        let colorize = self.choice.unwrap_or_else(color_choice).resolve(stream);
+        stats::write_counted(
+            out,
+            (&self.value, &self.spec),
+            stream,
+            colorize,
+            |out, colorize| self.write_colorized(out, colorize),
+        )
+    }
+
+    /// ## Writes the styled text, with or without escape codes.
+    ///
+    /// The padding set by [`CLW::width`] goes around the codes and the link.
+    fn write_colorized(&self, out: &mut dyn fmt::Write, colorize: bool) -> fmt::Result {
+        let (before, after) = self.padding();
+        write!(out, "{:1$}", "", before)?;
+        let body = |out: &mut dyn fmt::Write| match colorize {
+            true => render::write_ansi(out, &self.value, &self.spec, self.line_codes),
+            false => PlainRenderer.render(out, &self.value, &self.spec),
+        };
+        let linked = |out: &mut dyn fmt::Write| match &self.link {
+            Some(url) => link::write_link(out, url, self.link_id.as_deref(), colorize, body),
+            None => body(out),
+        };
+        match self.wrap {
+            Some(width) => {
+                let mut styled = String::new();
+                linked(&mut styled)?;
+                out.write_str(&text::wrap_ansi(&styled, width).join("\n"))?;
+            }
+            None => linked(out)?,
+        }
+        write!(out, "{:1$}", "", after)
+    }
+
     /// ## Prints the styled text to the standard output.
+    ///
+    /// Escape codes are left out as decided by the color choice for the stream,
+    /// see [`should_colorize`].
     pub fn print(&self) {
-        print!("{}", self);
+        #[cfg(windows)]
+        if console::print_legacy(self, Stream::Stdout, false) {
+            return;
+        }
+        print!("{}", self.for_stream(Stream::Stdout));
     }
 
     /// ## Prints the styled text to the standard output with a newline.
+    ///
+    /// Escape codes are left out as decided by the color choice for the stream,
+    /// see [`should_colorize`].
     pub fn println(&self) {
-        println!("{}", self);
+        #[cfg(windows)]
+        if console::print_legacy(self, Stream::Stdout, true) {
+            return;
+        }
+        println!("{}", self.for_stream(Stream::Stdout));
     }
 
     /// ## Prints the styled text to the standard error.
+    ///
+    /// Escape codes are left out as decided by the color choice for the stream,
+    /// see [`should_colorize`].
     pub fn eprint(&self) {
-        eprint!("{}", self);
+        #[cfg(windows)]
+        if console::print_legacy(self, Stream::Stderr, false) {
+            return;
+        }
+        eprint!("{}", self.for_stream(Stream::Stderr));
     }
 
     /// ## Prints the styled text to the standard error with a newline.
+    ///
+    /// Escape codes are left out as decided by the color choice for the stream,
+    /// see [`should_colorize`].
     pub fn eprintln(&self) {
-        eprintln!("{}", self);
+        #[cfg(windows)]
+        if console::print_legacy(self, Stream::Stderr, true) {
+            return;
+        }
+        eprintln!("{}", self.for_stream(Stream::Stderr));
     }
 }
 
-impl fmt::Display for CLW {
+impl fmt::Display for CLW<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut codes = Vec::new();
-        if let Some(color) = self.text {
-            codes.push(color.to_ansi_code());
-        }
-
-        if let Some(color) = self.bg {
-            codes.push(color.to_bg_ansi_code());
-        }
-
-        for style in &self.font {
-            codes.push(style.to_ansi_code());
-        }
-
-        match codes.len() {
-            0 => write!(f, "{}", self.value),
-            _ => write!(f, "\x1b[{}m{}\x1b[0m", codes.join(";"), self.value),
-        }
+        self.write_styled(f, None)
     }
 }
 
 /// #### Creates a new `CLW` instance with the given text.
 ///
-/// This is a convenience function for `CLW::new()`.
+/// This is a convenience function for `CLW::new()`. String literals and other
+/// borrowed text are not copied, a `String` is moved in, and numbers are
+/// formatted, see [`IntoText`]. Use [`clw_display`] for paths, errors and
+/// other `Display` values.
 ///
 /// ##### Arguments
 ///
-/// * `str` - The text to be styled
+/// * `value` - The text to be styled
 ///
 /// ##### Examples
 ///
@@ -863,7 +2244,67 @@ impl fmt::Display for CLW {
 ///     .font_blink();
 ///
 /// println!("{}", styled_text);
+/// assert_eq!(clw(String::from("moved")).text_green().plain(), "moved");
+/// assert_eq!(clw(42).text_green().plain(), "42");
+/// ```
+pub fn clw<'a>(value: impl IntoText<'a>) -> CLW<'a> {
+    CLW::new(value.into_text())
+}
+
+/// #### Creates a new `CLW` instance with any `Display` value as its text.
+///
+/// Formatting is deferred until the text is first needed, usually when it is
+/// rendered, and the result is kept for later renders. Values borrowed from
+/// the caller, such as `path.display()`, are not copied before that.
+///
+/// ##### Arguments
+///
+/// * `value` - The value to be styled
+///
+/// ##### Examples
+///
+/// ```
+/// use std::path::Path;
+/// use clwind::clw_display;
+///
+/// let path = Path::new("src/lib.rs");
+/// let styled = clw_display(path.display()).text_blue();
+///
+/// assert_eq!(styled.plain(), "src/lib.rs");
 /// ```
-pub fn clw(str: &str) -> CLW {
-    CLW::new(str)
+pub fn clw_display<'a, T: fmt::Display + Send + Sync + 'a>(value: T) -> CLW<'a> {
+    CLW {
+        value: Text::Lazy(Arc::new(value), OnceLock::new()),
+        ..CLW::new("")
+    }
+}
+
+/// ## Checks whether the `CLWIND_DEBUG` layout overlay is enabled.
+///
+/// When `CLWIND_DEBUG=1`, every rendered `CLW` is wrapped in visible markers
+/// showing its codes and width, e.g. `⟨31;1⟩text⟨/4⟩`. The variable is read once.
+pub(crate) fn debug_overlay() -> bool {
+    static DEBUG: OnceLock<bool> = OnceLock::new();
+    *DEBUG.get_or_init(|| std::env::var("CLWIND_DEBUG").is_ok_and(|v| v == "1"))
+}
+
+/// ## Returns the number of terminal columns available for layout.
+///
+/// Uses the width of the terminal, then the `COLUMNS` environment variable,
+/// and falls back to 80.
+pub(crate) fn terminal_width() -> usize {
+    if let Some((columns, _)) = terminal_size() {
+        return columns as usize;
+    }
+
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|c| c.trim().parse().ok())
+        .filter(|&c| c > 0)
+        .unwrap_or(80)
+}
+
+/// ## Returns the number of columns the given text occupies, ignoring escape sequences.
+pub(crate) fn text_width(text: &str) -> usize {
+    text::visible_width(text)
 }
@@ -0,0 +1,161 @@
+//! OSC 8 hyperlinks and how they degrade on terminals without support.
+
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Mutex;
+
+use crate::{capabilities, clw, osc, render, Style, StyleSpec};
+
+/// How a link is shown when the terminal doesn't support hyperlinks.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LinkFallback {
+    /// Only the link text is shown.
+    #[default]
+    Hide,
+    /// The URL is appended in parentheses, e.g. `docs (https://…)`.
+    Parens,
+    /// A numbered marker is appended, e.g. `docs[1]`, and the URL is collected
+    /// to be printed later with [`render_footnotes`].
+    Footnote,
+}
+
+/// The fallback set by [`set_link_fallback`].
+static FALLBACK: AtomicU8 = AtomicU8::new(0);
+/// `0` to detect hyperlink support, `1` if forced on, `2` if forced off.
+static SUPPORT: AtomicU8 = AtomicU8::new(0);
+/// The URLs collected by the `Footnote` fallback, in marker order.
+static FOOTNOTES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// ## Sets how links are shown when hyperlinks aren't supported.
+///
+/// ### Arguments
+///
+/// * `fallback` - The fallback to use
+pub fn set_link_fallback(fallback: LinkFallback) {
+    FALLBACK.store(fallback as u8, Ordering::Relaxed);
+}
+
+/// ## Returns how links are shown when hyperlinks aren't supported.
+pub fn link_fallback() -> LinkFallback {
+    match FALLBACK.load(Ordering::Relaxed) {
+        1 => LinkFallback::Parens,
+        2 => LinkFallback::Footnote,
+        _ => LinkFallback::Hide,
+    }
+}
+
+/// ## Forces hyperlinks on or off, overriding terminal detection.
+///
+/// ### Arguments
+///
+/// * `enabled` - Whether to emit OSC 8 hyperlinks
+pub fn set_hyperlinks(enabled: bool) {
+    SUPPORT.store(if enabled { 1 } else { 2 }, Ordering::Relaxed);
+}
+
+/// ## Checks whether OSC 8 hyperlinks are emitted.
+///
+/// Unless set with [`set_hyperlinks`], this is detected once from the
+/// environment of terminals known to support them, as part of the
+/// [`capabilities`].
+pub fn hyperlinks_supported() -> bool {
+    match SUPPORT.load(Ordering::Relaxed) {
+        1 => true,
+        2 => false,
+        _ => capabilities().hyperlinks,
+    }
+}
+
+/// ## Removes and returns the URLs collected by the `Footnote` fallback.
+pub fn take_footnotes() -> Vec<String> {
+    std::mem::take(&mut *FOOTNOTES.lock().unwrap_or_else(|e| e.into_inner()))
+}
+
+/// ## Renders and clears the collected footnotes, one `[n] url` per line.
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{clw, link::{render_footnotes, set_hyperlinks, set_link_fallback, LinkFallback}};
+///
+/// set_hyperlinks(false);
+/// set_link_fallback(LinkFallback::Footnote);
+///
+/// println!("See the {}.", clw("docs").link("https://example.com/docs"));
+/// println!("{}", render_footnotes());
+/// ```
+pub fn render_footnotes() -> String {
+    take_footnotes()
+        .iter()
+        .enumerate()
+        .map(|(i, url)| format!("{} {}", clw(format!("[{}]", i + 1)).font_dim(), url))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// ## Writes `body` as a hyperlink to `url`, or its fallback.
+///
+/// Segments sharing an `id` are grouped into one link by the terminal.
+/// Without `escapes`, the fallback is used and its markers are left unstyled.
+pub(crate) fn write_link(
+    out: &mut dyn fmt::Write,
+    url: &str,
+    id: Option<&str>,
+    escapes: bool,
+    body: impl FnOnce(&mut dyn fmt::Write) -> fmt::Result,
+) -> fmt::Result {
+    if escapes && hyperlinks_supported() {
+        write!(out, "{}", osc::hyperlink(url, id))?;
+        body(out)?;
+        return write!(out, "{}", osc::hyperlink_end());
+    }
+
+    body(out)?;
+    match link_fallback() {
+        LinkFallback::Hide => Ok(()),
+        LinkFallback::Parens => {
+            out.write_char(' ')?;
+            write_marker(out, &format!("({})", url), escapes)
+        }
+        LinkFallback::Footnote => {
+            let mut footnotes = FOOTNOTES.lock().unwrap_or_else(|e| e.into_inner());
+            let number = match footnotes.iter().position(|u| u == url) {
+                Some(i) => i + 1,
+                None => {
+                    footnotes.push(url.to_string());
+                    footnotes.len()
+                }
+            };
+            write_marker(out, &format!("[{}]", number), escapes)
+        }
+    }
+}
+
+/// ## Writes a fallback marker, dimmed when `escapes` is set.
+///
+/// The codes are written directly rather than through a `CLW`, so rendering
+/// the marker isn't recorded as a separate output in the stats.
+fn write_marker(out: &mut dyn fmt::Write, text: &str, escapes: bool) -> fmt::Result {
+    match escapes {
+        true => render::write_ansi(out, text, &StyleSpec::new().font(Style::Dim), false),
+        false => out.write_str(text),
+    }
+}
+
+/// ## Detects hyperlink support from the terminal's environment variables.
+///
+/// Unset variables are looked up as empty strings.
+pub(crate) fn detect_hyperlinks(var: &dyn Fn(&str) -> String) -> bool {
+    let term_program = var("TERM_PROGRAM");
+    let term = var("TERM");
+    let vte = var("VTE_VERSION").parse::<u32>().unwrap_or(0);
+
+    matches!(
+        term_program.as_str(),
+        "iTerm.app" | "WezTerm" | "vscode" | "ghostty" | "Hyper"
+    ) || !var("WT_SESSION").is_empty()
+        || !var("KITTY_WINDOW_ID").is_empty()
+        || term.contains("kitty")
+        || term.contains("alacritty")
+        || vte >= 5000
+}
@@ -0,0 +1,147 @@
+//! A [`log`] backend that colors records by level, enabled with the `log` feature.
+
+use std::io::Write;
+
+use log::{Level, LevelFilter, Log, Metadata, Record, SetLoggerError};
+
+use crate::{clw, Stream, Style, StyleSpec};
+
+/// A logger writing colored records, `LEVEL module: message`, to a standard stream.
+///
+/// Levels are styled with the current [theme](crate::theme) unless set with
+/// [`ClwindLogger::level_style`]: errors as `"error"`, warnings as
+/// `"warning"`, info as `"info"` and the rest as `"muted"`. Module paths are
+/// dim. Escape codes are left out when the stream isn't a terminal or
+/// `NO_COLOR` is set, like any other output.
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::logger::ClwindLogger;
+/// use log::LevelFilter;
+///
+/// ClwindLogger::new().level(LevelFilter::Debug).init().unwrap();
+/// log::warn!("cache is {}% full", 93);
+/// ```
+pub struct ClwindLogger {
+    level: LevelFilter,
+    stream: Stream,
+    styles: [Option<StyleSpec>; 5],
+}
+
+impl Default for ClwindLogger {
+    fn default() -> Self {
+        ClwindLogger {
+            level: LevelFilter::Info,
+            stream: Stream::Stderr,
+            styles: Default::default(),
+        }
+    }
+}
+
+impl ClwindLogger {
+    /// ## Creates a logger for `Info` and more severe records, written to the standard error.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// ## Sets the most verbose level that is logged.
+    pub fn level(mut self, level: LevelFilter) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// ## Sets the stream records are written to.
+    pub fn stream(mut self, stream: Stream) -> Self {
+        self.stream = stream;
+        self
+    }
+
+    /// ## Sets the style of a level's name, instead of the theme's.
+    ///
+    /// ### Arguments
+    ///
+    /// * `level` - The level to style
+    /// * `spec` - The style of its name
+    pub fn level_style(mut self, level: Level, spec: StyleSpec) -> Self {
+        self.styles[level as usize - 1] = Some(spec);
+        self
+    }
+
+    /// ## Installs the logger and sets the maximum level of the `log` macros.
+    ///
+    /// Returns an error if a logger was already installed.
+    pub fn init(self) -> Result<(), SetLoggerError> {
+        let level = self.level;
+        log::set_logger(Box::leak(Box::new(self)))?;
+        log::set_max_level(level);
+        Ok(())
+    }
+
+    /// ## Formats a record as the logger writes it, without the newline.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{logger::ClwindLogger, with_capabilities, Capabilities};
+    /// use log::{Level, Record};
+    ///
+    /// let record = Record::builder()
+    ///     .level(Level::Error)
+    ///     .module_path(Some("app::net"))
+    ///     .args(format_args!("connection refused"))
+    ///     .build();
+    /// let pipe = Capabilities::from_env(|_| None, false);
+    ///
+    /// assert_eq!(
+    ///     with_capabilities(pipe, || ClwindLogger::new().format(&record)),
+    ///     "ERROR app::net: connection refused"
+    /// );
+    /// ```
+    pub fn format(&self, record: &Record) -> String {
+        let level = record.level();
+        let name = match (&self.styles[level as usize - 1], level) {
+            (Some(spec), _) => spec.apply(level.as_str()),
+            (None, Level::Error) => clw(level.as_str()).error(),
+            (None, Level::Warn) => clw(level.as_str()).warning(),
+            (None, Level::Info) => clw(level.as_str()).info(),
+            (None, Level::Debug | Level::Trace) => clw(level.as_str()).style("muted"),
+        }
+        .pad_right(5);
+
+        let mut line = name.for_stream(self.stream);
+        if let Some(module) = record.module_path() {
+            let module = clw(format!("{}:", module)).font(Style::Dim);
+            line.push(' ');
+            line.push_str(&module.for_stream(self.stream));
+        }
+        line.push(' ');
+        line.push_str(&record.args().to_string());
+        line
+    }
+}
+
+impl Log for ClwindLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let line = format!("{}\n", self.format(record));
+        let _ = match self.stream {
+            Stream::Stdout => std::io::stdout().lock().write_all(line.as_bytes()),
+            Stream::Stderr => std::io::stderr().lock().write_all(line.as_bytes()),
+        };
+    }
+
+    fn flush(&self) {
+        let _ = match self.stream {
+            Stream::Stdout => std::io::stdout().flush(),
+            Stream::Stderr => std::io::stderr().flush(),
+        };
+    }
+}
@@ -0,0 +1,301 @@
+//! Man-page style help output, for the terminal or as roff.
+
+use std::fmt;
+
+use crate::text::wrap_words;
+use crate::{terminal_width, text_width, AnsiRenderer, Renderer, Style, StyleSpec};
+
+/// The indent of section bodies, as used by `man`.
+const INDENT: usize = 7;
+/// The widest option tag that keeps its description on the same line.
+const MAX_TAG: usize = 24;
+
+/// A man page with NAME, SYNOPSIS, DESCRIPTION and OPTIONS sections.
+///
+/// Placeholders written as `<FILE>` in the synopsis and option tags are
+/// underlined, following the man page convention.
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::manpage::ManPage;
+///
+/// let page = ManPage::new("mytool", "do things with files")
+///     .synopsis("mytool [OPTIONS] <FILE>")
+///     .description("Reads FILE and does things with it.")
+///     .option("-o, --output <PATH>", "Write the result to PATH instead of stdout.")
+///     .option("-v", "Print more.")
+///     .width(60);
+///
+/// println!("{}", page);
+/// assert!(page.to_roff().starts_with(".TH MYTOOL 1"));
+/// ```
+pub struct ManPage {
+    name: String,
+    summary: String,
+    section: u8,
+    synopsis: Vec<String>,
+    description: Vec<String>,
+    options: Vec<(String, String)>,
+    width: Option<usize>,
+}
+
+impl ManPage {
+    /// ## Creates a page for section 1 with the NAME line.
+    ///
+    /// ### Arguments
+    ///
+    /// * `name` - The command name
+    /// * `summary` - The one-line summary shown after the name
+    pub fn new(name: &str, summary: &str) -> Self {
+        ManPage {
+            name: name.to_string(),
+            summary: summary.to_string(),
+            section: 1,
+            synopsis: Vec::new(),
+            description: Vec::new(),
+            options: Vec::new(),
+            width: None,
+        }
+    }
+
+    /// ## Sets the manual section number.
+    pub fn section(mut self, section: u8) -> Self {
+        self.section = section;
+        self
+    }
+
+    /// ## Adds a usage line to the SYNOPSIS section.
+    pub fn synopsis(mut self, usage: &str) -> Self {
+        self.synopsis.push(usage.to_string());
+        self
+    }
+
+    /// ## Adds a paragraph to the DESCRIPTION section.
+    pub fn description(mut self, paragraph: &str) -> Self {
+        self.description.push(paragraph.to_string());
+        self
+    }
+
+    /// ## Adds an entry to the OPTIONS section.
+    ///
+    /// ### Arguments
+    ///
+    /// * `tag` - The flags, e.g. `-o, --output <FILE>`
+    /// * `description` - What the option does
+    pub fn option(mut self, tag: &str, description: &str) -> Self {
+        self.options
+            .push((tag.to_string(), description.to_string()));
+        self
+    }
+
+    /// ## Sets the width to wrap at, the terminal width by default.
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// ## Renders the page for the terminal.
+    pub fn render(&self) -> String {
+        self.render_with(&AnsiRenderer)
+    }
+
+    /// ## Renders the page through the given renderer.
+    ///
+    /// ### Arguments
+    ///
+    /// * `renderer` - The backend used to style the output
+    pub fn render_with(&self, renderer: &dyn Renderer) -> String {
+        let width = self.width.unwrap_or_else(terminal_width);
+        let bold = StyleSpec::new().font(Style::Bold);
+        let plain = StyleSpec::new();
+        let indent = " ".repeat(INDENT);
+        let mut sections = Vec::new();
+
+        let mut name = vec![renderer.paint("NAME", &bold)];
+        let line = format!("{} - {}", self.name, self.summary);
+        for line in wrap_words(&line, width.saturating_sub(INDENT)) {
+            name.push(format!("{}{}", indent, renderer.paint(&line, &plain)));
+        }
+        sections.push(name.join("\n"));
+
+        if !self.synopsis.is_empty() {
+            let mut lines = vec![renderer.paint("SYNOPSIS", &bold)];
+            for usage in &self.synopsis {
+                let (command, args) = usage.split_once(' ').unwrap_or((usage, ""));
+                let mut line = format!("{}{}", indent, renderer.paint(command, &bold));
+                if !args.is_empty() {
+                    line.push(' ');
+                    line.push_str(&paint_placeholders(renderer, args, &plain));
+                }
+                lines.push(line);
+            }
+            sections.push(lines.join("\n"));
+        }
+
+        if !self.description.is_empty() {
+            let mut lines = vec![renderer.paint("DESCRIPTION", &bold)];
+            for (i, paragraph) in self.description.iter().enumerate() {
+                if i > 0 {
+                    lines.push(String::new());
+                }
+                for line in wrap_words(paragraph, width.saturating_sub(INDENT)) {
+                    lines.push(format!("{}{}", indent, renderer.paint(&line, &plain)));
+                }
+            }
+            sections.push(lines.join("\n"));
+        }
+
+        if !self.options.is_empty() {
+            let mut lines = vec![renderer.paint("OPTIONS", &bold)];
+            let tag_width = self
+                .options
+                .iter()
+                .map(|(tag, _)| placeholder_width(tag))
+                .filter(|&w| w <= MAX_TAG)
+                .max()
+                .unwrap_or(0);
+            let column = INDENT + tag_width + 2;
+            let hang = " ".repeat(column);
+
+            for (tag, description) in &self.options {
+                let tag_cols = placeholder_width(tag);
+                let mut line = format!("{}{}", indent, paint_placeholders(renderer, tag, &bold));
+                let body = wrap_words(description, width.saturating_sub(column).max(1));
+                let mut body = body.iter();
+                if tag_cols <= tag_width {
+                    if let Some(first) = body.next() {
+                        line.push_str(&" ".repeat(column - INDENT - tag_cols));
+                        line.push_str(&renderer.paint(first, &plain));
+                    }
+                }
+                lines.push(line);
+                for rest in body {
+                    lines.push(format!("{}{}", hang, renderer.paint(rest, &plain)));
+                }
+            }
+            sections.push(lines.join("\n"));
+        }
+
+        sections.join("\n\n")
+    }
+
+    /// ## Exports the page as roff source for the `man` command.
+    pub fn to_roff(&self) -> String {
+        let mut out = format!(
+            ".TH {} {}\n.SH NAME\n{} \\- {}\n",
+            roff_escape(&self.name.to_uppercase()),
+            self.section,
+            roff_escape(&self.name),
+            roff_escape(&self.summary)
+        );
+
+        if !self.synopsis.is_empty() {
+            out.push_str(".SH SYNOPSIS\n");
+            for usage in &self.synopsis {
+                let (command, args) = usage.split_once(' ').unwrap_or((usage, ""));
+                out.push_str(&format!(
+                    "\\fB{}\\fR {}\n.br\n",
+                    roff_escape(command),
+                    roff_placeholders(args, "\\fR")
+                ));
+            }
+        }
+
+        if !self.description.is_empty() {
+            out.push_str(".SH DESCRIPTION\n");
+            for (i, paragraph) in self.description.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(".PP\n");
+                }
+                out.push_str(&roff_line(paragraph));
+                out.push('\n');
+            }
+        }
+
+        if !self.options.is_empty() {
+            out.push_str(".SH OPTIONS\n");
+            for (tag, description) in &self.options {
+                out.push_str(&format!(
+                    ".TP\n\\fB{}\\fR\n{}\n",
+                    roff_placeholders(tag, "\\fB"),
+                    roff_line(description)
+                ));
+            }
+        }
+
+        out
+    }
+}
+
+impl fmt::Display for ManPage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render())
+    }
+}
+
+/// ## Splits text into `(segment, is_placeholder)` pairs around `<...>` placeholders.
+fn split_placeholders(text: &str) -> Vec<(&str, bool)> {
+    let mut parts = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('<') {
+        let Some(len) = rest[start..].find('>') else {
+            break;
+        };
+        if start > 0 {
+            parts.push((&rest[..start], false));
+        }
+        parts.push((&rest[start + 1..start + len], true));
+        rest = &rest[start + len + 1..];
+    }
+
+    if !rest.is_empty() {
+        parts.push((rest, false));
+    }
+    parts
+}
+
+/// ## Renders text with its placeholders underlined and the rest styled by `base`.
+fn paint_placeholders(renderer: &dyn Renderer, text: &str, base: &StyleSpec) -> String {
+    let underline = StyleSpec::new().font(Style::Underline);
+    split_placeholders(text)
+        .into_iter()
+        .map(|(part, placeholder)| match placeholder {
+            true => renderer.paint(part, &underline),
+            false => renderer.paint(part, base),
+        })
+        .collect()
+}
+
+/// ## Returns the visible width of text once its placeholder brackets are removed.
+fn placeholder_width(text: &str) -> usize {
+    split_placeholders(text)
+        .into_iter()
+        .map(|(part, _)| text_width(part))
+        .sum()
+}
+
+/// ## Converts placeholders to roff italics, returning to `font` after each.
+fn roff_placeholders(text: &str, font: &str) -> String {
+    split_placeholders(text)
+        .into_iter()
+        .map(|(part, placeholder)| match placeholder {
+            true => format!("\\fI{}{}", roff_escape(part), font),
+            false => roff_escape(part),
+        })
+        .collect()
+}
+
+/// ## Escapes a line of text, guarding against it being read as a request.
+fn roff_line(text: &str) -> String {
+    let escaped = roff_escape(text);
+    match escaped.starts_with(['.', '\'']) {
+        true => format!("\\&{}", escaped),
+        false => escaped,
+    }
+}
+
+/// ## Escapes backslashes and hyphens for roff.
+fn roff_escape(text: &str) -> String {
+    text.replace('\\', "\\e").replace('-', "\\-")
+}
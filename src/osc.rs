@@ -0,0 +1,109 @@
+//! Operating System Commands: window titles and hyperlinks.
+//!
+//! The functions returning an [`Osc`] build the sequences; `set_title` and
+//! `reset_title` also print them, when standard output is a terminal.
+
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::ansi::{Csi, Osc};
+use crate::Stream;
+
+/// Whether the title from before the first `set_title` was pushed on the title stack.
+static SAVED: AtomicBool = AtomicBool::new(false);
+
+/// ## Builds the sequence that sets the window and tab title.
+///
+/// Control characters are removed, since they would end the sequence early.
+///
+/// ### Arguments
+///
+/// * `text` - The new title
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::osc;
+///
+/// assert_eq!(osc::title("build\x07").to_string(), "\x1b]2;build\x1b\\");
+/// ```
+pub fn title(text: &str) -> Osc {
+    Osc::new(2).arg(text.chars().filter(|c| !c.is_control()).collect::<String>())
+}
+
+/// ## Sets the window title, if standard output is a terminal.
+///
+/// The first call saves the current title, to be restored by [`reset_title`].
+///
+/// ### Arguments
+///
+/// * `text` - The new title
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::osc;
+///
+/// osc::set_title("my tool: building");
+/// osc::reset_title();
+/// ```
+pub fn set_title(text: &str) {
+    if !Stream::Stdout.is_terminal() {
+        return;
+    }
+
+    let mut out = String::new();
+    if !SAVED.swap(true, Ordering::Relaxed) {
+        out.push_str(&Csi::new('t').param(22).param(2).to_string());
+    }
+    out.push_str(&title(text).to_string());
+    print_flushed(&out);
+}
+
+/// ## Restores the title saved by [`set_title`], if standard output is a terminal.
+///
+/// Terminals without a title stack fall back to their default title.
+pub fn reset_title() {
+    if !Stream::Stdout.is_terminal() {
+        return;
+    }
+
+    let mut out = title("").to_string();
+    if SAVED.swap(false, Ordering::Relaxed) {
+        out.push_str(&Csi::new('t').param(23).param(2).to_string());
+    }
+    print_flushed(&out);
+}
+
+/// ## Builds the sequence that starts an OSC 8 hyperlink.
+///
+/// ### Arguments
+///
+/// * `url` - The target of the link
+/// * `id` - An id grouping several segments into one link, if any
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::osc;
+///
+/// let link = format!("{}docs{}", osc::hyperlink("https://example.com", None), osc::hyperlink_end());
+///
+/// assert_eq!(link, "\x1b]8;;https://example.com\x1b\\docs\x1b]8;;\x1b\\");
+/// ```
+pub fn hyperlink(url: &str, id: Option<&str>) -> Osc {
+    let params = id.map_or(String::new(), |id| format!("id={}", id));
+    Osc::new(8).arg(params).arg(url)
+}
+
+/// ## Builds the sequence that ends an OSC 8 hyperlink.
+pub fn hyperlink_end() -> Osc {
+    Osc::new(8).arg("").arg("")
+}
+
+/// ## Prints a sequence to standard output and flushes it, ignoring errors.
+fn print_flushed(sequence: &str) {
+    let mut stdout = std::io::stdout().lock();
+    let _ = stdout.write_all(sequence.as_bytes());
+    let _ = stdout.flush();
+}
@@ -0,0 +1,253 @@
+//! Boxed panels around styled content, for summaries and error boxes.
+
+use std::fmt;
+
+use crate::symbols::unicode_supported;
+use crate::{text_width, AnsiRenderer, Renderer, Style, StyleSpec, StyledString, CLW};
+
+/// The characters a border is drawn with.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BorderStyle {
+    /// `┌─┐`
+    #[default]
+    Single,
+    /// `╭─╮`
+    Rounded,
+    /// `╔═╗`
+    Double,
+    /// `┏━┓`
+    Heavy,
+    /// `+-+`, for terminals without Unicode.
+    Ascii,
+}
+
+impl BorderStyle {
+    /// ## Returns the corners, clockwise from the top left, then the horizontal and vertical lines.
+    pub(crate) fn chars(self) -> ([&'static str; 4], &'static str, &'static str) {
+        match self {
+            BorderStyle::Single => (["┌", "┐", "┘", "└"], "─", "│"),
+            BorderStyle::Rounded => (["╭", "╮", "╯", "╰"], "─", "│"),
+            BorderStyle::Double => (["╔", "╗", "╝", "╚"], "═", "║"),
+            BorderStyle::Heavy => (["┏", "┓", "┛", "┗"], "━", "┃"),
+            BorderStyle::Ascii => (["+", "+", "+", "+"], "-", "|"),
+        }
+    }
+
+    /// ## Returns the joints: down, up, right and left tees, then the cross.
+    pub(crate) fn joints(self) -> [&'static str; 5] {
+        match self {
+            BorderStyle::Single | BorderStyle::Rounded => ["┬", "┴", "├", "┤", "┼"],
+            BorderStyle::Double => ["╦", "╩", "╠", "╣", "╬"],
+            BorderStyle::Heavy => ["┳", "┻", "┣", "┫", "╋"],
+            BorderStyle::Ascii => ["+", "+", "+", "+", "+"],
+        }
+    }
+
+    /// ## Returns the border style drawn when none is set, depending on Unicode support.
+    pub(crate) fn detect() -> Self {
+        match unicode_supported() {
+            true => BorderStyle::Single,
+            false => BorderStyle::Ascii,
+        }
+    }
+}
+
+/// Styled content drawn inside a border, with an optional title.
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{clw, panel::{BorderStyle, Panel}, PlainRenderer};
+///
+/// let summary = Panel::new(clw("3 passed\n1 failed"))
+///     .title("Tests")
+///     .border(BorderStyle::Ascii)
+///     .render_with(&PlainRenderer);
+///
+/// assert_eq!(
+///     summary,
+///     "+- Tests --+\n\
+///      | 3 passed |\n\
+///      | 1 failed |\n\
+///      +----------+"
+/// );
+/// ```
+pub struct Panel {
+    content: StyledString,
+    title: Option<String>,
+    border: Option<BorderStyle>,
+    padding: (usize, usize),
+    spec: StyleSpec,
+}
+
+impl Panel {
+    /// ## Creates a panel around the content, which may span several lines.
+    ///
+    /// ### Arguments
+    ///
+    /// * `content` - The text inside the border, `CLW` values or strings
+    pub fn new<S: Into<StyledString>>(content: S) -> Self {
+        Panel {
+            content: content.into(),
+            title: None,
+            border: None,
+            padding: (1, 0),
+            spec: StyleSpec::new(),
+        }
+    }
+
+    /// ## Sets a title, shown in bold in the top border.
+    pub fn title<S: Into<String>>(mut self, title: S) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// ## Sets the characters of the border.
+    ///
+    /// Defaults to `Single`, or `Ascii` when Unicode isn't supported.
+    pub fn border(mut self, border: BorderStyle) -> Self {
+        self.border = Some(border);
+        self
+    }
+
+    /// ## Sets the spaces between the border and the content.
+    ///
+    /// ### Arguments
+    ///
+    /// * `horizontal` - The columns on the left and right, 1 by default
+    /// * `vertical` - The empty lines above and below, 0 by default
+    pub fn padding(mut self, horizontal: usize, vertical: usize) -> Self {
+        self.padding = (horizontal, vertical);
+        self
+    }
+
+    /// ## Sets the style of the panel.
+    ///
+    /// Its text color is used for the border and its background fills the
+    /// whole panel, under content that has no background of its own.
+    ///
+    /// ### Arguments
+    ///
+    /// * `spec` - The colors of the panel
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{clw, panel::{BorderStyle, Panel}, Color, StyleSpec, TestRenderer};
+    ///
+    /// let error = Panel::new(clw("disk full"))
+    ///     .border(BorderStyle::Ascii)
+    ///     .padding(0, 0)
+    ///     .style(StyleSpec::new().bg(Color::Red))
+    ///     .render_with(&TestRenderer);
+    ///
+    /// assert_eq!(error.lines().nth(1), Some("[bg=Red]|[/][bg=Red]disk full[/][bg=Red]|[/]"));
+    /// ```
+    pub fn style(mut self, spec: StyleSpec) -> Self {
+        self.spec = spec;
+        self
+    }
+
+    /// ## Renders the panel with ANSI escape sequences.
+    pub fn render(&self) -> String {
+        self.render_with(&AnsiRenderer)
+    }
+
+    /// ## Renders the panel through the given renderer.
+    ///
+    /// ### Arguments
+    ///
+    /// * `renderer` - The backend used to style the panel
+    pub fn render_with(&self, renderer: &dyn Renderer) -> String {
+        let ([top_left, top_right, bottom_right, bottom_left], horizontal, vertical) =
+            self.border.unwrap_or_else(BorderStyle::detect).chars();
+        let (pad_x, pad_y) = self.padding;
+
+        let lines = self.content.lines();
+        let content_width = lines.iter().map(|l| text_width(&l.plain())).max();
+        let title_width = self.title.as_deref().map_or(0, |t| text_width(t) + 3);
+        let inner = content_width.unwrap_or(0).max(title_width) + pad_x * 2;
+
+        let paint = |text: &str| renderer.paint(text, &self.spec);
+        let mut top = paint(top_left);
+        match &self.title {
+            Some(title) => {
+                let bold = self.spec.clone().font(Style::Bold);
+                let rest = inner - title_width;
+                top.push_str(&paint(&format!("{} ", horizontal)));
+                top.push_str(&renderer.paint(title, &bold));
+                top.push_str(&paint(&format!(
+                    " {}{}",
+                    horizontal.repeat(rest),
+                    top_right
+                )));
+            }
+            None => top.push_str(&paint(&format!(
+                "{}{}",
+                horizontal.repeat(inner),
+                top_right
+            ))),
+        }
+
+        let background = StyleSpec {
+            bg: self.spec.bg,
+            ..StyleSpec::new()
+        };
+        let blank = StyledString::new();
+        let rows = std::iter::repeat_n(&blank, pad_y)
+            .chain(&lines)
+            .chain(std::iter::repeat_n(&blank, pad_y));
+        let mut out = vec![top];
+        for line in rows {
+            let fill = inner - pad_x - text_width(&line.plain());
+            let mut row = paint(&format!("{}{:2$}", vertical, "", pad_x));
+            for segment in line.segments() {
+                row.push_str(&paint_over(renderer, segment, &background));
+            }
+            row.push_str(&paint(&format!("{:1$}{2}", "", fill, vertical)));
+            out.push(row);
+        }
+        out.push(paint(&format!(
+            "{}{}{}",
+            bottom_left,
+            horizontal.repeat(inner),
+            bottom_right
+        )));
+        out.join("\n")
+    }
+}
+
+/// ## Paints a segment over a base style, which fills in what the segment doesn't set.
+///
+/// The colors of the segment win, and the styles of both are combined.
+pub(crate) fn paint_over(renderer: &dyn Renderer, segment: &CLW, base: &StyleSpec) -> String {
+    renderer.paint(&segment.value, &segment.spec.clone().over(base))
+}
+
+impl fmt::Display for Panel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render())
+    }
+}
+
+impl CLW<'_> {
+    /// ## Puts the styled text in a [`Panel`], to add a border around it.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{clw, panel::BorderStyle};
+    ///
+    /// println!("{}", clw("Deployed to production").text_green().boxed().border(BorderStyle::Rounded));
+    /// ```
+    pub fn boxed(self) -> Panel {
+        Panel::new(self)
+    }
+}
+
+impl StyledString {
+    /// ## Puts the segments in a [`Panel`], to add a border around them.
+    pub fn boxed(self) -> Panel {
+        Panel::new(self)
+    }
+}
@@ -0,0 +1,125 @@
+//! Powerline-style segments for prompt and status-bar generators.
+
+use std::fmt;
+
+use crate::{AnsiRenderer, Color, Renderer, StyleSpec};
+
+/// The separator glyph of powerline fonts.
+const GLYPH: &str = "\u{e0b0}";
+
+/// The separator used without a powerline font.
+const ASCII: &str = ">";
+
+/// A colored segment of the line.
+struct Segment {
+    text: String,
+    fg: Color,
+    bg: Color,
+}
+
+/// Renders segments on colored backgrounds, joined by arrow separators.
+///
+/// Each separator takes the background of the segment before it as its color,
+/// so the segments seem to point into each other.
+#[derive(Default)]
+pub struct Powerline {
+    segments: Vec<Segment>,
+    ascii: bool,
+}
+
+impl Powerline {
+    /// ## Creates a line without segments.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// ## Adds a segment at the end of the line.
+    ///
+    /// ### Arguments
+    ///
+    /// * `text` - The text of the segment, padded with a space on each side
+    /// * `fg` - The color of the text
+    /// * `bg` - The background color of the segment
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{powerline::Powerline, Color};
+    ///
+    /// let line = Powerline::new()
+    ///     .segment("user", Color::Black, Color::Green)
+    ///     .segment("~/src", Color::White, Color::Blue);
+    /// ```
+    pub fn segment<S: Into<String>>(mut self, text: S, fg: Color, bg: Color) -> Self {
+        self.segments.push(Segment {
+            text: text.into(),
+            fg,
+            bg,
+        });
+        self
+    }
+
+    /// ## Uses a plain `>` as the separator, for terminals without a powerline font.
+    ///
+    /// ### Arguments
+    ///
+    /// * `ascii` - Whether to use the ASCII separator
+    pub fn ascii(mut self, ascii: bool) -> Self {
+        self.ascii = ascii;
+        self
+    }
+
+    /// ## Renders the line with ANSI escape sequences.
+    pub fn render(&self) -> String {
+        self.render_with(&AnsiRenderer)
+    }
+
+    /// ## Renders the line through the given renderer.
+    ///
+    /// ### Arguments
+    ///
+    /// * `renderer` - The backend used to style the segments, e.g. a
+    ///   [`PromptRenderer`](crate::PromptRenderer) for a shell prompt
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{powerline::Powerline, Color, TestRenderer};
+    ///
+    /// let line = Powerline::new()
+    ///     .segment("user", Color::Black, Color::Green)
+    ///     .segment("~/src", Color::White, Color::Blue)
+    ///     .ascii(true)
+    ///     .render_with(&TestRenderer);
+    ///
+    /// assert_eq!(
+    ///     line,
+    ///     "[fg=Black,bg=Green] user [/][fg=Green,bg=Blue]>[/][fg=White,bg=Blue] ~/src [/][fg=Blue]>[/]"
+    /// );
+    /// ```
+    pub fn render_with(&self, renderer: &dyn Renderer) -> String {
+        let separator = match self.ascii {
+            true => ASCII,
+            false => GLYPH,
+        };
+
+        let mut out = String::new();
+        for (i, segment) in self.segments.iter().enumerate() {
+            let spec = StyleSpec::new().text(segment.fg).bg(segment.bg);
+            out.push_str(&renderer.paint(&format!(" {} ", segment.text), &spec));
+
+            let mut arrow = StyleSpec::new().text(segment.bg);
+            if let Some(next) = self.segments.get(i + 1) {
+                arrow = arrow.bg(next.bg);
+            }
+            out.push_str(&renderer.paint(separator, &arrow));
+        }
+        out
+    }
+}
+
+impl fmt::Display for Powerline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render())
+    }
+}
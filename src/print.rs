@@ -0,0 +1,85 @@
+//! `print!`-style macros that style the formatted text.
+//!
+//! Each macro takes the text color, optionally a [`Style`](crate::Style), then
+//! the format string and its arguments. Like [`CLW::println`](crate::CLW::println),
+//! escape codes are left out when the stream shouldn't be colorized.
+
+/// ## Prints styled, formatted text to the standard output.
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{cprint, Color, Style};
+///
+/// cprint!(Color::Green, "{} passed, ", 12);
+/// cprint!(Color::Red, Style::Bold, "{} failed\n", 1);
+/// ```
+#[macro_export]
+macro_rules! cprint {
+    ($color:expr, $fmt:literal $($arg:tt)*) => {
+        $crate::clw(::std::format!($fmt $($arg)*)).text($color).print()
+    };
+    ($color:expr, $style:expr, $fmt:literal $($arg:tt)*) => {
+        $crate::clw(::std::format!($fmt $($arg)*)).text($color).font($style).print()
+    };
+}
+
+/// ## Prints styled, formatted text to the standard output with a newline.
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{cprintln, Color, Style};
+///
+/// let err = "no such file";
+/// cprintln!(Color::Red, Style::Bold, "failed: {}", err);
+/// cprintln!(Color::Green, "done in {}s", 3);
+/// ```
+#[macro_export]
+macro_rules! cprintln {
+    ($color:expr, $fmt:literal $($arg:tt)*) => {
+        $crate::clw(::std::format!($fmt $($arg)*)).text($color).println()
+    };
+    ($color:expr, $style:expr, $fmt:literal $($arg:tt)*) => {
+        $crate::clw(::std::format!($fmt $($arg)*)).text($color).font($style).println()
+    };
+}
+
+/// ## Prints styled, formatted text to the standard error.
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{ceprint, Color};
+///
+/// ceprint!(Color::Yellow, "warning: ");
+/// ```
+#[macro_export]
+macro_rules! ceprint {
+    ($color:expr, $fmt:literal $($arg:tt)*) => {
+        $crate::clw(::std::format!($fmt $($arg)*)).text($color).eprint()
+    };
+    ($color:expr, $style:expr, $fmt:literal $($arg:tt)*) => {
+        $crate::clw(::std::format!($fmt $($arg)*)).text($color).font($style).eprint()
+    };
+}
+
+/// ## Prints styled, formatted text to the standard error with a newline.
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{ceprintln, Color, Style};
+///
+/// let path = "Cargo.toml";
+/// ceprintln!(Color::Red, Style::Bold, "error: can't read {}", path);
+/// ```
+#[macro_export]
+macro_rules! ceprintln {
+    ($color:expr, $fmt:literal $($arg:tt)*) => {
+        $crate::clw(::std::format!($fmt $($arg)*)).text($color).eprintln()
+    };
+    ($color:expr, $style:expr, $fmt:literal $($arg:tt)*) => {
+        $crate::clw(::std::format!($fmt $($arg)*)).text($color).font($style).eprintln()
+    };
+}
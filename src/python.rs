@@ -0,0 +1,344 @@
+//! Python bindings, enabled by the `python` feature.
+//!
+//! The module mirrors the Rust builder API so Python tools produce
+//! byte-identical output:
+//!
+//! ```python
+//! from clwind import clw, set_theme, Color, Style, StyleSpec, Theme
+//!
+//! print(clw("Hello").text(Color.RED).bg(Color.hex(0xf2f2f2)).font(Style.BOLD))
+//!
+//! set_theme(Theme().style("path", StyleSpec().text(Color.BLUE)))
+//! print(clw("src/lib.rs").style("path"))
+//! ```
+
+use pyo3::prelude::*;
+
+use crate::theme::{self, Theme};
+use crate::{Color, Style, StyleSpec, CLW};
+
+/// A color for terminal output.
+#[pyclass(name = "Color", module = "clwind", frozen, from_py_object)]
+#[derive(Clone, Copy)]
+struct PyColor(Color);
+
+#[pymethods]
+impl PyColor {
+    #[classattr]
+    #[pyo3(name = "BLACK")]
+    fn black() -> Self {
+        PyColor(Color::Black)
+    }
+
+    #[classattr]
+    #[pyo3(name = "RED")]
+    fn red() -> Self {
+        PyColor(Color::Red)
+    }
+
+    #[classattr]
+    #[pyo3(name = "GREEN")]
+    fn green() -> Self {
+        PyColor(Color::Green)
+    }
+
+    #[classattr]
+    #[pyo3(name = "YELLOW")]
+    fn yellow() -> Self {
+        PyColor(Color::Yellow)
+    }
+
+    #[classattr]
+    #[pyo3(name = "BLUE")]
+    fn blue() -> Self {
+        PyColor(Color::Blue)
+    }
+
+    #[classattr]
+    #[pyo3(name = "MAGENTA")]
+    fn magenta() -> Self {
+        PyColor(Color::Magenta)
+    }
+
+    #[classattr]
+    #[pyo3(name = "CYAN")]
+    fn cyan() -> Self {
+        PyColor(Color::Cyan)
+    }
+
+    #[classattr]
+    #[pyo3(name = "WHITE")]
+    fn white() -> Self {
+        PyColor(Color::White)
+    }
+
+    #[classattr]
+    #[pyo3(name = "BRIGHT_BLACK")]
+    fn bright_black() -> Self {
+        PyColor(Color::BrightBlack)
+    }
+
+    #[classattr]
+    #[pyo3(name = "BRIGHT_RED")]
+    fn bright_red() -> Self {
+        PyColor(Color::BrightRed)
+    }
+
+    #[classattr]
+    #[pyo3(name = "BRIGHT_GREEN")]
+    fn bright_green() -> Self {
+        PyColor(Color::BrightGreen)
+    }
+
+    #[classattr]
+    #[pyo3(name = "BRIGHT_YELLOW")]
+    fn bright_yellow() -> Self {
+        PyColor(Color::BrightYellow)
+    }
+
+    #[classattr]
+    #[pyo3(name = "BRIGHT_BLUE")]
+    fn bright_blue() -> Self {
+        PyColor(Color::BrightBlue)
+    }
+
+    #[classattr]
+    #[pyo3(name = "BRIGHT_MAGENTA")]
+    fn bright_magenta() -> Self {
+        PyColor(Color::BrightMagenta)
+    }
+
+    #[classattr]
+    #[pyo3(name = "BRIGHT_CYAN")]
+    fn bright_cyan() -> Self {
+        PyColor(Color::BrightCyan)
+    }
+
+    #[classattr]
+    #[pyo3(name = "BRIGHT_WHITE")]
+    fn bright_white() -> Self {
+        PyColor(Color::BrightWhite)
+    }
+
+    #[classattr]
+    #[pyo3(name = "DEFAULT")]
+    fn default() -> Self {
+        PyColor(Color::Default)
+    }
+
+    /// RGB color with red, green, and blue components.
+    #[staticmethod]
+    fn rgb(r: u8, g: u8, b: u8) -> Self {
+        PyColor(Color::Rgb(r, g, b))
+    }
+
+    /// 256-color palette index.
+    #[staticmethod]
+    fn color256(index: u8) -> Self {
+        PyColor(Color::Color256(index))
+    }
+
+    /// Hexadecimal color code.
+    #[staticmethod]
+    fn hex(code: u32) -> Self {
+        PyColor(Color::Hex(code))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Color.{:?}", self.0)
+    }
+}
+
+/// A text style for terminal output.
+#[pyclass(name = "Style", module = "clwind", eq, eq_int, frozen, from_py_object)]
+#[derive(Clone, Copy, PartialEq)]
+enum PyStyle {
+    #[pyo3(name = "BOLD")]
+    Bold,
+    #[pyo3(name = "DIM")]
+    Dim,
+    #[pyo3(name = "ITALIC")]
+    Italic,
+    #[pyo3(name = "UNDERLINE")]
+    Underline,
+    #[pyo3(name = "BLINK")]
+    Blink,
+    #[pyo3(name = "REVERSE")]
+    Reverse,
+    #[pyo3(name = "HIDDEN")]
+    Hidden,
+    #[pyo3(name = "STRIKETHROUGH")]
+    Strikethrough,
+    #[pyo3(name = "DOUBLE_UNDERLINE")]
+    DoubleUnderline,
+    #[pyo3(name = "CURLY_UNDERLINE")]
+    CurlyUnderline,
+    #[pyo3(name = "DOTTED_UNDERLINE")]
+    DottedUnderline,
+    #[pyo3(name = "DASHED_UNDERLINE")]
+    DashedUnderline,
+    #[pyo3(name = "OVERLINE")]
+    Overline,
+}
+
+impl From<PyStyle> for Style {
+    fn from(style: PyStyle) -> Self {
+        match style {
+            PyStyle::Bold => Style::Bold,
+            PyStyle::Dim => Style::Dim,
+            PyStyle::Italic => Style::Italic,
+            PyStyle::Underline => Style::Underline,
+            PyStyle::Blink => Style::Blink,
+            PyStyle::Reverse => Style::Reverse,
+            PyStyle::Hidden => Style::Hidden,
+            PyStyle::Strikethrough => Style::Strikethrough,
+            PyStyle::DoubleUnderline => Style::DoubleUnderline,
+            PyStyle::CurlyUnderline => Style::CurlyUnderline,
+            PyStyle::DottedUnderline => Style::DottedUnderline,
+            PyStyle::DashedUnderline => Style::DashedUnderline,
+            PyStyle::Overline => Style::Overline,
+        }
+    }
+}
+
+/// A reusable set of colors and styles; every builder returns a new instance.
+#[pyclass(name = "StyleSpec", module = "clwind", eq, frozen, from_py_object)]
+#[derive(Clone, PartialEq)]
+struct PyStyleSpec(StyleSpec);
+
+#[pymethods]
+impl PyStyleSpec {
+    #[new]
+    fn new() -> Self {
+        PyStyleSpec(StyleSpec::new())
+    }
+
+    /// Sets the text color.
+    fn text(&self, color: PyColor) -> Self {
+        PyStyleSpec(self.0.clone().text(color.0))
+    }
+
+    /// Sets the background color.
+    fn bg(&self, color: PyColor) -> Self {
+        PyStyleSpec(self.0.clone().bg(color.0))
+    }
+
+    /// Adds a style.
+    fn font(&self, style: PyStyle) -> Self {
+        PyStyleSpec(self.0.clone().font(style.into()))
+    }
+
+    /// Styles the given text with this spec.
+    fn apply(&self, value: &str) -> PyClw {
+        PyClw(self.0.apply(value.to_string()))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{:?}", self.0)
+    }
+}
+
+/// Named styles used by `CLW.style`; every builder returns a new instance.
+#[pyclass(name = "Theme", module = "clwind", frozen, from_py_object)]
+#[derive(Clone)]
+struct PyTheme(Theme);
+
+#[pymethods]
+impl PyTheme {
+    /// Creates the default theme.
+    #[new]
+    fn new() -> Self {
+        PyTheme(Theme::new())
+    }
+
+    /// Creates a theme without any styles.
+    #[staticmethod]
+    fn empty() -> Self {
+        PyTheme(Theme::empty())
+    }
+
+    /// Sets the style for a name, replacing the previous one.
+    fn style(&self, name: &str, spec: PyStyleSpec) -> Self {
+        PyTheme(self.0.clone().style(name, spec.0))
+    }
+
+    /// Returns the style for a name, or `None` if the theme has none.
+    fn get(&self, name: &str) -> Option<PyStyleSpec> {
+        self.0.get(name).cloned().map(PyStyleSpec)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{:?}", self.0)
+    }
+}
+
+/// A colored and styled text; every builder returns a new instance.
+#[pyclass(name = "CLW", module = "clwind", frozen)]
+struct PyClw(CLW<'static>);
+
+#[pymethods]
+impl PyClw {
+    /// Sets the text color.
+    fn text(&self, color: PyColor) -> Self {
+        PyClw(self.0.clone().text(color.0))
+    }
+
+    /// Sets the background color.
+    fn bg(&self, color: PyColor) -> Self {
+        PyClw(self.0.clone().bg(color.0))
+    }
+
+    /// Adds a style to the text.
+    fn font(&self, style: PyStyle) -> Self {
+        PyClw(self.0.clone().font(style.into()))
+    }
+
+    /// Applies the theme's style for a name, if it has one.
+    fn style(&self, name: &str) -> Self {
+        PyClw(self.0.clone().style(name))
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("clw({:?})", self.0.value)
+    }
+}
+
+/// Creates a new `CLW` instance with the given text.
+#[pyfunction]
+#[pyo3(name = "clw")]
+fn py_clw(value: &str) -> PyClw {
+    PyClw(crate::clw(value.to_string()))
+}
+
+/// Sets the theme used by `CLW.style`, for the whole process.
+#[pyfunction]
+#[pyo3(name = "set_theme")]
+fn py_set_theme(theme: PyTheme) {
+    theme::set_theme(theme.0);
+}
+
+/// Returns the theme used by `CLW.style`.
+#[pyfunction]
+#[pyo3(name = "get_theme")]
+fn py_get_theme() -> PyTheme {
+    PyTheme(theme::current())
+}
+
+/// The `clwind` Python module.
+#[pymodule]
+#[pyo3(name = "clwind")]
+fn clwind_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyColor>()?;
+    m.add_class::<PyStyle>()?;
+    m.add_class::<PyStyleSpec>()?;
+    m.add_class::<PyTheme>()?;
+    m.add_class::<PyClw>()?;
+    m.add_function(wrap_pyfunction!(py_clw, m)?)?;
+    m.add_function(wrap_pyfunction!(py_set_theme, m)?)?;
+    m.add_function(wrap_pyfunction!(py_get_theme, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,94 @@
+//! Rainbow text, coloring characters along the hue wheel.
+
+use std::fmt;
+
+use crate::{Color, StyleSpec, StyledString, CLW};
+
+/// Text colored along the hue wheel, character by character, like `lolcat`.
+///
+/// Created with [`CLW::rainbow`]. It can be printed directly, or converted into
+/// a [`StyledString`] to combine it with other text.
+#[derive(Clone)]
+pub struct Rainbow<'a> {
+    text: CLW<'a>,
+    frequency: f32,
+    phase: f32,
+    line_offset: f32,
+}
+
+impl<'a> CLW<'a> {
+    /// ## Colors the text along the hue wheel, one character at a time.
+    ///
+    /// The colors are converted to the nearest ones the terminal can show.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{clw, RenderMode, StyledString};
+    ///
+    /// let rgb = StyledString::from(clw("RGB").rainbow().frequency(120.0));
+    ///
+    /// assert_eq!(
+    ///     rgb.to_string_for(RenderMode::Test),
+    ///     "[fg=Rgb(255, 51, 51)]R[/][fg=Rgb(51, 255, 51)]G[/][fg=Rgb(51, 51, 255)]B[/]"
+    /// );
+    ///
+    /// println!("{}", clw("Hello\nWorld").font_bold().rainbow().line_offset(2.0));
+    /// ```
+    pub fn rainbow(self) -> Rainbow<'a> {
+        Rainbow {
+            text: self,
+            frequency: 10.0,
+            phase: 0.0,
+            line_offset: 0.0,
+        }
+    }
+}
+
+impl Rainbow<'_> {
+    /// ## Sets how fast the hue changes, in degrees per character, 10 by default.
+    pub fn frequency(mut self, frequency: f32) -> Self {
+        self.frequency = frequency;
+        self
+    }
+
+    /// ## Sets the hue of the first character, in degrees, 0 (red) by default.
+    pub fn phase(mut self, phase: f32) -> Self {
+        self.phase = phase;
+        self
+    }
+
+    /// ## Shifts each line by a number of characters, for a diagonal effect.
+    ///
+    /// ### Arguments
+    ///
+    /// * `offset` - The characters each line is shifted by, 0 by default
+    pub fn line_offset(mut self, offset: f32) -> Self {
+        self.line_offset = offset;
+        self
+    }
+}
+
+impl From<Rainbow<'_>> for StyledString {
+    fn from(rainbow: Rainbow<'_>) -> Self {
+        let (mut line, mut column) = (0.0, 0.0);
+        rainbow.text.map_chars(|_, c| {
+            if c == '\n' {
+                line += 1.0;
+                column = 0.0;
+                return StyleSpec::new();
+            }
+
+            let position = column + line * rainbow.line_offset;
+            column += 1.0;
+            let hue = rainbow.phase + rainbow.frequency * position;
+            StyleSpec::new().text(Color::hsl(hue, 1.0, 0.6))
+        })
+    }
+}
+
+impl fmt::Display for Rainbow<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", StyledString::from(self.clone()))
+    }
+}
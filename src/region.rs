@@ -0,0 +1,113 @@
+//! Highlighting a region of interest inside long text.
+
+use std::ops::Range;
+
+use crate::{AnsiRenderer, Renderer, Style, StyleSpec};
+
+/// ## Extracts the lines around a byte range and styles the range itself.
+///
+/// Lines outside of the surrounding context are replaced by a dim `…` marker,
+/// so the output points at "where in this text" without printing all of it.
+/// The range is clamped to the text and to character boundaries.
+///
+/// ### Arguments
+///
+/// * `text` - The full text containing the region
+/// * `range` - The byte range to highlight
+/// * `spec` - The style applied to the highlighted range
+/// * `context_lines` - How many lines to keep before and after the region
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{highlight_region, Color, StyleSpec};
+///
+/// let source = "one\ntwo\nthree\nfour\nfive";
+/// let spec = StyleSpec::new().text(Color::Red);
+///
+/// let snippet = highlight_region(source, 8..13, &spec, 1);
+///
+/// assert_eq!(snippet.lines().count(), 5);
+/// ```
+pub fn highlight_region(
+    text: &str,
+    range: Range<usize>,
+    spec: &StyleSpec,
+    context_lines: usize,
+) -> String {
+    highlight_region_with(&AnsiRenderer, text, range, spec, context_lines)
+}
+
+/// ## Same as [`highlight_region`], rendering through the given renderer.
+///
+/// ### Arguments
+///
+/// * `renderer` - The backend used to style the output
+/// * `text` - The full text containing the region
+/// * `range` - The byte range to highlight
+/// * `spec` - The style applied to the highlighted range
+/// * `context_lines` - How many lines to keep before and after the region
+pub fn highlight_region_with(
+    renderer: &dyn Renderer,
+    text: &str,
+    range: Range<usize>,
+    spec: &StyleSpec,
+    context_lines: usize,
+) -> String {
+    let plain = StyleSpec::new();
+    let ellipsis = renderer.paint("…", &StyleSpec::new().font(Style::Dim));
+    let start = floor_char_boundary(text, range.start);
+    let end = floor_char_boundary(text, range.end).max(start);
+
+    let mut lines = Vec::new();
+    let mut offset = 0;
+    for line in text.split('\n') {
+        lines.push((offset, line));
+        offset += line.len() + 1;
+    }
+
+    let line_of = |pos: usize| lines.iter().rposition(|&(o, _)| o <= pos).unwrap_or(0);
+    let first = line_of(start);
+    let last = if end > start { line_of(end - 1) } else { first };
+    let from = first.saturating_sub(context_lines);
+    let to = (last + context_lines).min(lines.len() - 1);
+
+    let mut out = Vec::with_capacity(to - from + 3);
+    if from > 0 {
+        out.push(ellipsis.clone());
+    }
+
+    for (i, &(offset, line)) in lines.iter().enumerate().take(to + 1).skip(from) {
+        if i < first || i > last {
+            out.push(renderer.paint(line, &plain));
+            continue;
+        }
+
+        let a = start.saturating_sub(offset).min(line.len());
+        let b = end.saturating_sub(offset).min(line.len());
+        match b > a {
+            true => out.push(format!(
+                "{}{}{}",
+                renderer.paint(&line[..a], &plain),
+                renderer.paint(&line[a..b], spec),
+                renderer.paint(&line[b..], &plain)
+            )),
+            false => out.push(renderer.paint(line, &plain)),
+        }
+    }
+
+    if to < lines.len() - 1 {
+        out.push(ellipsis.clone());
+    }
+
+    out.join("\n")
+}
+
+/// ## Rounds a byte index down to the nearest character boundary within `text`.
+fn floor_char_boundary(text: &str, index: usize) -> usize {
+    let mut index = index.min(text.len());
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
@@ -0,0 +1,439 @@
+//! Rendering backends that turn styled text into output.
+//!
+//! Every component renders through a [`Renderer`], so downstream crates can
+//! implement the trait to add their own backend (SVG, PDF, ...) without forking.
+//! Backends should pass specs through [`apply_transform`](crate::apply_transform)
+//! so the global transform hook applies to them too.
+
+use std::fmt;
+
+use crate::completion::Shell;
+use crate::{
+    apply_transform, color_level, colors_enabled, debug_overlay, link, stats, text_width, Color,
+    Sgr, Style, StyleSpec,
+};
+
+/// A backend that writes text styled by a [`StyleSpec`].
+pub trait Renderer {
+    /// ## Writes `text` styled by `spec` into `out`.
+    ///
+    /// ### Arguments
+    ///
+    /// * `out` - The destination to write to
+    /// * `text` - The text to be styled
+    /// * `spec` - The colors and styles to apply
+    fn render(&self, out: &mut dyn fmt::Write, text: &str, spec: &StyleSpec) -> fmt::Result;
+
+    /// ## Writes rendered text as a hyperlink to `url`.
+    ///
+    /// The default keeps only the text, for formats without links.
+    ///
+    /// ### Arguments
+    ///
+    /// * `out` - The destination to write to
+    /// * `url` - The target of the link
+    /// * `id` - The id grouping the parts of one link, see [`CLW::link_id`](crate::CLW::link_id)
+    /// * `body` - The text, already rendered by this renderer
+    fn render_link(
+        &self,
+        out: &mut dyn fmt::Write,
+        url: &str,
+        id: Option<&str>,
+        body: &str,
+    ) -> fmt::Result {
+        let _ = (url, id);
+        out.write_str(body)
+    }
+
+    /// ## Renders `text` styled by `spec` into a new `String`.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{PlainRenderer, Renderer, StyleSpec};
+    ///
+    /// assert_eq!(PlainRenderer.paint("hi", &StyleSpec::new()), "hi");
+    /// ```
+    fn paint(&self, text: &str, spec: &StyleSpec) -> String {
+        let mut out = String::new();
+        self.render(&mut out, text, spec)
+            .expect("writing to a String cannot fail");
+        out
+    }
+}
+
+/// The built-in output formats, for picking a renderer by value.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RenderMode {
+    /// ANSI escape sequences, see [`AnsiRenderer`].
+    #[default]
+    Ansi,
+    /// HTML with inline CSS, see [`HtmlRenderer`].
+    Html,
+    /// Unstyled text, see [`PlainRenderer`].
+    Plain,
+    /// Readable markup for tests, see [`TestRenderer`].
+    Test,
+    /// ANSI escape sequences for a bash `PS1`, see [`PromptRenderer`].
+    BashPrompt,
+    /// ANSI escape sequences for a zsh `PROMPT`, see [`PromptRenderer`].
+    ZshPrompt,
+}
+
+impl RenderMode {
+    /// ## Returns the renderer for this mode.
+    pub fn renderer(self) -> &'static dyn Renderer {
+        match self {
+            RenderMode::Ansi => &AnsiRenderer,
+            RenderMode::Html => &HtmlRenderer,
+            RenderMode::Plain => &PlainRenderer,
+            RenderMode::Test => &TestRenderer,
+            RenderMode::BashPrompt => &PromptRenderer(Shell::Bash),
+            RenderMode::ZshPrompt => &PromptRenderer(Shell::Zsh),
+        }
+    }
+}
+
+/// Renders ANSI escape sequences for terminals, or plain text when
+/// [`colors_enabled`](crate::colors_enabled) is false.
+///
+/// Text that is already styled keeps the outer style after its own resets,
+/// so styled values can be nested.
+///
+/// Like a [`CLW`](crate::CLW) rendered with `Display`, each painted piece is
+/// passed to the [output hook](crate::set_output_hook), counted in the
+/// [`stats`](crate::stats) of output without a stream, and left unstyled once
+/// the [escape budget](crate::set_escape_budget) is spent. Components don't
+/// know the stream they are printed to, so the color choice is resolved as
+/// for [`colors_enabled`](crate::colors_enabled).
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{clw, set_colors_enabled};
+///
+/// set_colors_enabled(true);
+/// let inner = clw("inner").text_red();
+/// let outer = clw(format!("outer {} more", inner)).text_blue();
+///
+/// assert_eq!(
+///     outer.to_string(),
+///     "\x1b[34mouter \x1b[31minner\x1b[0m\x1b[34m more\x1b[0m"
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AnsiRenderer;
+
+impl Renderer for AnsiRenderer {
+    fn render(&self, out: &mut dyn fmt::Write, text: &str, spec: &StyleSpec) -> fmt::Result {
+        let write = |out: &mut dyn fmt::Write, colorize: bool| match colorize {
+            true => write_ansi(out, text, spec, false),
+            false => write_codes(out, text, &StyleSpec::new(), ("", ""), false),
+        };
+        stats::write_counted(out, (text, spec), None, colors_enabled(), write)
+    }
+
+    fn render_link(
+        &self,
+        out: &mut dyn fmt::Write,
+        url: &str,
+        id: Option<&str>,
+        body: &str,
+    ) -> fmt::Result {
+        link::write_link(out, url, id, colors_enabled(), |out| out.write_str(body))
+    }
+}
+
+/// Renders ANSI escape sequences for a shell prompt, like [`AnsiRenderer`].
+///
+/// The escape sequences are wrapped in `\[`/`\]` for bash and `%{`/`%}` for zsh,
+/// so line editing knows they take no space. fish measures prompts itself, so
+/// they are left as they are. The text is left as it is, so prompt escapes such
+/// as `\w` or `%~` in it still expand.
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::completion::Shell;
+/// use clwind::{clw, set_colors_enabled, PromptRenderer};
+///
+/// set_colors_enabled(true);
+/// let cwd = clw("\\w").text_blue().render_with(&PromptRenderer(Shell::Bash));
+///
+/// assert_eq!(cwd, "\\[\x1b[34m\\]\\w\\[\x1b[0m\\]");
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct PromptRenderer(pub Shell);
+
+impl Renderer for PromptRenderer {
+    fn render(&self, out: &mut dyn fmt::Write, text: &str, spec: &StyleSpec) -> fmt::Result {
+        let escape = match self.0 {
+            Shell::Bash => ("\\[", "\\]"),
+            Shell::Zsh => ("%{", "%}"),
+            Shell::Fish => ("", ""),
+        };
+        match colors_enabled() {
+            true => write_codes(out, text, &apply_transform(spec), escape, false),
+            false => write_codes(out, text, &StyleSpec::new(), escape, false),
+        }
+    }
+}
+
+/// ## Writes `text` wrapped in the escape codes of `spec`, regardless of the color choice.
+///
+/// Colors are downgraded to the [`color_level`] of the terminal. With
+/// `per_line`, the style is closed before each line break and opened again
+/// after it, see [`CLW::per_line_codes`](crate::CLW::per_line_codes).
+pub(crate) fn write_ansi(
+    out: &mut dyn fmt::Write,
+    text: &str,
+    spec: &StyleSpec,
+    per_line: bool,
+) -> fmt::Result {
+    write_codes(out, text, &apply_transform(spec), ("", ""), per_line)
+}
+
+/// ## Writes `text` wrapped in an SGR sequence made of the codes of `spec`.
+///
+/// Colors are downgraded to the [`color_level`] of the terminal. Each escape
+/// sequence is put between the `escape` pair, which marks it as taking no
+/// space for shell prompts.
+fn write_codes(
+    out: &mut dyn fmt::Write,
+    text: &str,
+    spec: &StyleSpec,
+    (open, close): (&str, &str),
+    per_line: bool,
+) -> fmt::Result {
+    let codes = spec.sgr(color_level());
+    if debug_overlay() {
+        return match codes.is_empty() {
+            true => write!(out, "⟨⟩{}⟨/{}⟩", text, text_width(text)),
+            false => write!(
+                out,
+                "⟨{0}⟩{3}\x1b[{0}m{4}{1}{3}\x1b[0m{4}⟨/{2}⟩",
+                codes,
+                Reopened(text, &codes, (open, close), per_line),
+                text_width(text),
+                open,
+                close
+            ),
+        };
+    }
+
+    match codes.is_empty() {
+        true => out.write_str(text),
+        false => write!(
+            out,
+            "{2}\x1b[{0}m{3}{1}{2}\x1b[0m{3}",
+            codes,
+            Reopened(text, &codes, (open, close), per_line),
+            open,
+            close
+        ),
+    }
+}
+
+/// Styled text that opens its SGR sequence again after each reset it contains,
+/// so the style of the surrounding text survives nested styled text.
+///
+/// With the last field set, the style is also closed before each line break
+/// and opened again after it.
+struct Reopened<'a>(&'a str, &'a Sgr<'a>, (&'a str, &'a str), bool);
+
+impl fmt::Display for Reopened<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Reopened(mut rest, codes, (open, close), per_line) = *self;
+        let breaks: &[&str] = match per_line {
+            true => &["\x1b[0m", "\x1b[m", "\r\n", "\n"],
+            false => &["\x1b[0m", "\x1b[m"],
+        };
+        while let Some((i, found)) = breaks
+            .iter()
+            .filter_map(|&b| rest.find(b).map(|i| (i, b)))
+            .min()
+        {
+            let end = i + found.len();
+            match found.ends_with('\n') {
+                true => write!(f, "{}{}\x1b[0m{}{}", &rest[..i], open, close, found)?,
+                false => f.write_str(&rest[..end])?,
+            }
+            write!(f, "{}\x1b[{}m{}", open, codes, close)?;
+            rest = &rest[end..];
+        }
+        f.write_str(rest)
+    }
+}
+
+/// Renders the text without any styling.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PlainRenderer;
+
+impl Renderer for PlainRenderer {
+    fn render(&self, out: &mut dyn fmt::Write, text: &str, _spec: &StyleSpec) -> fmt::Result {
+        out.write_str(text)
+    }
+}
+
+/// Renders HTML `<span>` elements with inline CSS, with the text escaped.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct HtmlRenderer;
+
+impl Renderer for HtmlRenderer {
+    fn render(&self, out: &mut dyn fmt::Write, text: &str, spec: &StyleSpec) -> fmt::Result {
+        let css = spec_css(&apply_transform(spec));
+        if css.is_empty() {
+            return write_html_escaped(out, text);
+        }
+
+        write!(out, "<span style=\"{}\">", css)?;
+        write_html_escaped(out, text)?;
+        out.write_str("</span>")
+    }
+
+    fn render_link(
+        &self,
+        out: &mut dyn fmt::Write,
+        url: &str,
+        _id: Option<&str>,
+        body: &str,
+    ) -> fmt::Result {
+        out.write_str("<a href=\"")?;
+        write_html_escaped(out, url)?;
+        write!(out, "\">{}</a>", body)
+    }
+}
+
+/// Renders a readable markup such as `[fg=Red,Bold]text[/]`, for asserting in tests.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TestRenderer;
+
+impl Renderer for TestRenderer {
+    fn render(&self, out: &mut dyn fmt::Write, text: &str, spec: &StyleSpec) -> fmt::Result {
+        let attrs = spec_attrs(&apply_transform(spec));
+        match attrs.len() {
+            0 => out.write_str(text),
+            _ => write!(out, "[{}]{}[/]", attrs, text),
+        }
+    }
+}
+
+/// ## Describes a spec as readable attributes, e.g. `fg=Red,Bold`.
+pub(crate) fn spec_attrs(spec: &StyleSpec) -> String {
+    let mut attrs = Vec::new();
+    if let Some(color) = spec.text_color() {
+        attrs.push(format!("fg={:?}", color));
+    }
+
+    if let Some(color) = spec.bg_color() {
+        attrs.push(format!("bg={:?}", color));
+    }
+
+    if let Some(color) = spec.underline_color() {
+        attrs.push(format!("ul={:?}", color));
+    }
+
+    for style in spec.fonts() {
+        attrs.push(format!("{:?}", style));
+    }
+    attrs.join(",")
+}
+
+/// ## Builds the inline CSS declarations for a spec.
+fn spec_css(spec: &StyleSpec) -> String {
+    let mut fg = spec.text_color();
+    let mut bg = spec.bg_color();
+    if spec.fonts().contains(&Style::Reverse) {
+        (fg, bg) = (bg.or(Some(Color::Black)), fg.or(Some(Color::White)));
+    }
+
+    let mut css = Vec::new();
+    if let Some(color) = fg {
+        css.push(format!("color:{}", css_color(color)));
+    }
+
+    if let Some(color) = bg {
+        css.push(format!("background-color:{}", css_color(color)));
+    }
+
+    let mut lines = Vec::new();
+    let mut line_style = None;
+    for style in spec.fonts() {
+        let line = match style {
+            Style::Bold => {
+                css.push("font-weight:bold".to_string());
+                continue;
+            }
+            Style::Dim => {
+                css.push("opacity:0.5".to_string());
+                continue;
+            }
+            Style::Italic => {
+                css.push("font-style:italic".to_string());
+                continue;
+            }
+            Style::Hidden => {
+                css.push("visibility:hidden".to_string());
+                continue;
+            }
+            Style::Reverse => continue,
+            Style::Underline => "underline",
+            Style::DoubleUnderline => underline(&mut line_style, "double"),
+            Style::CurlyUnderline => underline(&mut line_style, "wavy"),
+            Style::DottedUnderline => underline(&mut line_style, "dotted"),
+            Style::DashedUnderline => underline(&mut line_style, "dashed"),
+            Style::Blink => "blink",
+            Style::Strikethrough => "line-through",
+            Style::Overline => "overline",
+        };
+        if !lines.contains(&line) {
+            lines.push(line);
+        }
+    }
+
+    if !lines.is_empty() {
+        lines.extend(line_style);
+        css.push(format!("text-decoration:{}", lines.join(" ")));
+    }
+
+    if let Some(color) = spec.underline_color() {
+        css.push(format!("text-decoration-color:{}", css_color(color)));
+    }
+
+    css.join(";")
+}
+
+/// ## Sets the CSS `text-decoration-style` of an underline, returning its line.
+///
+/// A single `text-decoration` has one style, so the last underline kind wins.
+fn underline(line_style: &mut Option<&'static str>, style: &'static str) -> &'static str {
+    *line_style = Some(style);
+    "underline"
+}
+
+/// ## Formats a color as a CSS hex color.
+///
+/// `Color::Default` becomes `inherit`, leaving the color to the surrounding markup.
+pub(crate) fn css_color(color: Color) -> String {
+    if color == Color::Default {
+        return "inherit".to_string();
+    }
+
+    let (r, g, b) = color.to_rgb();
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+/// ## Writes `text` with the HTML special characters escaped.
+pub(crate) fn write_html_escaped(out: &mut dyn fmt::Write, text: &str) -> fmt::Result {
+    for c in text.chars() {
+        match c {
+            '<' => out.write_str("&lt;")?,
+            '>' => out.write_str("&gt;")?,
+            '&' => out.write_str("&amp;")?,
+            '"' => out.write_str("&quot;")?,
+            '\'' => out.write_str("&#39;")?,
+            _ => out.write_char(c)?,
+        }
+    }
+    Ok(())
+}
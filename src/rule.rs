@@ -0,0 +1,104 @@
+//! Full-width dividers and section headings, to structure long output.
+
+use crate::symbols::unicode_supported;
+use crate::{terminal_width, text_width, AnsiRenderer, Renderer, Style, StyleSpec};
+
+/// ## Renders a horizontal rule across the terminal width.
+///
+/// ### Arguments
+///
+/// * `fill` - The character the rule is drawn with, e.g. `'─'` or `'='`
+/// * `spec` - The style of the rule
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{hr, StyleSpec, Style};
+///
+/// println!("{}", hr('─', StyleSpec::new().font(Style::Dim)));
+/// ```
+pub fn hr(fill: char, spec: StyleSpec) -> String {
+    hr_with(&AnsiRenderer, fill, &spec, terminal_width())
+}
+
+/// ## Renders a horizontal rule of `width` columns through the given renderer.
+///
+/// Wide characters are repeated as many times as fit.
+///
+/// ### Arguments
+///
+/// * `renderer` - The backend used to style the output
+/// * `fill` - The character the rule is drawn with
+/// * `spec` - The style of the rule
+/// * `width` - The number of columns of the rule
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{rule::hr_with, Color, StyleSpec, TestRenderer};
+///
+/// let rule = hr_with(&TestRenderer, '=', &StyleSpec::new().text(Color::Blue), 8);
+///
+/// assert_eq!(rule, "[fg=Blue]========[/]");
+/// ```
+pub fn hr_with(renderer: &dyn Renderer, fill: char, spec: &StyleSpec, width: usize) -> String {
+    let fill = fill.to_string();
+    let count = width / text_width(&fill).max(1);
+    renderer.paint(&fill.repeat(count), spec)
+}
+
+/// ## Renders a heading in bold, followed by a dim rule up to the terminal width.
+///
+/// ### Arguments
+///
+/// * `title` - The text of the heading
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::section;
+///
+/// println!("{}", section("Dependencies"));
+/// ```
+pub fn section(title: &str) -> String {
+    section_with(&AnsiRenderer, title, terminal_width())
+}
+
+/// ## Renders a section heading of `width` columns through the given renderer.
+///
+/// The rule is drawn with `─`, or `-` when Unicode isn't supported. Titles
+/// wider than `width` are shown whole, without a rule.
+///
+/// ### Arguments
+///
+/// * `renderer` - The backend used to style the output
+/// * `title` - The text of the heading
+/// * `width` - The number of columns of the heading
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{rule::section_with, with_capabilities, Capabilities, TestRenderer};
+///
+/// let ascii = Capabilities::from_env(|_| None, false).unicode(false);
+/// let heading = with_capabilities(ascii, || section_with(&TestRenderer, "Build", 12));
+///
+/// assert_eq!(heading, "[Dim]--[/] [Bold]Build[/] [Dim]---[/]");
+/// ```
+pub fn section_with(renderer: &dyn Renderer, title: &str, width: usize) -> String {
+    let fill = match unicode_supported() {
+        true => '─',
+        false => '-',
+    };
+    let dim = StyleSpec::new().font(Style::Dim);
+    let rest = width.saturating_sub(text_width(title) + 4);
+
+    let mut out = hr_with(renderer, fill, &dim, 2);
+    out.push(' ');
+    out.push_str(&renderer.paint(title, &StyleSpec::new().font(Style::Bold)));
+    if rest > 0 {
+        out.push(' ');
+        out.push_str(&hr_with(renderer, fill, &dim, rest));
+    }
+    out
+}
@@ -0,0 +1,62 @@
+//! Querying the size of the terminal window.
+
+/// ## Returns the size of the terminal as `(columns, rows)`.
+///
+/// The size is read from standard output, with `ioctl` on Unix and the
+/// console API on Windows. Returns `None` when standard output isn't a
+/// terminal, e.g. when it is piped to a file or another program.
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::terminal_size;
+///
+/// let width = terminal_size().map_or(80, |(columns, _)| columns);
+/// assert!(width > 0);
+/// ```
+pub fn terminal_size() -> Option<(u16, u16)> {
+    let (columns, rows) = query()?;
+    match columns > 0 && rows > 0 {
+        true => Some((columns, rows)),
+        false => None,
+    }
+}
+
+#[cfg(unix)]
+fn query() -> Option<(u16, u16)> {
+    let mut size = libc::winsize {
+        ws_row: 0,
+        ws_col: 0,
+        ws_xpixel: 0,
+        ws_ypixel: 0,
+    };
+    // SAFETY: TIOCGWINSZ only writes a `winsize` through the valid pointer.
+    let result = unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) };
+    match result {
+        0 => Some((size.ws_col, size.ws_row)),
+        _ => None,
+    }
+}
+
+#[cfg(windows)]
+fn query() -> Option<(u16, u16)> {
+    use crate::sys::windows::{std_handle, GetConsoleScreenBufferInfo, ScreenBufferInfo};
+    use crate::Stream;
+
+    let handle = std_handle(Stream::Stdout)?;
+    let mut info = ScreenBufferInfo::default();
+    // SAFETY: the handle is a valid console handle and `info` outlives the call.
+    if unsafe { GetConsoleScreenBufferInfo(handle, &mut info) } == 0 {
+        return None;
+    }
+
+    let window = info.window;
+    let columns = window.right - window.left + 1;
+    let rows = window.bottom - window.top + 1;
+    Some((columns.try_into().ok()?, rows.try_into().ok()?))
+}
+
+#[cfg(not(any(unix, windows)))]
+fn query() -> Option<(u16, u16)> {
+    None
+}
@@ -0,0 +1,325 @@
+//! Animated spinners for long-running tasks, finished with a success or failure line.
+
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::symbols::unicode_supported;
+use crate::theme::current_style;
+use crate::{clw, format, Color, Stream, StyleSpec, StyledString, CLW};
+
+/// The frames a spinner cycles through.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Frames {
+    /// `⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏`
+    #[default]
+    Dots,
+    /// `-\|/`, also used when Unicode isn't supported.
+    Line,
+    /// The phases of the moon, `🌑` to `🌘`.
+    Moon,
+    /// `◜◠◝◞◡◟`
+    Arc,
+    /// `⠁⠂⠄⠂`
+    Bounce,
+}
+
+impl Frames {
+    /// ## Returns the frames, in order.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::spinner::Frames;
+    ///
+    /// assert_eq!(Frames::Line.frames(), ["-", "\\", "|", "/"]);
+    /// ```
+    pub fn frames(self) -> &'static [&'static str] {
+        match self {
+            Frames::Dots => &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"],
+            Frames::Line => &["-", "\\", "|", "/"],
+            Frames::Moon => &["🌑", "🌒", "🌓", "🌔", "🌕", "🌖", "🌗", "🌘"],
+            Frames::Arc => &["◜", "◠", "◝", "◞", "◡", "◟"],
+            Frames::Bounce => &["⠁", "⠂", "⠄", "⠂"],
+        }
+    }
+
+    /// ## Returns how long each frame is shown.
+    pub fn interval(self) -> Duration {
+        let millis = match self {
+            Frames::Dots | Frames::Moon => 80,
+            Frames::Line => 130,
+            Frames::Arc => 100,
+            Frames::Bounce => 120,
+        };
+        Duration::from_millis(millis)
+    }
+}
+
+/// An animated spinner with a styled label, drawn on one line of a terminal.
+///
+/// The animation only runs when the stream is a terminal. In pipes and files,
+/// nothing is drawn until a finalizer writes the result as a single line,
+/// styled as the color choice decides. Dropping a running spinner clears it.
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{clw, spinner::{Frames, Spinner}};
+///
+/// let spinner = Spinner::new("Compiling").frames(Frames::Line).start();
+/// spinner.set_label(clw("Linking").font_bold());
+/// spinner.success("Built in 2.4s");
+/// ```
+pub struct Spinner {
+    label: Arc<Mutex<StyledString>>,
+    frames: Frames,
+    spec: StyleSpec,
+    stream: Stream,
+    show_elapsed: bool,
+    soft_timeout: Option<Duration>,
+    started: Option<Instant>,
+    running: Option<(Arc<AtomicBool>, JoinHandle<()>)>,
+}
+
+impl Spinner {
+    /// ## Creates a spinner, which doesn't move until it's started.
+    ///
+    /// ### Arguments
+    ///
+    /// * `label` - The text after the spinner, `CLW` values or strings
+    pub fn new<S: Into<StyledString>>(label: S) -> Self {
+        Spinner {
+            label: Arc::new(Mutex::new(label.into())),
+            frames: Frames::default(),
+            spec: StyleSpec::new().text(Color::Cyan),
+            stream: Stream::Stderr,
+            show_elapsed: false,
+            soft_timeout: None,
+            started: None,
+            running: None,
+        }
+    }
+
+    /// ## Sets the frames, dots by default.
+    ///
+    /// Without Unicode support, `Frames::Line` is used instead.
+    pub fn frames(mut self, frames: Frames) -> Self {
+        self.frames = frames;
+        self
+    }
+
+    /// ## Sets the style of the spinner itself, cyan by default.
+    pub fn style(mut self, spec: StyleSpec) -> Self {
+        self.spec = spec;
+        self
+    }
+
+    /// ## Sets the stream the spinner is drawn on, the standard error by default.
+    pub fn stream(mut self, stream: Stream) -> Self {
+        self.stream = stream;
+        self
+    }
+
+    /// ## Shows the time since the spinner started after its label, and on its final line.
+    ///
+    /// ### Arguments
+    ///
+    /// * `show` - Whether to show the time, as `(12s)`
+    pub fn elapsed(mut self, show: bool) -> Self {
+        self.show_elapsed = show;
+        self
+    }
+
+    /// ## Warns that the task may be stuck when it runs longer than a timeout.
+    ///
+    /// Past the timeout, the spinner and the elapsed time take the `"warning"` style of the
+    /// current theme, yellow by default, and past twice the timeout its `"error"` style, red
+    /// by default. The elapsed time is shown from then on, even without
+    /// [`elapsed`](Spinner::elapsed).
+    ///
+    /// ### Arguments
+    ///
+    /// * `timeout` - How long the task is expected to take at most
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use clwind::{spinner::Spinner, Color};
+    ///
+    /// let spinner = Spinner::new("Uploading").soft_timeout(Duration::from_secs(30));
+    ///
+    /// assert_eq!(spinner.style_after(Duration::from_secs(10)).text_color(), Some(Color::Cyan));
+    /// assert_eq!(spinner.style_after(Duration::from_secs(45)).text_color(), Some(Color::Yellow));
+    /// assert_eq!(spinner.style_after(Duration::from_secs(90)).text_color(), Some(Color::BrightRed));
+    /// ```
+    pub fn soft_timeout(mut self, timeout: Duration) -> Self {
+        self.soft_timeout = Some(timeout);
+        self
+    }
+
+    /// ## Returns the style of the spinner once it has run for a time.
+    ///
+    /// This is the spinner style, until the soft timeout is exceeded.
+    ///
+    /// ### Arguments
+    ///
+    /// * `elapsed` - The time since the spinner started
+    pub fn style_after(&self, elapsed: Duration) -> StyleSpec {
+        timed_style(&self.spec, self.soft_timeout, elapsed)
+    }
+
+    /// ## Starts the animation on a background thread, if the stream is a terminal.
+    pub fn start(mut self) -> Self {
+        if self.started.is_none() {
+            self.started = Some(Instant::now());
+        }
+        if self.running.is_some() || !self.stream.is_terminal() {
+            return self;
+        }
+
+        let frames = match unicode_supported() {
+            true => self.frames,
+            false => Frames::Line,
+        };
+        let stop = Arc::new(AtomicBool::new(false));
+        let (label, spec, stream) = (Arc::clone(&self.label), self.spec.clone(), self.stream);
+        let (show_elapsed, timeout) = (self.show_elapsed, self.soft_timeout);
+        let started = self.started.unwrap_or_else(Instant::now);
+        let running = Arc::clone(&stop);
+        let thread = thread::spawn(move || {
+            for frame in frames.frames().iter().cycle() {
+                if running.load(Ordering::Relaxed) {
+                    break;
+                }
+
+                let elapsed = started.elapsed();
+                let spec = timed_style(&spec, timeout, elapsed);
+                let label = render(&label.lock().unwrap_or_else(|e| e.into_inner()), stream);
+                let frame = spec.apply(*frame).for_stream(stream);
+                let late = timeout.is_some_and(|timeout| elapsed > timeout);
+                let time = match show_elapsed || late {
+                    true => elapsed_label(elapsed, late.then_some(&spec)).for_stream(stream),
+                    false => String::new(),
+                };
+                write(stream, &format!("\r{} {}{}\x1b[K", frame, label, time));
+                thread::sleep(frames.interval());
+            }
+        });
+
+        write(self.stream, "\x1b[?25l");
+        self.running = Some((stop, thread));
+        self
+    }
+
+    /// ## Replaces the label, shown from the next frame.
+    pub fn set_label<S: Into<StyledString>>(&self, label: S) {
+        *self.label.lock().unwrap_or_else(|e| e.into_inner()) = label.into();
+    }
+
+    /// ## Stops the spinner and clears its line.
+    pub fn stop(mut self) {
+        self.halt();
+    }
+
+    /// ## Stops the spinner and replaces it with a success line, `✔ message`.
+    pub fn success<S: Into<StyledString>>(self, message: S) {
+        let symbol = symbol("✔", "+").success();
+        self.finish(symbol, message.into());
+    }
+
+    /// ## Stops the spinner and replaces it with a failure line, `✖ message`.
+    pub fn fail<S: Into<StyledString>>(self, message: S) {
+        let symbol = symbol("✖", "x").error();
+        self.finish(symbol, message.into());
+    }
+
+    /// ## Stops the spinner and writes a final line in its place.
+    fn finish(mut self, symbol: CLW, message: StyledString) {
+        self.halt();
+        let time = match (self.show_elapsed, self.started) {
+            (true, Some(started)) => elapsed_label(started.elapsed(), None).for_stream(self.stream),
+            _ => String::new(),
+        };
+        let line = format!(
+            "{} {}{}\n",
+            symbol.for_stream(self.stream),
+            render(&message, self.stream),
+            time
+        );
+        write(self.stream, &line);
+    }
+
+    /// ## Stops the animation, clears the line and shows the cursor again.
+    fn halt(&mut self) {
+        if let Some((stop, thread)) = self.running.take() {
+            stop.store(true, Ordering::Relaxed);
+            let _ = thread.join();
+            write(self.stream, "\r\x1b[K\x1b[?25h");
+        }
+    }
+}
+
+impl Drop for Spinner {
+    fn drop(&mut self) {
+        self.halt();
+    }
+}
+
+/// ## Returns the spinner style, or a warning one once the soft timeout is exceeded.
+fn timed_style(spec: &StyleSpec, timeout: Option<Duration>, elapsed: Duration) -> StyleSpec {
+    match timeout {
+        Some(timeout) if elapsed > timeout * 2 => {
+            current_style("error").unwrap_or_else(|| StyleSpec::new().text(Color::Red))
+        }
+        Some(timeout) if elapsed > timeout => {
+            current_style("warning").unwrap_or_else(|| StyleSpec::new().text(Color::Yellow))
+        }
+        _ => spec.clone(),
+    }
+}
+
+/// ## Formats the elapsed time shown after the label, dim unless a warning style is given.
+fn elapsed_label(elapsed: Duration, warning: Option<&StyleSpec>) -> CLW<'static> {
+    let time = match elapsed.as_secs() {
+        secs @ 0..60 => format!("{}s", secs),
+        secs => format::duration(Duration::from_secs(secs)),
+    };
+    match warning {
+        Some(spec) => spec.apply(format!(" ({})", time)),
+        None => clw(format!(" ({})", time)).font_dim(),
+    }
+}
+
+/// ## Picks the Unicode symbol, or its ASCII fallback.
+fn symbol<'a>(unicode: &'a str, ascii: &'a str) -> CLW<'a> {
+    match unicode_supported() {
+        true => clw(unicode),
+        false => clw(ascii),
+    }
+}
+
+/// ## Renders styled text for a stream, without escape codes if it shouldn't be colorized.
+pub(crate) fn render(text: &StyledString, stream: Stream) -> String {
+    text.segments()
+        .iter()
+        .map(|s| s.for_stream(stream))
+        .collect()
+}
+
+/// ## Writes to a stream and flushes it, ignoring errors so a closed stream doesn't stop the task.
+pub(crate) fn write(stream: Stream, text: &str) {
+    let _ = match stream {
+        Stream::Stdout => {
+            let mut out = std::io::stdout().lock();
+            out.write_all(text.as_bytes()).and_then(|_| out.flush())
+        }
+        Stream::Stderr => {
+            let mut out = std::io::stderr().lock();
+            out.write_all(text.as_bytes()).and_then(|_| out.flush())
+        }
+    };
+}
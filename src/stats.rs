@@ -0,0 +1,230 @@
+//! Accounting of the bytes spent on escape sequences, with an optional budget.
+//!
+//! Counting is off until [`track_stats`] or [`set_escape_budget`] turns it on,
+//! so output that isn't measured costs nothing extra.
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use crate::{hook, strip_ansi, Stream, StyleSpec};
+
+/// Whether rendered output is counted.
+static TRACKING: AtomicBool = AtomicBool::new(false);
+/// The escape bytes allowed before styling is dropped, `u64::MAX` for no budget.
+static BUDGET: AtomicU64 = AtomicU64::new(u64::MAX);
+/// Text and escape bytes for stdout, stderr and output without a stream, in that order.
+static COUNTS: [[AtomicU64; 2]; 3] = [
+    [AtomicU64::new(0), AtomicU64::new(0)],
+    [AtomicU64::new(0), AtomicU64::new(0)],
+    [AtomicU64::new(0), AtomicU64::new(0)],
+];
+
+/// The bytes of text and of escape sequences written for one stream.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StreamStats {
+    /// The bytes of the text itself.
+    pub text_bytes: u64,
+    /// The bytes of escape sequences around the text.
+    pub escape_bytes: u64,
+}
+
+impl StreamStats {
+    /// ## Returns the escape bytes per byte of text, `0.0` if there was no text.
+    pub fn overhead(&self) -> f64 {
+        match self.text_bytes {
+            0 => 0.0,
+            text => self.escape_bytes as f64 / text as f64,
+        }
+    }
+}
+
+/// The bytes written since counting started, per stream.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OutputStats {
+    /// Output printed to the standard output, e.g. with `CLW::println`.
+    pub stdout: StreamStats,
+    /// Output printed to the standard error, e.g. with `CLW::eprintln`.
+    pub stderr: StreamStats,
+    /// Output rendered with `Display` or `to_string`, whose stream isn't known.
+    pub other: StreamStats,
+}
+
+impl OutputStats {
+    /// ## Returns the sum of all streams.
+    pub fn total(&self) -> StreamStats {
+        let streams = [self.stdout, self.stderr, self.other];
+        StreamStats {
+            text_bytes: streams.iter().map(|s| s.text_bytes).sum(),
+            escape_bytes: streams.iter().map(|s| s.escape_bytes).sum(),
+        }
+    }
+}
+
+/// ## Turns counting of rendered output on or off.
+///
+/// Styled values are counted for the stream they are printed to, and
+/// components rendered with [`AnsiRenderer`](crate::AnsiRenderer) as output
+/// without a stream.
+///
+/// ### Arguments
+///
+/// * `enabled` - Whether to count the bytes of styled values as they are rendered
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{clw, reset_stats, stats, track_stats, ColorChoice};
+///
+/// track_stats(true);
+/// reset_stats();
+/// let _ = clw("ok").text_green().color_choice(ColorChoice::Always).to_string();
+///
+/// let other = stats().other;
+/// assert_eq!((other.text_bytes, other.escape_bytes), (2, 9));
+/// # track_stats(false);
+/// ```
+///
+/// Components are counted piece by piece:
+///
+/// ```
+/// use clwind::{reset_stats, set_colors_enabled, stats, track_stats, AnsiRenderer, Color};
+/// use clwind::{Renderer, StyleSpec};
+///
+/// track_stats(true);
+/// set_colors_enabled(true);
+/// reset_stats();
+/// let _ = AnsiRenderer.paint("ok", &StyleSpec::new().text(Color::Green));
+///
+/// let other = stats().other;
+/// assert_eq!((other.text_bytes, other.escape_bytes), (2, 9));
+/// # track_stats(false);
+/// ```
+///
+/// Only escape sequences count as escape bytes, so a link shown with its
+/// fallback counts the URL as text:
+///
+/// ```
+/// use clwind::{clw, link::{set_hyperlinks, set_link_fallback, LinkFallback}};
+/// use clwind::{reset_stats, stats, track_stats, ColorChoice};
+///
+/// track_stats(true);
+/// set_hyperlinks(false);
+/// set_link_fallback(LinkFallback::Parens);
+/// reset_stats();
+/// let _ = clw("docs").link("x.io").color_choice(ColorChoice::Never).to_string();
+///
+/// let other = stats().other;
+/// assert_eq!((other.text_bytes, other.escape_bytes), (11, 0));
+/// # track_stats(false);
+/// ```
+pub fn track_stats(enabled: bool) {
+    TRACKING.store(enabled, Ordering::Relaxed);
+}
+
+/// ## Returns the bytes counted since counting started or was last reset.
+pub fn stats() -> OutputStats {
+    let read = |stream: usize| StreamStats {
+        text_bytes: COUNTS[stream][0].load(Ordering::Relaxed),
+        escape_bytes: COUNTS[stream][1].load(Ordering::Relaxed),
+    };
+    OutputStats {
+        stdout: read(0),
+        stderr: read(1),
+        other: read(2),
+    }
+}
+
+/// ## Sets the counts back to zero, which also starts the escape budget over.
+pub fn reset_stats() {
+    COUNTS
+        .iter()
+        .flatten()
+        .for_each(|c| c.store(0, Ordering::Relaxed));
+}
+
+/// ## Limits the bytes spent on escape sequences, then prints plain text.
+///
+/// Setting a budget turns counting on. Once the escape bytes of all streams
+/// reach it, styled values are rendered without escape codes, as if colors
+/// were disabled, until the budget is raised or the counts are reset.
+///
+/// ### Arguments
+///
+/// * `bytes` - The escape bytes allowed, or `None` for no limit
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{clw, reset_stats, set_escape_budget, ColorChoice};
+///
+/// reset_stats();
+/// set_escape_budget(Some(9));
+/// let ok = || clw("ok").text_green().color_choice(ColorChoice::Always).to_string();
+///
+/// assert_eq!(ok(), "\x1b[32mok\x1b[0m");
+/// assert_eq!(ok(), "ok");
+/// # set_escape_budget(None);
+/// ```
+pub fn set_escape_budget(bytes: Option<u64>) {
+    BUDGET.store(bytes.unwrap_or(u64::MAX), Ordering::Relaxed);
+    if bytes.is_some() {
+        track_stats(true);
+    }
+}
+
+/// ## Writes one styled value, passing it to the output hook and counting its bytes.
+///
+/// Styled values and the built-in components, through
+/// [`AnsiRenderer`](crate::AnsiRenderer), all render through here. Styling is
+/// dropped once the escape budget is spent. Only escape sequences count as
+/// escape bytes; everything else, link fallbacks included, is text.
+///
+/// ### Arguments
+///
+/// * `out` - The destination to write to
+/// * `text` - The text, as passed to the hook
+/// * `spec` - The style, as passed to the hook
+/// * `stream` - The stream the output goes to, if known
+/// * `colorize` - Whether escape codes are wanted for the stream
+/// * `write` - Writes the value, with or without escape codes
+pub(crate) fn write_counted(
+    out: &mut dyn fmt::Write,
+    (text, spec): (&str, &StyleSpec),
+    stream: Option<Stream>,
+    colorize: bool,
+    write: impl FnOnce(&mut dyn fmt::Write, bool) -> fmt::Result,
+) -> fmt::Result {
+    hook::notify(text, spec, stream);
+    let colorize = colorize && !over_budget();
+    if !tracking() {
+        return write(out, colorize);
+    }
+
+    let mut counted = String::new();
+    write(&mut counted, colorize)?;
+    let text_bytes = strip_ansi(&counted).len();
+    record(stream, text_bytes, counted.len() - text_bytes);
+    out.write_str(&counted)
+}
+
+/// ## Checks whether rendered output should be counted.
+fn tracking() -> bool {
+    TRACKING.load(Ordering::Relaxed)
+}
+
+/// ## Checks whether the escape budget is spent, so styling should be dropped.
+fn over_budget() -> bool {
+    let budget = BUDGET.load(Ordering::Relaxed);
+    budget != u64::MAX && stats().total().escape_bytes >= budget
+}
+
+/// ## Adds the bytes of one rendered value to the counts of its stream.
+fn record(stream: Option<Stream>, text_bytes: usize, escape_bytes: usize) {
+    let counts = &COUNTS[match stream {
+        Some(Stream::Stdout) => 0,
+        Some(Stream::Stderr) => 1,
+        None => 2,
+    }];
+    counts[0].fetch_add(text_bytes as u64, Ordering::Relaxed);
+    counts[1].fetch_add(escape_bytes as u64, Ordering::Relaxed);
+}
@@ -0,0 +1,125 @@
+//! A status line pinned to the bottom of the terminal while output scrolls above it.
+
+use std::sync::Mutex;
+
+use crate::spinner::{render, write};
+use crate::{terminal_size, Stream, StyledString};
+
+/// One styled line kept at the bottom of the terminal, e.g. the progress of a build.
+///
+/// While it is shown, the rows above it form a scrolling region, so output
+/// printed as usual scrolls without overwriting it. It is drawn with the cursor
+/// saved and restored around it. Nothing is drawn when the stream isn't a
+/// terminal. Dropping it clears the line and restores the whole screen.
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{clw, status::StatusLine};
+///
+/// let status = StatusLine::new().show();
+/// for (i, name) in ["serde", "syn", "clwind"].iter().enumerate() {
+///     status.set(clw(format!("[{}/3] Compiling", i + 1)).text_cyan() + " " + *name);
+///     println!("   Compiled {}", name);
+/// }
+/// status.clear();
+/// ```
+pub struct StatusLine {
+    stream: Stream,
+    rows: Option<u16>,
+    text: Mutex<StyledString>,
+}
+
+impl Default for StatusLine {
+    fn default() -> Self {
+        StatusLine {
+            stream: Stream::Stdout,
+            rows: None,
+            text: Mutex::new(StyledString::new()),
+        }
+    }
+}
+
+impl StatusLine {
+    /// ## Creates an empty status line, which isn't shown until [`StatusLine::show`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// ## Sets the stream the line is drawn on, the standard output by default.
+    ///
+    /// It should be the stream the rest of the output goes to.
+    pub fn stream(mut self, stream: Stream) -> Self {
+        self.stream = stream;
+        self
+    }
+
+    /// ## Reserves the bottom row of the terminal and draws the line there.
+    ///
+    /// The size of the terminal is read once, here.
+    pub fn show(mut self) -> Self {
+        if self.rows.is_some() || !self.stream.is_terminal() {
+            return self;
+        }
+        let Some((_, rows)) = terminal_size().filter(|&(_, rows)| rows > 1) else {
+            return self;
+        };
+
+        // Scroll once so the cursor isn't left on the reserved row, then limit
+        // scrolling to the rows above it, which moves the cursor to the top.
+        write(
+            self.stream,
+            &format!("\n\x1b[1A\x1b7\x1b[1;{}r\x1b8", rows - 1),
+        );
+        self.rows = Some(rows);
+        self.draw();
+        self
+    }
+
+    /// ## Replaces the text of the line and redraws it.
+    ///
+    /// ### Arguments
+    ///
+    /// * `text` - The new text, `CLW` values or strings
+    pub fn set<S: Into<StyledString>>(&self, text: S) {
+        *self.text.lock().unwrap_or_else(|e| e.into_inner()) = text.into();
+        self.draw();
+    }
+
+    /// ## Removes the line and lets output scroll over the whole terminal again.
+    pub fn clear(mut self) {
+        self.hide();
+    }
+
+    /// ## Draws the text on the bottom row, leaving the cursor where it was.
+    fn draw(&self) {
+        let Some(rows) = self.rows else {
+            return;
+        };
+
+        let text = render(
+            &self.text.lock().unwrap_or_else(|e| e.into_inner()),
+            self.stream,
+        );
+        write(
+            self.stream,
+            &format!("\x1b7\x1b[{};1H\x1b[2K{}\x1b8", rows, text),
+        );
+    }
+
+    /// ## Resets the scrolling region and clears the bottom row.
+    fn hide(&mut self) {
+        if let Some(rows) = self.rows.take() {
+            write(
+                self.stream,
+                &format!("\x1b7\x1b[r\x1b[{};1H\x1b[2K\x1b8", rows),
+            );
+        }
+    }
+}
+
+impl Drop for StatusLine {
+    fn drop(&mut self) {
+        self.hide();
+    }
+}
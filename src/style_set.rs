@@ -0,0 +1,160 @@
+//! Sets of styles, built with `|`.
+
+use std::fmt;
+use std::ops::{BitOr, BitOrAssign};
+
+use crate::{Style, StyleSpec, CLW, STYLE_NAMES};
+
+/// A set of styles, passed around as a plain value.
+///
+/// Combine styles with `|`, e.g. `Style::Bold | Style::Underline`.
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{Style, StyleSet};
+///
+/// let emphasis = Style::Bold | Style::Italic;
+///
+/// assert!(emphasis.contains(Style::Italic));
+/// assert!(!emphasis.contains(Style::Underline));
+/// assert_eq!(emphasis | Style::Bold, emphasis);
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct StyleSet {
+    bits: u16,
+}
+
+impl StyleSet {
+    /// ## Creates an empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// ## Returns true if the set contains the style.
+    pub fn contains(self, style: Style) -> bool {
+        self.bits & bit(style) != 0
+    }
+
+    /// ## Returns true if the set has no styles.
+    pub fn is_empty(self) -> bool {
+        self.bits == 0
+    }
+
+    /// ## Returns the set without the given style.
+    pub fn without(self, style: Style) -> Self {
+        StyleSet {
+            bits: self.bits & !bit(style),
+        }
+    }
+
+    /// ## Returns the styles of the set, in the order of their codes.
+    pub fn iter(self) -> impl Iterator<Item = Style> {
+        STYLE_NAMES
+            .into_iter()
+            .map(|(style, _)| style)
+            .filter(move |&style| self.contains(style))
+    }
+}
+
+/// ## Returns the bit of a style in a set, after its place in `STYLE_NAMES`.
+fn bit(style: Style) -> u16 {
+    let index = STYLE_NAMES
+        .iter()
+        .position(|&(s, _)| s == style)
+        .expect("every style has a name");
+    1 << index
+}
+
+impl From<Style> for StyleSet {
+    fn from(style: Style) -> Self {
+        StyleSet { bits: bit(style) }
+    }
+}
+
+impl FromIterator<Style> for StyleSet {
+    fn from_iter<I: IntoIterator<Item = Style>>(styles: I) -> Self {
+        styles
+            .into_iter()
+            .fold(StyleSet::new(), |set, style| set | style)
+    }
+}
+
+impl<S: Into<StyleSet>> BitOr<S> for StyleSet {
+    type Output = StyleSet;
+
+    fn bitor(self, other: S) -> StyleSet {
+        StyleSet {
+            bits: self.bits | other.into().bits,
+        }
+    }
+}
+
+impl<S: Into<StyleSet>> BitOr<S> for Style {
+    type Output = StyleSet;
+
+    fn bitor(self, other: S) -> StyleSet {
+        StyleSet::from(self) | other
+    }
+}
+
+impl<S: Into<StyleSet>> BitOrAssign<S> for StyleSet {
+    fn bitor_assign(&mut self, other: S) {
+        *self = *self | other;
+    }
+}
+
+impl fmt::Debug for StyleSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl StyleSpec {
+    /// ## Adds every style of a set to the text.
+    ///
+    /// ### Arguments
+    ///
+    /// * `styles` - The styles to add, e.g. `Style::Bold | Style::Dim`
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{Style, StyleSpec};
+    ///
+    /// let spec = StyleSpec::new().font_set(Style::Bold | Style::Underline);
+    ///
+    /// assert_eq!(spec.style_set(), Style::Bold | Style::Underline);
+    /// ```
+    pub fn font_set<S: Into<StyleSet>>(mut self, styles: S) -> Self {
+        self.font.extend(styles.into().iter());
+        self
+    }
+
+    /// ## Returns the styles as a set.
+    pub fn style_set(&self) -> StyleSet {
+        self.font.iter().copied().collect()
+    }
+}
+
+impl CLW<'_> {
+    /// ## Adds every style of a set to the text.
+    ///
+    /// ### Arguments
+    ///
+    /// * `styles` - The styles to add, e.g. `Style::Bold | Style::Underline`
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{clw, RenderMode, Style};
+    ///
+    /// let title = clw("x").fonts(Style::Bold | Style::Underline).font(Style::Italic);
+    ///
+    /// assert_eq!(title.to_string_for(RenderMode::Test), "[Bold,Underline,Italic]x[/]");
+    /// ```
+    pub fn fonts<S: Into<StyleSet>>(mut self, styles: S) -> Self {
+        self.spec = self.spec.font_set(styles);
+        self
+    }
+}
@@ -0,0 +1,304 @@
+//! Lines made of independently styled segments, built with `+`.
+
+use std::fmt;
+use std::ops::{Add, AddAssign, Range};
+
+use crate::{char_width, clw, parse_ansi, RenderMode, Renderer, StyleSpec, CLW};
+
+/// A sequence of styled segments, rendered together.
+///
+/// Adding `CLW` values or strings with `+` builds one.
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{clw, RenderMode, StyledString};
+///
+/// let line = clw("error").text_red() + ": " + clw("missing file").font_bold();
+///
+/// assert_eq!(line.plain(), "error: missing file");
+/// assert_eq!(line.to_string_for(RenderMode::Test), "[fg=Red]error[/]: [Bold]missing file[/]");
+///
+/// let mut status = StyledString::new();
+/// status += clw("ok").text_green();
+/// status += " 12 passed";
+/// assert_eq!(status.plain(), "ok 12 passed");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct StyledString {
+    segments: Vec<CLW<'static>>,
+}
+
+impl StyledString {
+    /// ## Creates an empty string.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// ## Appends styled text, plain text, or the segments of another `StyledString`.
+    ///
+    /// ### Arguments
+    ///
+    /// * `segment` - The text to append
+    pub fn push<S: Into<StyledString>>(&mut self, segment: S) {
+        self.segments.extend(segment.into().segments);
+    }
+
+    /// ## Returns the segments, in order.
+    pub fn segments(&self) -> &[CLW<'static>] {
+        &self.segments
+    }
+
+    /// ## Returns true if there is no text.
+    pub fn is_empty(&self) -> bool {
+        self.segments.iter().all(|s| s.plain().is_empty())
+    }
+
+    /// ## Returns the text without any styling.
+    pub fn plain(&self) -> String {
+        self.segments.iter().map(CLW::plain).collect()
+    }
+
+    /// ## Finds every occurrence of a text, with where it is shown and how it is styled.
+    ///
+    /// Positions count visible characters only, so escape codes in the segments, from nested
+    /// styled values, are skipped. Matches don't overlap, and an empty pattern has none.
+    ///
+    /// ### Arguments
+    ///
+    /// * `pattern` - The text to look for
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{clw, Color, ColorChoice, StyleSpec, StyledString};
+    ///
+    /// let line = clw("错误").text_red() + ": disk " + clw("error").text_red();
+    /// let found = line.find("误: d");
+    ///
+    /// assert_eq!(found.len(), 1);
+    /// assert_eq!(found[0].range, 1..5);
+    /// assert_eq!(found[0].columns, 2..7);
+    /// assert_eq!(found[0].styles, [StyleSpec::new().text(Color::Red), StyleSpec::new()]);
+    ///
+    /// let error = clw("error").text_red().color_choice(ColorChoice::Always);
+    /// let nested = StyledString::from(clw(format!("disk {}", error)));
+    /// let found = nested.find("error");
+    ///
+    /// assert_eq!(found[0].range, 5..10);
+    /// assert_eq!(found[0].styles, [StyleSpec::new().text(Color::Red)]);
+    /// ```
+    pub fn find(&self, pattern: &str) -> Vec<TextMatch> {
+        if pattern.is_empty() {
+            return Vec::new();
+        }
+
+        // The visible text, with the byte where each run of one style starts.
+        let mut text = String::new();
+        let mut runs: Vec<(usize, StyleSpec)> = Vec::new();
+        for segment in &self.segments {
+            for piece in parse_ansi(&segment.value) {
+                runs.push((text.len(), piece.style.over(&segment.spec)));
+                text.push_str(piece.text);
+            }
+        }
+
+        let (mut chars, mut columns, mut counted) = (0, 0, 0);
+        let mut position = |byte: usize| {
+            for c in text[counted..byte].chars() {
+                chars += 1;
+                columns += char_width(c);
+            }
+            counted = byte;
+            (chars, columns)
+        };
+        text.match_indices(pattern)
+            .map(|(start, found)| {
+                let end = start + found.len();
+                let (first_char, first_column) = position(start);
+                let (last_char, last_column) = position(end);
+                let mut styles: Vec<StyleSpec> = Vec::new();
+                for (i, (run, spec)) in runs.iter().enumerate() {
+                    let run_end = runs.get(i + 1).map_or(text.len(), |(next, _)| *next);
+                    if *run < end && run_end > start && !styles.contains(spec) {
+                        styles.push(spec.clone());
+                    }
+                }
+                TextMatch {
+                    range: first_char..last_char,
+                    columns: first_column..last_column,
+                    styles,
+                }
+            })
+            .collect()
+    }
+
+    /// ## Splits the string at line breaks, each segment keeping its style.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{clw, RenderMode};
+    ///
+    /// let text = clw("a\nb").text_red() + " c";
+    /// let lines: Vec<String> = text.lines().iter().map(|l| l.to_string_for(RenderMode::Test)).collect();
+    ///
+    /// assert_eq!(lines, ["[fg=Red]a[/]", "[fg=Red]b[/] c"]);
+    /// ```
+    pub fn lines(&self) -> Vec<StyledString> {
+        let mut lines = vec![StyledString::new()];
+        for segment in &self.segments {
+            for (i, part) in segment.value.split('\n').enumerate() {
+                if i > 0 {
+                    lines.push(StyledString::new());
+                }
+                if !part.is_empty() {
+                    let mut piece = segment.clone();
+                    piece.value = part.to_string().into();
+                    lines
+                        .last_mut()
+                        .expect("there is always a line")
+                        .push(piece);
+                }
+            }
+        }
+        lines
+    }
+
+    /// ## Renders the segments through the given renderer.
+    ///
+    /// ### Arguments
+    ///
+    /// * `renderer` - The backend to render with
+    pub fn render_with(&self, renderer: &dyn Renderer) -> String {
+        self.segments
+            .iter()
+            .map(|segment| segment.render_with(renderer))
+            .collect()
+    }
+
+    /// ## Renders the segments in the given output format.
+    ///
+    /// ### Arguments
+    ///
+    /// * `mode` - The output format
+    pub fn to_string_for(&self, mode: RenderMode) -> String {
+        self.render_with(mode.renderer())
+    }
+}
+
+/// An occurrence of a text in a [`StyledString`], as found by [`StyledString::find`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TextMatch {
+    /// The visible characters matched, counted without escape codes.
+    pub range: Range<usize>,
+    /// The terminal columns matched, where wide characters take two.
+    pub columns: Range<usize>,
+    /// The styles active over the match, in order and each listed once.
+    pub styles: Vec<StyleSpec>,
+}
+
+/// ## Joins styled items with a separator, keeping the style of each item to itself.
+///
+/// ### Arguments
+///
+/// * `items` - The items to join, `CLW` values or strings
+/// * `separator` - The text put between two items, which may be styled too
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{clw, join_styled, RenderMode};
+///
+/// let tags = join_styled([clw("bug").text_red(), clw("ui").text_blue()], ", ");
+///
+/// assert_eq!(tags.plain(), "bug, ui");
+/// assert_eq!(tags.to_string_for(RenderMode::Test), "[fg=Red]bug[/], [fg=Blue]ui[/]");
+/// ```
+pub fn join_styled<I, S>(items: I, separator: S) -> StyledString
+where
+    I: IntoIterator,
+    I::Item: Into<StyledString>,
+    S: Into<StyledString>,
+{
+    let separator = separator.into();
+    let mut joined = StyledString::new();
+    for (i, item) in items.into_iter().enumerate() {
+        if i > 0 {
+            joined.push(separator.clone());
+        }
+        joined.push(item);
+    }
+    joined
+}
+
+impl fmt::Display for StyledString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.segments.iter().try_for_each(|s| write!(f, "{}", s))
+    }
+}
+
+impl AsRef<[CLW<'static>]> for StyledString {
+    fn as_ref(&self) -> &[CLW<'static>] {
+        &self.segments
+    }
+}
+
+impl From<CLW<'_>> for StyledString {
+    fn from(segment: CLW<'_>) -> Self {
+        StyledString {
+            segments: vec![segment.into_owned()],
+        }
+    }
+}
+
+impl From<&str> for StyledString {
+    fn from(text: &str) -> Self {
+        clw(text.to_string()).into()
+    }
+}
+
+impl From<String> for StyledString {
+    fn from(text: String) -> Self {
+        clw(text).into()
+    }
+}
+
+impl<S: Into<StyledString>> Add<S> for StyledString {
+    type Output = StyledString;
+
+    fn add(mut self, other: S) -> StyledString {
+        self.push(other);
+        self
+    }
+}
+
+impl<S: Into<StyledString>> AddAssign<S> for StyledString {
+    fn add_assign(&mut self, other: S) {
+        self.push(other);
+    }
+}
+
+impl<S: Into<StyledString>> Add<S> for CLW<'_> {
+    type Output = StyledString;
+
+    fn add(self, other: S) -> StyledString {
+        StyledString::from(self) + other
+    }
+}
+
+impl Add<CLW<'_>> for &str {
+    type Output = StyledString;
+
+    fn add(self, other: CLW<'_>) -> StyledString {
+        StyledString::from(self) + other
+    }
+}
+
+impl Add<StyledString> for &str {
+    type Output = StyledString;
+
+    fn add(self, other: StyledString) -> StyledString {
+        StyledString::from(self) + other
+    }
+}
@@ -0,0 +1,287 @@
+//! SVG snapshots of styled terminal output.
+
+use std::fmt::{self, Write};
+
+use crate::render::{css_color, write_html_escaped};
+use crate::{
+    apply_transform, parse_ansi, strip_ansi, text_width, Color, Renderer, Style, StyleSpec, CLW,
+};
+
+/// The foreground used for text without a color.
+const DEFAULT_FG: &str = "#d4d4d4";
+/// The background of the terminal frame.
+const FRAME_BG: &str = "#1e1e1e";
+
+/// Renders styled lines into an SVG image with a terminal-like frame.
+///
+/// As a [`Renderer`], it emits `<tspan>` elements that can be placed in any
+/// `<text>` element; [`SvgRenderer::snapshot`] builds a complete document.
+#[derive(Clone, Debug)]
+pub struct SvgRenderer {
+    font_size: f32,
+    title: Option<String>,
+}
+
+impl Default for SvgRenderer {
+    fn default() -> Self {
+        SvgRenderer {
+            font_size: 14.0,
+            title: None,
+        }
+    }
+}
+
+impl SvgRenderer {
+    /// ## Creates a renderer with a 14px font and no title.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// ## Sets the font size in pixels.
+    ///
+    /// ### Arguments
+    ///
+    /// * `font_size` - The font size; cell sizes are derived from it
+    pub fn font_size(mut self, font_size: f32) -> Self {
+        self.font_size = font_size;
+        self
+    }
+
+    /// ## Sets the title shown in the frame's title bar.
+    ///
+    /// ### Arguments
+    ///
+    /// * `title` - The window title
+    pub fn title<S: Into<String>>(mut self, title: S) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// ## Renders the lines into a standalone SVG document.
+    ///
+    /// Each line is a sequence of independently styled segments, such as a
+    /// `Vec<CLW>` or a [`StyledString`](crate::StyledString). Segments are
+    /// placed on a fixed character grid, so box-drawing characters line up.
+    ///
+    /// ### Arguments
+    ///
+    /// * `lines` - The styled lines to draw, top to bottom
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{clw, SvgRenderer};
+    ///
+    /// let svg = SvgRenderer::new().title("demo").snapshot(&[
+    ///     vec![clw("┌──┐")],
+    ///     vec![clw("│"), clw("ok").text_green().bg_black(), clw("│")],
+    ///     vec![clw("└──┘")],
+    /// ]);
+    ///
+    /// assert!(svg.starts_with("<svg"));
+    ///
+    /// let status = clw("ok").text_green() + " 12 passed";
+    /// assert!(SvgRenderer::new().snapshot(&[status]).contains("12 passed"));
+    /// ```
+    ///
+    /// Styled values nested in the text keep their style, and default colors
+    /// leave the frame showing through:
+    ///
+    /// ```
+    /// use clwind::{clw, Color, ColorChoice, SvgRenderer};
+    ///
+    /// let ok = clw("ok").text_green().color_choice(ColorChoice::Always);
+    /// let line = clw(format!("{} done", ok)).bg(Color::Default);
+    /// let svg = SvgRenderer::new().snapshot(&[vec![line]]);
+    ///
+    /// assert!(!svg.contains('\x1b'));
+    /// assert!(svg.contains("<tspan x=\"14.0\" fill=\"#00cd00\">ok</tspan>"));
+    /// assert!(!svg.contains("inherit"));
+    /// ```
+    pub fn snapshot<'a, L: AsRef<[CLW<'a>]>>(&self, lines: &[L]) -> String {
+        let cell_width = self.font_size * 0.6;
+        let line_height = self.font_size * 1.4;
+        let padding = self.font_size;
+        let title_bar = self.font_size * 2.0;
+
+        let columns = lines
+            .iter()
+            .map(|line| {
+                line.as_ref()
+                    .iter()
+                    .map(|s| text_width(&s.value))
+                    .sum::<usize>()
+            })
+            .max()
+            .unwrap_or(0);
+        let width = padding * 2.0 + columns as f32 * cell_width;
+        let height = title_bar + padding * 2.0 + lines.len() as f32 * line_height;
+
+        let mut svg = String::new();
+        write!(
+            svg,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0:.1}\" height=\"{1:.1}\" \
+             viewBox=\"0 0 {0:.1} {1:.1}\" font-family=\"ui-monospace, Menlo, Consolas, monospace\" \
+             font-size=\"{2:.1}\">",
+            width, height, self.font_size
+        )
+        .expect("writing to a String cannot fail");
+        write!(
+            svg,
+            "<rect width=\"100%\" height=\"100%\" rx=\"8\" fill=\"{}\"/>",
+            FRAME_BG
+        )
+        .expect("writing to a String cannot fail");
+        for (i, fill) in ["#ff5f56", "#ffbd2e", "#27c93f"].iter().enumerate() {
+            write!(
+                svg,
+                "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.1}\" fill=\"{}\"/>",
+                padding + i as f32 * self.font_size * 1.4,
+                title_bar / 2.0,
+                self.font_size * 0.4,
+                fill
+            )
+            .expect("writing to a String cannot fail");
+        }
+
+        if let Some(title) = &self.title {
+            write!(
+                svg,
+                "<text x=\"{:.1}\" y=\"{:.1}\" fill=\"#999999\" text-anchor=\"middle\">",
+                width / 2.0,
+                title_bar / 2.0 + self.font_size * 0.35
+            )
+            .expect("writing to a String cannot fail");
+            write_html_escaped(&mut svg, &strip_ansi(title))
+                .expect("writing to a String cannot fail");
+            svg.push_str("</text>");
+        }
+
+        for (row, line) in lines.iter().enumerate() {
+            let top = title_bar + padding + row as f32 * line_height;
+            let baseline = top + line_height * 0.75;
+            let pieces: Vec<_> = line.as_ref().iter().flat_map(pieces).collect();
+
+            let mut column = 0;
+            for (text, spec) in &pieces {
+                let cells = text_width(text);
+                if let (_, Some(bg)) = colors(spec) {
+                    write!(
+                        svg,
+                        "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"{}\"/>",
+                        padding + column as f32 * cell_width,
+                        top,
+                        cells as f32 * cell_width,
+                        line_height,
+                        bg
+                    )
+                    .expect("writing to a String cannot fail");
+                }
+                column += cells;
+            }
+
+            write!(
+                svg,
+                "<text y=\"{:.1}\" fill=\"{}\" xml:space=\"preserve\">",
+                baseline, DEFAULT_FG
+            )
+            .expect("writing to a String cannot fail");
+            let mut column = 0;
+            for (text, spec) in &pieces {
+                write!(
+                    svg,
+                    "<tspan x=\"{:.1}\"{}>",
+                    padding + column as f32 * cell_width,
+                    tspan_attributes(spec)
+                )
+                .expect("writing to a String cannot fail");
+                write_html_escaped(&mut svg, text).expect("writing to a String cannot fail");
+                svg.push_str("</tspan>");
+                column += text_width(text);
+            }
+            svg.push_str("</text>");
+        }
+
+        svg.push_str("</svg>");
+        svg
+    }
+}
+
+impl Renderer for SvgRenderer {
+    fn render(&self, out: &mut dyn fmt::Write, text: &str, spec: &StyleSpec) -> fmt::Result {
+        for segment in parse_ansi(text) {
+            let spec = apply_transform(&segment.style.over(spec)).into_owned();
+            write!(out, "<tspan{}>", tspan_attributes(&spec))?;
+            write_html_escaped(out, segment.text)?;
+            out.write_str("</tspan>")?;
+        }
+        Ok(())
+    }
+}
+
+/// ## Splits a styled value into runs of text and their transformed styles.
+///
+/// Escape sequences in the text, from nested styled values, become runs of
+/// their own, so no control characters reach the XML.
+fn pieces<'a>(value: &'a CLW<'_>) -> Vec<(&'a str, StyleSpec)> {
+    parse_ansi(&value.value)
+        .into_iter()
+        .map(|segment| {
+            let spec = segment.style.over(&value.spec);
+            (segment.text, apply_transform(&spec).into_owned())
+        })
+        .collect()
+}
+
+/// ## Resolves the foreground and background fills of a spec, honoring `Reverse`.
+///
+/// `Color::Default` is no fill, so the frame's colors show through.
+fn colors(spec: &StyleSpec) -> (Option<String>, Option<String>) {
+    let set = |color: Option<Color>| color.filter(|&c| c != Color::Default).map(css_color);
+    let (fg, bg) = (set(spec.text_color()), set(spec.bg_color()));
+    match spec.fonts().contains(&Style::Reverse) {
+        true => (
+            Some(bg.unwrap_or_else(|| FRAME_BG.to_string())),
+            Some(fg.unwrap_or_else(|| DEFAULT_FG.to_string())),
+        ),
+        false => (fg, bg),
+    }
+}
+
+/// ## Builds the `<tspan>` presentation attributes for a spec.
+fn tspan_attributes(spec: &StyleSpec) -> String {
+    let mut attrs = String::new();
+    if let (Some(fg), _) = colors(spec) {
+        write!(attrs, " fill=\"{}\"", fg).expect("writing to a String cannot fail");
+    }
+
+    let mut decorations = Vec::new();
+    let mut decorate = |line| {
+        if !decorations.contains(&line) {
+            decorations.push(line);
+        }
+    };
+    for style in spec.fonts() {
+        match style {
+            Style::Bold => attrs.push_str(" font-weight=\"bold\""),
+            Style::Dim => attrs.push_str(" opacity=\"0.5\""),
+            Style::Italic => attrs.push_str(" font-style=\"italic\""),
+            Style::Hidden => attrs.push_str(" fill-opacity=\"0\""),
+            Style::Underline
+            | Style::DoubleUnderline
+            | Style::CurlyUnderline
+            | Style::DottedUnderline
+            | Style::DashedUnderline => decorate("underline"),
+            Style::Strikethrough => decorate("line-through"),
+            Style::Overline => decorate("overline"),
+            Style::Blink | Style::Reverse => {}
+        }
+    }
+
+    if !decorations.is_empty() {
+        write!(attrs, " text-decoration=\"{}\"", decorations.join(" "))
+            .expect("writing to a String cannot fail");
+    }
+
+    attrs
+}
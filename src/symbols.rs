@@ -0,0 +1,63 @@
+//! Small symbols shared by list and menu components, with ASCII fallbacks.
+//!
+//! Symbols are styled with the current [theme](crate::theme): checked and
+//! selected ones as `"success"`, the others as `"muted"`.
+
+use crate::{capabilities, clw, CLW};
+
+/// ## Checks whether symbols are drawn with Unicode characters rather than ASCII.
+///
+/// This is part of the [`capabilities`], detected from the locale.
+pub fn unicode_supported() -> bool {
+    capabilities().unicode
+}
+
+/// ## Returns a checkbox, `☑`/`☐` or `[x]`/`[ ]` without Unicode.
+///
+/// ### Arguments
+///
+/// * `checked` - Whether the box is checked
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{symbols::checkbox, with_capabilities, Capabilities};
+///
+/// let ascii = Capabilities::from_env(|_| None, false).unicode(false);
+///
+/// assert_eq!(with_capabilities(ascii, || checkbox(true).plain().to_string()), "[x]");
+/// ```
+pub fn checkbox(checked: bool) -> CLW<'static> {
+    symbol(checked, ("☑", "☐"), ("[x]", "[ ]"))
+}
+
+/// ## Returns a radio button, `◉`/`○` or `(*)`/`( )` without Unicode.
+///
+/// ### Arguments
+///
+/// * `selected` - Whether the option is selected
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{symbols::radio, with_capabilities, Capabilities};
+///
+/// let utf8 = Capabilities::from_env(|_| None, false).unicode(true);
+///
+/// assert_eq!(with_capabilities(utf8, || radio(false).plain().to_string()), "○");
+/// ```
+pub fn radio(selected: bool) -> CLW<'static> {
+    symbol(selected, ("◉", "○"), ("(*)", "( )"))
+}
+
+/// ## Picks the `(on, off)` symbol for the state and styles it.
+fn symbol<'a>(on: bool, unicode: (&'a str, &'a str), ascii: (&'a str, &'a str)) -> CLW<'a> {
+    let (on_symbol, off_symbol) = match unicode_supported() {
+        true => unicode,
+        false => ascii,
+    };
+    match on {
+        true => clw(on_symbol).success(),
+        false => clw(off_symbol).style("muted"),
+    }
+}
@@ -0,0 +1,67 @@
+//! Bindings to the operating system, for what the standard library doesn't cover.
+
+#[cfg(windows)]
+pub(crate) mod windows {
+    use std::ffi::c_void;
+
+    use crate::Stream;
+
+    pub(crate) type Handle = *mut c_void;
+
+    const STD_OUTPUT_HANDLE: u32 = -11i32 as u32;
+    const STD_ERROR_HANDLE: u32 = -12i32 as u32;
+    const INVALID_HANDLE_VALUE: Handle = -1isize as Handle;
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Default)]
+    pub(crate) struct Coord {
+        pub x: i16,
+        pub y: i16,
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Default)]
+    pub(crate) struct SmallRect {
+        pub left: i16,
+        pub top: i16,
+        pub right: i16,
+        pub bottom: i16,
+    }
+
+    /// `CONSOLE_SCREEN_BUFFER_INFO`
+    #[repr(C)]
+    #[derive(Clone, Copy, Default)]
+    pub(crate) struct ScreenBufferInfo {
+        pub size: Coord,
+        pub cursor: Coord,
+        pub attributes: u16,
+        pub window: SmallRect,
+        pub max_window: Coord,
+    }
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetStdHandle(std_handle: u32) -> Handle;
+        pub(crate) fn GetConsoleScreenBufferInfo(
+            console: Handle,
+            info: *mut ScreenBufferInfo,
+        ) -> i32;
+        pub(crate) fn GetConsoleMode(console: Handle, mode: *mut u32) -> i32;
+        pub(crate) fn SetConsoleMode(console: Handle, mode: u32) -> i32;
+        pub(crate) fn SetConsoleTextAttribute(console: Handle, attributes: u16) -> i32;
+    }
+
+    /// ## Returns the handle of a standard stream, if there is one.
+    pub(crate) fn std_handle(stream: Stream) -> Option<Handle> {
+        let id = match stream {
+            Stream::Stdout => STD_OUTPUT_HANDLE,
+            Stream::Stderr => STD_ERROR_HANDLE,
+        };
+        // SAFETY: GetStdHandle has no preconditions.
+        let handle = unsafe { GetStdHandle(id) };
+        match handle.is_null() || handle == INVALID_HANDLE_VALUE {
+            true => None,
+            false => Some(handle),
+        }
+    }
+}
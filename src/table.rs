@@ -0,0 +1,1168 @@
+//! Tables of styled cells, aligned by their visible width.
+
+use std::borrow::Cow;
+use std::fmt::{self, Write};
+
+use crate::panel::{paint_over, BorderStyle};
+use crate::{
+    text_width, Align, AnsiRenderer, HtmlRenderer, Renderer, Style, StyleSpec, StyledString,
+};
+
+/// Rows of styled cells, rendered in aligned columns.
+///
+/// Column widths are measured without escape codes, so styled cells line up.
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{clw, table::Table, Align, PlainRenderer};
+///
+/// let table = Table::new()
+///     .header(["crate", "downloads"])
+///     .row([clw("clwind").text_cyan(), clw(1200).text_green()])
+///     .row(["serde", "9000000"])
+///     .align(1, Align::Right);
+///
+/// assert_eq!(
+///     table.render_with(&PlainRenderer),
+///     "crate   downloads\n\
+///      clwind       1200\n\
+///      serde     9000000"
+/// );
+/// ```
+pub struct Table {
+    header: Option<Vec<Cell>>,
+    rows: Vec<Vec<Cell>>,
+    header_spec: StyleSpec,
+    border: Option<BorderStyle>,
+    zebra: Option<StyleSpec>,
+    aligns: Vec<Align>,
+    frozen: usize,
+    offset: usize,
+    max_width: Option<usize>,
+    rules: Vec<(usize, ColumnRule)>,
+    footers: Vec<(StyledString, Vec<(usize, Aggregate)>)>,
+    footer_spec: StyleSpec,
+}
+
+impl Default for Table {
+    fn default() -> Self {
+        Table {
+            header: None,
+            rows: Vec::new(),
+            header_spec: StyleSpec::new().font(Style::Bold),
+            border: None,
+            zebra: None,
+            aligns: Vec::new(),
+            frozen: 0,
+            offset: 0,
+            max_width: None,
+            rules: Vec::new(),
+            footers: Vec::new(),
+            footer_spec: StyleSpec::new().font(Style::Bold),
+        }
+    }
+}
+
+impl Table {
+    /// ## Creates an empty table without a header or borders.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// ## Sets the header row.
+    ///
+    /// ### Arguments
+    ///
+    /// * `cells` - The column titles, `CLW` values, strings or [`Cell`]s
+    pub fn header<I, C>(mut self, cells: I) -> Self
+    where
+        I: IntoIterator<Item = C>,
+        C: Into<Cell>,
+    {
+        self.header = Some(cells.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// ## Adds a row.
+    ///
+    /// Rows may have fewer cells than others; the missing ones are left empty.
+    ///
+    /// ### Arguments
+    ///
+    /// * `cells` - The cells of the row, `CLW` values, strings or [`Cell`]s
+    pub fn row<I, C>(mut self, cells: I) -> Self
+    where
+        I: IntoIterator<Item = C>,
+        C: Into<Cell>,
+    {
+        self.rows.push(cells.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// ## Sets the style of the header row, bold by default.
+    pub fn header_style(mut self, spec: StyleSpec) -> Self {
+        self.header_spec = spec;
+        self
+    }
+
+    /// ## Draws borders around the table and between its cells.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{panel::BorderStyle, table::Table, PlainRenderer};
+    ///
+    /// let table = Table::new().header(["a", "b"]).row(["1", "2"]).border(BorderStyle::Ascii);
+    ///
+    /// assert_eq!(
+    ///     table.render_with(&PlainRenderer),
+    ///     "+---+---+\n\
+    ///      | a | b |\n\
+    ///      +---+---+\n\
+    ///      | 1 | 2 |\n\
+    ///      +---+---+"
+    /// );
+    /// ```
+    pub fn border(mut self, border: BorderStyle) -> Self {
+        self.border = Some(border);
+        self
+    }
+
+    /// ## Styles every other row, starting with the second, for readability.
+    ///
+    /// ### Arguments
+    ///
+    /// * `spec` - The style of the striped rows, usually a background
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{table::Table, Color, StyleSpec, TestRenderer};
+    ///
+    /// let table = Table::new()
+    ///     .row(["a"])
+    ///     .row(["b"])
+    ///     .zebra(StyleSpec::new().bg(Color::BrightBlack));
+    ///
+    /// assert_eq!(table.render_with(&TestRenderer), "a\n[bg=BrightBlack]b[/]");
+    /// ```
+    pub fn zebra(mut self, spec: StyleSpec) -> Self {
+        self.zebra = Some(spec);
+        self
+    }
+
+    /// ## Sets the alignment of a column, left by default.
+    ///
+    /// ### Arguments
+    ///
+    /// * `column` - The index of the column, from 0
+    /// * `align` - Where the cells sit in the column
+    pub fn align(mut self, column: usize, align: Align) -> Self {
+        if self.aligns.len() <= column {
+            self.aligns.resize(column + 1, Align::Left);
+        }
+        self.aligns[column] = align;
+        self
+    }
+
+    /// ## Adds a footer row that sums up columns of the body, such as a totals row.
+    ///
+    /// Footer rows are drawn after a separator, in the footer style. Numbers are read from
+    /// the cells like [`ColumnRule`] does, and cells that aren't numbers are left out, except
+    /// by [`Aggregate::Count`].
+    ///
+    /// ### Arguments
+    ///
+    /// * `label` - The text of the first column, unless it is summed up itself
+    /// * `columns` - The columns to sum up, each with how
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{table::{Aggregate, Table}, with_capabilities, Align, Capabilities, PlainRenderer};
+    ///
+    /// let table = Table::new()
+    ///     .header(["crate", "downloads", "size"])
+    ///     .row(["clwind", "1,200", "0.5"])
+    ///     .row(["serde", "9000", "1.25"])
+    ///     .align(1, Align::Right)
+    ///     .footer("total", [(1, Aggregate::Sum), (2, Aggregate::Sum)])
+    ///     .footer("mean", [(1, Aggregate::Mean), (2, Aggregate::Mean)]);
+    ///
+    /// // Without a border, the separator is drawn like one, in ASCII if Unicode isn't supported.
+    /// let unicode = Capabilities::from_env(|_| None, false).unicode(true);
+    ///
+    /// assert_eq!(
+    ///     with_capabilities(unicode, || table.render_with(&PlainRenderer)),
+    ///     "crate   downloads  size\n\
+    ///      clwind      1,200  0.5\n\
+    ///      serde        9000  1.25\n\
+    ///      ──────  ─────────  ─────\n\
+    ///      total       10200  1.75\n\
+    ///      mean         5100  0.875"
+    /// );
+    /// ```
+    pub fn footer<L, I>(mut self, label: L, columns: I) -> Self
+    where
+        L: Into<StyledString>,
+        I: IntoIterator<Item = (usize, Aggregate)>,
+    {
+        self.footers
+            .push((label.into(), columns.into_iter().collect()));
+        self
+    }
+
+    /// ## Sets the style of the footer rows, bold by default.
+    pub fn footer_style(mut self, spec: StyleSpec) -> Self {
+        self.footer_spec = spec;
+        self
+    }
+
+    /// ## Adds a rule that styles the body cells of a column that meet a condition.
+    ///
+    /// Every rule of a column is checked against the plain text of its cells. The styles of
+    /// the rules that match are combined, later rules winning, and drawn over the row style,
+    /// while colors set on the cell itself still win.
+    ///
+    /// ### Arguments
+    ///
+    /// * `column` - The index of the column, from 0
+    /// * `rule` - The condition and the style of the cells that meet it
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{table::{ColumnRule, Table}, Color, Style, StyleSpec, TestRenderer};
+    ///
+    /// let table = Table::new()
+    ///     .row(["build", "ok", "80"])
+    ///     .row(["test", "FAILED", "1,250"])
+    ///     .rule(1, ColumnRule::equals("FAILED", StyleSpec::new().text(Color::Red)))
+    ///     .rule(2, ColumnRule::gt(1000.0, StyleSpec::new().font(Style::Bold)));
+    ///
+    /// assert_eq!(
+    ///     table.render_with(&TestRenderer),
+    ///     "build  ok      80\n\
+    ///      test   [fg=Red]FAILED[/]  [Bold]1,250[/]"
+    /// );
+    /// ```
+    pub fn rule(mut self, column: usize, rule: ColumnRule) -> Self {
+        self.rules.push((column, rule));
+        self
+    }
+
+    /// ## Keeps the first columns in view when the table is scrolled or split into pages.
+    ///
+    /// ### Arguments
+    ///
+    /// * `columns` - How many columns, from the left, stay in view
+    pub fn freeze(mut self, columns: usize) -> Self {
+        self.frozen = columns;
+        self
+    }
+
+    /// ## Scrolls the columns after the frozen ones to the left.
+    ///
+    /// ### Arguments
+    ///
+    /// * `columns` - How many of the columns after the frozen ones to leave out
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{table::Table, PlainRenderer};
+    ///
+    /// let table = Table::new()
+    ///     .header(["host", "cpu", "mem", "disk"])
+    ///     .row(["web-1", "12%", "40%", "71%"])
+    ///     .freeze(1)
+    ///     .scroll(1);
+    ///
+    /// assert_eq!(
+    ///     table.render_with(&PlainRenderer),
+    ///     "host   mem  disk\n\
+    ///      web-1  40%  71%"
+    /// );
+    /// ```
+    pub fn scroll(mut self, columns: usize) -> Self {
+        self.offset = columns;
+        self
+    }
+
+    /// ## Leaves out the columns on the right that don't fit in a width.
+    ///
+    /// The frozen columns and the first column after them are always shown, even if they
+    /// are wider. Without a width, every column is shown.
+    ///
+    /// ### Arguments
+    ///
+    /// * `width` - The number of terminal columns available, such as `terminal_width()`
+    pub fn max_width(mut self, width: usize) -> Self {
+        self.max_width = Some(width);
+        self
+    }
+
+    /// ## Renders the table with ANSI escape sequences.
+    pub fn render(&self) -> String {
+        self.render_with(&AnsiRenderer)
+    }
+
+    /// ## Renders the table through the given renderer.
+    ///
+    /// ### Arguments
+    ///
+    /// * `renderer` - The backend used to style the cells
+    pub fn render_with(&self, renderer: &dyn Renderer) -> String {
+        let grid = self.layout();
+        let visible = self.visible(&grid.widths, self.offset, self.max_width);
+        self.render_columns(renderer, &grid, &visible)
+    }
+
+    /// ## Splits a wide table into pages of columns that fit in a width.
+    ///
+    /// Every page repeats the frozen columns, followed by as many of the other columns as
+    /// fit, starting from the scroll offset.
+    ///
+    /// ### Arguments
+    ///
+    /// * `width` - The number of terminal columns available, such as `terminal_width()`
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{table::Table, PlainRenderer};
+    ///
+    /// let table = Table::new()
+    ///     .header(["host", "cpu", "mem", "disk"])
+    ///     .row(["web-1", "12%", "40%", "71%"])
+    ///     .freeze(1);
+    ///
+    /// assert_eq!(
+    ///     table.pages_with(&PlainRenderer, 16),
+    ///     ["host   cpu  mem\nweb-1  12%  40%", "host   disk\nweb-1  71%"]
+    /// );
+    /// ```
+    pub fn pages_with(&self, renderer: &dyn Renderer, width: usize) -> Vec<String> {
+        let grid = self.layout();
+        let frozen = self.frozen.min(grid.widths.len());
+        let mut pages = Vec::new();
+        let mut offset = self.offset;
+        loop {
+            let visible = self.visible(&grid.widths, offset, Some(width));
+            pages.push(self.render_columns(renderer, &grid, &visible));
+            offset += visible.len() - frozen;
+            if visible.len() == frozen || frozen + offset >= grid.widths.len() {
+                return pages;
+            }
+        }
+    }
+
+    /// ## Splits a wide table into pages with ANSI escape sequences.
+    ///
+    /// ### Arguments
+    ///
+    /// * `width` - The number of terminal columns available, such as `terminal_width()`
+    pub fn pages(&self, width: usize) -> Vec<String> {
+        self.pages_with(&AnsiRenderer, width)
+    }
+
+    /// ## Exports the table as CSV, with the plain text of every cell.
+    ///
+    /// Every row, footer rows included, has a field per column. Spanned cells fill their
+    /// first column, leaving the others empty, and fields are quoted when needed.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{clw, table::Table};
+    ///
+    /// let table = Table::new()
+    ///     .header(["name", "note"])
+    ///     .row([clw("clwind").text_cyan(), clw("colors, \"tables\"")]);
+    ///
+    /// assert_eq!(table.to_csv(), "name,note\nclwind,\"colors, \"\"tables\"\"\"\n");
+    /// ```
+    pub fn to_csv(&self) -> String {
+        let grid = self.layout();
+        let mut csv = String::new();
+        for row in 0..grid.rows.len() {
+            let fields: Vec<String> = (0..grid.widths.len())
+                .map(|column| {
+                    let field = grid.plain(row, column);
+                    match field.contains([',', '"', '\n', '\r']) {
+                        true => format!("\"{}\"", field.replace('"', "\"\"")),
+                        false => field,
+                    }
+                })
+                .collect();
+            csv.push_str(&fields.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+
+    /// ## Exports the table as a Markdown table, with the plain text of every cell.
+    ///
+    /// Column alignments are kept. Markdown tables need a header, so one with empty titles
+    /// is written if the table has none, and footer rows follow the body.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{table::Table, Align};
+    ///
+    /// let table = Table::new()
+    ///     .header(["crate", "downloads"])
+    ///     .row(["clwind", "1200"])
+    ///     .align(1, Align::Right);
+    ///
+    /// assert_eq!(
+    ///     table.to_markdown(),
+    ///     "| crate | downloads |\n\
+    ///      | --- | ---: |\n\
+    ///      | clwind | 1200 |\n"
+    /// );
+    /// ```
+    pub fn to_markdown(&self) -> String {
+        let grid = self.layout();
+        let columns = 0..grid.widths.len();
+        let line = |cells: Vec<String>| format!("| {} |\n", cells.join(" | "));
+        let escape = |row: usize, column: usize| grid.plain(row, column).replace('|', "\\|");
+
+        let mut rows = 0..grid.rows.len();
+        let mut markdown = match self.header {
+            Some(_) => rows.next().map_or_else(String::new, |row| {
+                line(columns.clone().map(|column| escape(row, column)).collect())
+            }),
+            None => line(columns.clone().map(|_| String::new()).collect()),
+        };
+        markdown.push_str(&line(
+            columns
+                .clone()
+                .map(
+                    |column| match self.aligns.get(column).copied().unwrap_or_default() {
+                        Align::Left => "---".to_string(),
+                        Align::Right => "---:".to_string(),
+                        Align::Center => ":---:".to_string(),
+                    },
+                )
+                .collect(),
+        ));
+        for row in rows {
+            markdown.push_str(&line(
+                columns.clone().map(|column| escape(row, column)).collect(),
+            ));
+        }
+        markdown
+    }
+
+    /// ## Exports the table as an HTML `<table>`, with the styles of the cells kept.
+    ///
+    /// The header and footer rows go in `<thead>` and `<tfoot>`, spanned cells get `colspan`
+    /// and `rowspan` attributes, and the rules of the columns are applied.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{clw, table::{Cell, Table}};
+    ///
+    /// let table = Table::new()
+    ///     .header([Cell::new("build").colspan(2)])
+    ///     .row([clw("ok").text_green(), clw("12s")]);
+    ///
+    /// assert_eq!(
+    ///     table.to_html(),
+    ///     "<table>\n\
+    ///      <thead>\n<tr><th colspan=\"2\">build</th></tr>\n</thead>\n\
+    ///      <tbody>\n<tr><td><span style=\"color:#00cd00\">ok</span></td><td>12s</td></tr>\n</tbody>\n\
+    ///      </table>"
+    /// );
+    /// ```
+    pub fn to_html(&self) -> String {
+        let grid = self.layout();
+        let body = self.header.iter().count()..grid.footer;
+        let sections = [
+            ("thead", "th", 0..body.start),
+            ("tbody", "td", body.clone()),
+            ("tfoot", "td", grid.footer..grid.rows.len()),
+        ];
+
+        let mut html = String::from("<table>\n");
+        for (section, tag, rows) in sections {
+            if rows.is_empty() {
+                continue;
+            }
+
+            writeln!(html, "<{}>", section).expect("writing to a String cannot fail");
+            for row in rows {
+                html.push_str("<tr>");
+                for column in 0..grid.widths.len() {
+                    let Some((cell, colspan, rowspan)) = grid.start(row, column) else {
+                        continue;
+                    };
+                    write!(html, "<{}", tag).expect("writing to a String cannot fail");
+                    for (name, span) in [("colspan", colspan), ("rowspan", rowspan)] {
+                        if span > 1 {
+                            write!(html, " {}=\"{}\"", name, span)
+                                .expect("writing to a String cannot fail");
+                        }
+                    }
+                    match self.aligns.get(column).copied().unwrap_or_default() {
+                        Align::Left => {}
+                        Align::Right => html.push_str(" style=\"text-align:right\""),
+                        Align::Center => html.push_str(" style=\"text-align:center\""),
+                    }
+                    html.push('>');
+
+                    let spec = match body.contains(&row) {
+                        true => self.rule_spec(column, &cell.content.plain()),
+                        false => StyleSpec::new(),
+                    };
+                    for segment in cell.content.segments() {
+                        let mut segment = segment.clone();
+                        segment.spec = segment.spec.over(&spec);
+                        html.push_str(&segment.render_with(&HtmlRenderer));
+                    }
+                    write!(html, "</{}>", tag).expect("writing to a String cannot fail");
+                }
+                html.push_str("</tr>\n");
+            }
+            writeln!(html, "</{}>", section).expect("writing to a String cannot fail");
+        }
+        html.push_str("</table>");
+        html
+    }
+
+    /// ## Combines the styles of the rules of a column that a cell meets.
+    fn rule_spec(&self, column: usize, text: &str) -> StyleSpec {
+        self.rules
+            .iter()
+            .filter(|(ruled, rule)| *ruled == column && (rule.test)(text))
+            .fold(StyleSpec::new(), |spec, (_, rule)| {
+                rule.spec.clone().over(&spec)
+            })
+    }
+
+    /// ## Places the cells of the rows on a grid, and measures every column.
+    fn layout(&self) -> Grid<'_> {
+        let mut grid = Grid {
+            cells: Vec::new(),
+            rows: Vec::new(),
+            widths: Vec::new(),
+            footer: 0,
+        };
+        // Per column, the cell spanning down into the next rows and how many rows it has left.
+        let mut below: Vec<(usize, usize)> = Vec::new();
+        let header = self
+            .header
+            .iter()
+            .map(|cells| (cells, self.header_spec.clone(), 1));
+        let body = self.rows.iter().enumerate().map(|(i, cells)| {
+            let spec = match (&self.zebra, i % 2) {
+                (Some(zebra), 1) => zebra.clone(),
+                _ => StyleSpec::new(),
+            };
+            (cells, spec, self.rows.len() - i)
+        });
+        for (cells, spec, max_rows) in header.chain(body) {
+            let mut slots = Vec::new();
+            let mut cells = cells.iter();
+            loop {
+                let column = slots.len();
+                if let Some((id, left)) = below.get_mut(column).filter(|(_, left)| *left > 0) {
+                    *left -= 1;
+                    slots.push(Some(*id));
+                } else if let Some(cell) = cells.next() {
+                    let id = grid.cells.len();
+                    grid.cells
+                        .push((Cow::Borrowed(cell), grid.rows.len(), column));
+                    for column in column..column + cell.colspan {
+                        if below.len() <= column {
+                            below.resize(column + 1, (0, 0));
+                        }
+                        below[column] = (id, cell.rowspan.min(max_rows) - 1);
+                        slots.push(Some(id));
+                    }
+                } else if below[column..].iter().any(|(_, left)| *left > 0) {
+                    slots.push(None);
+                } else {
+                    break;
+                }
+            }
+            grid.rows.push((slots, spec));
+        }
+
+        grid.footer = grid.rows.len();
+        let body = self.header.iter().count()..grid.footer;
+        for (label, aggregates) in &self.footers {
+            let columns = grid.rows.iter().map(|(slots, _)| slots.len());
+            let summed = aggregates.iter().map(|(column, _)| column + 1);
+            let mut slots = Vec::new();
+            for column in 0..columns.chain(summed).max().unwrap_or(0).max(1) {
+                let aggregate = aggregates.iter().rev().find(|(c, _)| *c == column);
+                let content = match (aggregate, column) {
+                    (Some(&(_, aggregate)), _) => {
+                        let cells = grid.cells.iter().filter(|(cell, row, c)| {
+                            *c == column && cell.colspan == 1 && body.contains(row)
+                        });
+                        aggregate
+                            .apply(cells.map(|(cell, ..)| cell.content.plain()))
+                            .into()
+                    }
+                    (None, 0) => label.clone(),
+                    (None, _) => StyledString::new(),
+                };
+                slots.push(Some(grid.cells.len()));
+                grid.cells
+                    .push((Cow::Owned(Cell::new(content)), grid.rows.len(), column));
+            }
+            grid.rows.push((slots, self.footer_spec.clone()));
+        }
+
+        let columns = grid
+            .rows
+            .iter()
+            .map(|(slots, _)| slots.len())
+            .max()
+            .unwrap_or(0);
+        grid.widths = vec![0; columns];
+        let gap = self.gap();
+        let mut cells: Vec<_> = grid.cells.iter().collect();
+        cells.sort_by_key(|(cell, ..)| cell.colspan);
+        for (cell, _, column) in cells {
+            let (cell, column) = (&**cell, *column);
+            // A spanned cell widens the last of its columns, if the ones before are too narrow.
+            let span = &mut grid.widths[column..column + cell.colspan];
+            let width = text_width(&cell.content.plain());
+            let room = span.iter().sum::<usize>() + (cell.colspan - 1) * gap;
+            if let (Some(last), true) = (span.last_mut(), width > room) {
+                *last += width - room;
+            }
+        }
+        grid
+    }
+
+    /// ## Returns the columns between two cells: a border and padding, or two spaces.
+    fn gap(&self) -> usize {
+        match self.border {
+            Some(_) => 3,
+            None => 2,
+        }
+    }
+
+    /// ## Picks the columns shown: the frozen ones, then the others from an offset.
+    fn visible(&self, widths: &[usize], offset: usize, limit: Option<usize>) -> Vec<usize> {
+        let frozen = self.frozen.min(widths.len());
+        let mut visible: Vec<usize> = (0..frozen).collect();
+        // Without a border, the gap after the last column isn't drawn.
+        let (edges, slack) = match self.border {
+            Some(_) => (1, 0),
+            None => (0, 2),
+        };
+        let gap = self.gap();
+        let mut used = edges + widths[..frozen].iter().map(|w| w + gap).sum::<usize>();
+        for (column, width) in widths.iter().enumerate().skip(frozen + offset) {
+            used += width + gap;
+            let fits = limit.is_none_or(|limit| used <= limit + slack);
+            if !fits && visible.len() > frozen {
+                break;
+            }
+            visible.push(column);
+        }
+        visible
+    }
+
+    /// ## Renders the given columns of the grid.
+    fn render_columns(&self, renderer: &dyn Renderer, grid: &Grid, visible: &[usize]) -> String {
+        let gap = self.gap();
+        let mut out = Vec::new();
+        let border = self.border.map(|b| (b.chars(), b.joints()));
+        // Draws a rule between two rows, with joints where either of them has a border.
+        let rule = |above: Option<usize>, below: Option<usize>, left: &str, right: &str| {
+            let Some(((_, horizontal, _), [down, up, .., cross])) = border else {
+                return String::new();
+            };
+            let mut line = String::from(left);
+            for (position, &column) in visible.iter().enumerate() {
+                line.push_str(&horizontal.repeat(grid.widths[column] + 2));
+                if let Some(&next) = visible.get(position + 1) {
+                    let split =
+                        |row: Option<usize>| row.is_some_and(|row| grid.splits(row, column, next));
+                    line.push_str(match (split(above), split(below)) {
+                        (true, true) => cross,
+                        (true, false) => up,
+                        (false, true) => down,
+                        (false, false) => horizontal,
+                    });
+                }
+            }
+            line.push_str(right);
+            line
+        };
+        if let Some((([top_left, top_right, ..], ..), _)) = border {
+            out.push(rule(None, Some(0), top_left, top_right));
+        }
+
+        for (i, (slots, row_spec)) in grid.rows.iter().enumerate() {
+            let fill = |columns: usize, spec: &StyleSpec| match columns {
+                0 => String::new(),
+                n => renderer.paint(&" ".repeat(n), spec),
+            };
+            let (inset, separator) = match border {
+                Some(((_, _, vertical), _)) => (1, vertical),
+                None => (0, ""),
+            };
+
+            // Neighbouring columns showing the same cell are drawn as one.
+            let mut groups: Vec<(Option<usize>, Vec<usize>)> = Vec::new();
+            for &column in visible {
+                let id = slots.get(column).copied().flatten();
+                match groups.last_mut() {
+                    Some((last, columns)) if id.is_some() && *last == id => columns.push(column),
+                    _ => groups.push((id, vec![column])),
+                }
+            }
+
+            let blank = StyledString::new();
+            let mut line = String::from(separator);
+            for (position, (id, columns)) in groups.iter().enumerate() {
+                let (content, column) = match id.map(|id| &grid.cells[id]) {
+                    Some((cell, row, column)) if *row == i => (&cell.content, *column),
+                    _ => (&blank, columns[0]),
+                };
+                let body = (i > 0 || self.header.is_none()) && i < grid.footer;
+                let spec = match body {
+                    true => self.rule_spec(column, &content.plain()).over(row_spec),
+                    false => row_spec.clone(),
+                };
+                let spec = &spec;
+                let width = columns.iter().map(|&c| grid.widths[c]).sum::<usize>()
+                    + (columns.len() - 1) * gap;
+                let content = fit(content, width);
+                let extra = width - text_width(&content.plain());
+                let (before, mut after) = match self.aligns.get(column).copied().unwrap_or_default()
+                {
+                    Align::Left => (0, extra),
+                    Align::Right => (extra, 0),
+                    Align::Center => (extra / 2, extra - extra / 2),
+                };
+                let last = position + 1 == groups.len();
+                if border.is_none() && last && spec.bg.is_none() {
+                    after = 0;
+                }
+
+                if position > 0 && border.is_none() {
+                    line.push_str(&fill(2, row_spec));
+                }
+                line.push_str(&fill(inset + before, spec));
+                for segment in content.segments() {
+                    line.push_str(&paint_over(renderer, segment, spec));
+                }
+                line.push_str(&fill(after + inset, spec));
+                line.push_str(separator);
+            }
+            out.push(line);
+
+            let next = Some(i + 1).filter(|&next| next < grid.rows.len());
+            let header = i == 0 && self.header.is_some();
+            let footer = i + 1 == grid.footer && next.is_some();
+            match border {
+                Some(((_, _, _), [_, _, opening, closing, _])) if header || footer => {
+                    out.push(rule(Some(i), next, opening, closing));
+                }
+                None if footer => {
+                    let (_, horizontal, _) = BorderStyle::detect().chars();
+                    let parts: Vec<String> = visible
+                        .iter()
+                        .map(|&c| horizontal.repeat(grid.widths[c]))
+                        .collect();
+                    out.push(parts.join("  "));
+                }
+                _ => {}
+            }
+        }
+
+        if let Some((([.., bottom_right, bottom_left], ..), _)) = border {
+            out.push(rule(
+                grid.rows.len().checked_sub(1),
+                None,
+                bottom_left,
+                bottom_right,
+            ));
+        }
+        out.join("\n")
+    }
+}
+
+/// ## Cuts a cell to a width with an ellipsis, for spanned cells whose columns are scrolled away.
+fn fit(content: &StyledString, width: usize) -> Cow<'_, StyledString> {
+    if text_width(&content.plain()) <= width {
+        return Cow::Borrowed(content);
+    }
+
+    let mut fitted = StyledString::new();
+    let mut room = width;
+    for segment in content.segments() {
+        let columns = text_width(segment.plain());
+        if columns >= room {
+            fitted.push(segment.clone().truncate(room));
+            break;
+        }
+        fitted.push(segment.clone());
+        room -= columns;
+    }
+    Cow::Owned(fitted)
+}
+
+/// The cells of a table placed on a grid, where the columns and rows a cell spans point at it.
+struct Grid<'a> {
+    /// Every cell, with the row and column it starts at.
+    cells: Vec<(Cow<'a, Cell>, usize, usize)>,
+    /// Every row, with the cell in each column, if any, and its style.
+    rows: Vec<(Vec<Option<usize>>, StyleSpec)>,
+    widths: Vec<usize>,
+    /// The first of the footer rows.
+    footer: usize,
+}
+
+impl Grid<'_> {
+    /// ## Returns the cell starting at a row and column, with the columns and rows it spans.
+    fn start(&self, row: usize, column: usize) -> Option<(&Cell, usize, usize)> {
+        let slot = |row: usize, column: usize| self.rows[row].0.get(column).copied().flatten();
+        let id = slot(row, column)?;
+        let (cell, start_row, start_column) = &self.cells[id];
+        if (*start_row, *start_column) != (row, column) {
+            return None;
+        }
+
+        let colspan = (column..self.widths.len())
+            .take_while(|&c| slot(row, c) == Some(id))
+            .count();
+        let rowspan = (row..self.rows.len())
+            .take_while(|&r| slot(r, column) == Some(id))
+            .count();
+        Some((cell, colspan, rowspan))
+    }
+
+    /// ## Returns the plain text of the cell starting at a row and column, or an empty one.
+    fn plain(&self, row: usize, column: usize) -> String {
+        self.start(row, column)
+            .map_or_else(String::new, |(cell, ..)| cell.content.plain())
+    }
+
+    /// ## Tells whether a row has a border between two columns, as they show different cells.
+    fn splits(&self, row: usize, left: usize, right: usize) -> bool {
+        let slots = &self.rows[row].0;
+        match (
+            slots.get(left).copied().flatten(),
+            slots.get(right).copied().flatten(),
+        ) {
+            (Some(left), Some(right)) => left != right,
+            _ => true,
+        }
+    }
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render())
+    }
+}
+
+/// A table cell, which can span several columns or rows.
+///
+/// Strings, `CLW` values and styled strings convert into cells that span one column and one
+/// row, so rows only need a `Cell` for the spanned ones.
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{panel::BorderStyle, table::{Cell, Table}, Align, PlainRenderer};
+///
+/// let table = Table::new()
+///     .header(["region", "q1", "q2"])
+///     .row([Cell::new("Europe").colspan(3)])
+///     .row([Cell::new("west").rowspan(2), "10".into(), "12".into()])
+///     .row(["11", "13"])
+///     .row([Cell::new("total").colspan(2), "46".into()])
+///     .align(2, Align::Right)
+///     .border(BorderStyle::Ascii);
+///
+/// assert_eq!(
+///     table.render_with(&PlainRenderer),
+///     "+--------+----+----+\n\
+///      | region | q1 | q2 |\n\
+///      +--------+----+----+\n\
+///      | Europe           |\n\
+///      | west   | 10 | 12 |\n\
+///      |        | 11 | 13 |\n\
+///      | total       | 46 |\n\
+///      +-------------+----+"
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct Cell {
+    content: StyledString,
+    colspan: usize,
+    rowspan: usize,
+}
+
+impl Cell {
+    /// ## Creates a cell that spans one column and one row.
+    ///
+    /// ### Arguments
+    ///
+    /// * `content` - The text of the cell, a `CLW` value, string or styled string
+    pub fn new(content: impl Into<StyledString>) -> Self {
+        Cell {
+            content: content.into(),
+            colspan: 1,
+            rowspan: 1,
+        }
+    }
+
+    /// ## Makes the cell span several columns, drawn without borders between them.
+    ///
+    /// ### Arguments
+    ///
+    /// * `columns` - The number of columns, at least one
+    pub fn colspan(mut self, columns: usize) -> Self {
+        self.colspan = columns.max(1);
+        self
+    }
+
+    /// ## Makes the cell span several rows, leaving its columns empty in the rows below.
+    ///
+    /// The rows below skip the spanned columns, so their cells fill the columns after them.
+    /// Header cells span only the header row.
+    ///
+    /// ### Arguments
+    ///
+    /// * `rows` - The number of rows, at least one
+    pub fn rowspan(mut self, rows: usize) -> Self {
+        self.rowspan = rows.max(1);
+        self
+    }
+}
+
+impl<T: Into<StyledString>> From<T> for Cell {
+    fn from(content: T) -> Self {
+        Cell::new(content)
+    }
+}
+
+/// A condition on the cells of a column, and the style of the cells that meet it.
+///
+/// Rules are added to a column with [`Table::rule`], so conditional formatting is declared
+/// once instead of styling every cell. Numeric rules read cells such as `1,250`, `3.5` or
+/// `12%`, and never match cells that aren't numbers.
+pub struct ColumnRule {
+    test: Box<dyn Fn(&str) -> bool + Send + Sync>,
+    spec: StyleSpec,
+}
+
+impl ColumnRule {
+    /// ## Styles the cells holding a number greater than a value.
+    ///
+    /// ### Arguments
+    ///
+    /// * `value` - The number the cells must exceed
+    /// * `spec` - The style of the cells that do
+    pub fn gt(value: f64, spec: StyleSpec) -> Self {
+        Self::number(move |number| number > value, spec)
+    }
+
+    /// ## Styles the cells holding a number less than a value.
+    ///
+    /// ### Arguments
+    ///
+    /// * `value` - The number the cells must stay under
+    /// * `spec` - The style of the cells that do
+    pub fn lt(value: f64, spec: StyleSpec) -> Self {
+        Self::number(move |number| number < value, spec)
+    }
+
+    /// ## Styles the cells holding a number within a range, both ends included.
+    ///
+    /// ### Arguments
+    ///
+    /// * `min` - The lowest number of the range
+    /// * `max` - The highest number of the range
+    /// * `spec` - The style of the cells in the range
+    pub fn between(min: f64, max: f64, spec: StyleSpec) -> Self {
+        Self::number(move |number| (min..=max).contains(&number), spec)
+    }
+
+    /// ## Styles the cells whose text is exactly the given one.
+    ///
+    /// ### Arguments
+    ///
+    /// * `text` - The text of the cells, compared without surrounding spaces
+    /// * `spec` - The style of the cells that match
+    pub fn equals(text: impl Into<String>, spec: StyleSpec) -> Self {
+        let text = text.into();
+        Self::when(move |cell| cell.trim() == text, spec)
+    }
+
+    /// ## Styles the cells whose text contains the given one.
+    ///
+    /// ### Arguments
+    ///
+    /// * `text` - The text to look for
+    /// * `spec` - The style of the cells that contain it
+    pub fn contains(text: impl Into<String>, spec: StyleSpec) -> Self {
+        let text = text.into();
+        Self::when(move |cell| cell.contains(&text), spec)
+    }
+
+    /// ## Styles the cells whose whole text matches a wildcard pattern.
+    ///
+    /// `*` matches any run of characters, and `?` any single character.
+    ///
+    /// ### Arguments
+    ///
+    /// * `pattern` - The pattern, such as `ERR*` or `v?.*`
+    /// * `spec` - The style of the cells that match
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{table::{ColumnRule, Table}, Color, StyleSpec, TestRenderer};
+    ///
+    /// let table = Table::new()
+    ///     .row(["ERR_TIMEOUT"])
+    ///     .row(["WARN_SLOW"])
+    ///     .rule(0, ColumnRule::glob("ERR_*", StyleSpec::new().text(Color::Red)));
+    ///
+    /// assert_eq!(table.render_with(&TestRenderer), "[fg=Red]ERR_TIMEOUT[/]\nWARN_SLOW");
+    /// ```
+    pub fn glob(pattern: &str, spec: StyleSpec) -> Self {
+        let pattern: Vec<char> = pattern.chars().collect();
+        Self::when(move |cell| glob_match(&pattern, cell), spec)
+    }
+
+    /// ## Styles the cells for which a function returns `true`.
+    ///
+    /// This takes any condition on the plain text of a cell, such as a regular expression
+    /// from the `regex` crate.
+    ///
+    /// ### Arguments
+    ///
+    /// * `test` - The condition, given the plain text of a cell
+    /// * `spec` - The style of the cells that meet it
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{table::ColumnRule, Style, StyleSpec};
+    ///
+    /// let hashes = ColumnRule::when(
+    ///     |cell| cell.len() == 7 && cell.chars().all(|c| c.is_ascii_hexdigit()),
+    ///     StyleSpec::new().font(Style::Dim),
+    /// );
+    /// ```
+    pub fn when<F>(test: F, spec: StyleSpec) -> Self
+    where
+        F: Fn(&str) -> bool + Send + Sync + 'static,
+    {
+        ColumnRule {
+            test: Box::new(test),
+            spec,
+        }
+    }
+
+    /// ## Styles the cells holding a number that meets a condition.
+    fn number<F>(test: F, spec: StyleSpec) -> Self
+    where
+        F: Fn(f64) -> bool + Send + Sync + 'static,
+    {
+        Self::when(move |cell| parse_number(cell).is_some_and(&test), spec)
+    }
+}
+
+/// ## Reads a cell as a number, allowing `,` or `_` between digits and a trailing `%`.
+fn parse_number(text: &str) -> Option<f64> {
+    let text = text.trim();
+    let text = text.strip_suffix('%').unwrap_or(text);
+    text.replace([',', '_'], "")
+        .parse()
+        .ok()
+        .filter(|number: &f64| number.is_finite())
+}
+
+/// ## Checks whether a whole text matches a pattern with `*` and `?` wildcards.
+fn glob_match(pattern: &[char], text: &str) -> bool {
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // The last `*` seen, and where in the text it started matching.
+    let mut star = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((star_p, star_t)) => {
+                    star = Some((star_p, star_t + 1));
+                    p = star_p + 1;
+                    t = star_t + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// How a footer cell sums up the cells of its column.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Aggregate {
+    /// The sum of the numbers.
+    Sum,
+    /// The mean of the numbers, left empty without any.
+    Mean,
+    /// The number of cells that aren't empty.
+    Count,
+    /// The lowest number, left empty without any.
+    Min,
+    /// The highest number, left empty without any.
+    Max,
+}
+
+impl Aggregate {
+    /// ## Sums up the cells of a column, with as many decimals as the numbers in them.
+    fn apply<I: Iterator<Item = String>>(self, cells: I) -> String {
+        let cells: Vec<String> = cells.collect();
+        let numbers: Vec<f64> = cells.iter().filter_map(|cell| parse_number(cell)).collect();
+        let decimals = cells
+            .iter()
+            .filter(|cell| parse_number(cell).is_some())
+            .filter_map(|cell| cell.trim().trim_end_matches('%').split_once('.'))
+            .map(|(_, fraction)| fraction.len())
+            .max()
+            .unwrap_or(0);
+        let value = match self {
+            Aggregate::Count => {
+                let filled = cells.iter().filter(|cell| !cell.trim().is_empty());
+                return filled.count().to_string();
+            }
+            Aggregate::Sum => numbers.iter().sum(),
+            _ if numbers.is_empty() => return String::new(),
+            Aggregate::Mean => numbers.iter().sum::<f64>() / numbers.len() as f64,
+            Aggregate::Min => numbers.iter().copied().fold(f64::INFINITY, f64::min),
+            Aggregate::Max => numbers.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        };
+        let formatted = format!("{:.*}", decimals, value);
+        // A mean can need more decimals than the numbers, such as 2.5 for 2 and 3.
+        match self == Aggregate::Mean && formatted.parse::<f64>() != Ok(value) {
+            true => value.to_string(),
+            false => formatted,
+        }
+    }
+}
@@ -0,0 +1,31 @@
+//! The Tailwind CSS color palette.
+
+use crate::Color;
+
+include!("data/tailwind.rs");
+
+impl Color {
+    /// ## Looks up a Tailwind CSS palette color, e.g. `red-500` or `slate-950`.
+    ///
+    /// ### Arguments
+    ///
+    /// * `name` - The color and shade, separated by `-`
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{clw, Color};
+    ///
+    /// assert_eq!(Color::tailwind("red-500"), Some(Color::Rgb(0xef, 0x44, 0x44)));
+    /// assert_eq!(Color::tailwind("red-550"), None);
+    ///
+    /// println!("{}", clw("Saved").text(Color::tailwind("emerald-400").unwrap()));
+    /// ```
+    pub fn tailwind(name: &str) -> Option<Color> {
+        let (color, shade) = name.rsplit_once('-')?;
+        let shade = SHADES.iter().position(|&s| shade.parse() == Ok(s))?;
+        let (_, values) = PALETTE.iter().find(|(c, _)| *c == color)?;
+        let hex = values[shade];
+        Some(Color::Rgb((hex >> 16) as u8, (hex >> 8) as u8, hex as u8))
+    }
+}
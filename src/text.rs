@@ -0,0 +1,224 @@
+//! Width-aware text layout helpers shared by `CLW` and the components.
+
+use std::borrow::Cow;
+
+use crate::ansi::{apply_sgr, sequence_end, strip_ansi, ESC};
+use crate::width::char_width;
+use crate::{text_width, ColorLevel, StyleSpec};
+
+/// The marker inserted where text was elided.
+pub(crate) const ELLIPSIS: &str = "…";
+
+/// ## Shortens a path or URL to `max` columns by eliding its middle.
+///
+/// The scheme and host of URLs, or the first component of paths, are kept
+/// along with as many trailing components as fit. Text that doesn't look like
+/// a path is elided in the middle character-wise.
+pub(crate) fn shorten_path(path: &str, max: usize) -> String {
+    if text_width(path) <= max {
+        return path.to_string();
+    }
+
+    let sep = match (path.contains('/'), path.contains('\\')) {
+        (true, _) => '/',
+        (false, true) => '\\',
+        (false, false) => return elide_middle(path, max),
+    };
+
+    let split = match path.find("://") {
+        Some(i) => path[i + 3..].find(sep).map(|j| i + 3 + j),
+        None => {
+            let start = path.len() - path.trim_start_matches(sep).len();
+            path[start..].find(sep).map(|j| start + j)
+        }
+    };
+    let Some(split) = split else {
+        return elide_middle(path, max);
+    };
+
+    let (head, rest) = path.split_at(split);
+    let budget = max.saturating_sub(text_width(head) + sep.len_utf8() + text_width(ELLIPSIS));
+    let mut tail = String::new();
+    for part in rest.split(sep).filter(|p| !p.is_empty()).rev() {
+        let candidate = format!("{}{}{}", sep, part, tail);
+        if text_width(&candidate) > budget {
+            break;
+        }
+        tail = candidate;
+    }
+
+    match tail.is_empty() {
+        true => elide_middle(path, max),
+        false => format!("{}{}{}{}", head, sep, ELLIPSIS, tail),
+    }
+}
+
+/// ## Shortens text to `max` columns, keeping its start and end around an ellipsis.
+pub(crate) fn elide_middle(text: &str, max: usize) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    if chars.len() <= max {
+        return text.to_string();
+    }
+
+    if max == 0 {
+        return String::new();
+    }
+
+    let keep = max - 1;
+    let right = keep / 2;
+    let left = keep - right;
+    let mut out: String = chars[..left].iter().collect();
+    out.push_str(ELLIPSIS);
+    out.extend(&chars[chars.len() - right..]);
+    out
+}
+
+/// ## Cuts text to `max` columns, ending it with an ellipsis.
+///
+/// Escape sequences are kept, including those after the cut, so styles and
+/// hyperlinks opened before it are still closed.
+pub(crate) fn truncate(text: &str, max: usize) -> String {
+    if text_width(text) <= max {
+        return text.to_string();
+    }
+
+    let budget = max.saturating_sub(text_width(ELLIPSIS));
+    let mut out = String::with_capacity(text.len());
+    let mut width = 0;
+    let mut cut = false;
+    let mut start = 0;
+    while let Some(c) = text[start..].chars().next() {
+        if c == ESC {
+            let end = sequence_end(text, start);
+            out.push_str(&text[start..end]);
+            start = end;
+            continue;
+        }
+
+        let columns = char_width(c);
+        if !cut && width + columns <= budget {
+            out.push(c);
+            width += columns;
+        } else if !cut {
+            cut = true;
+            if max > 0 {
+                out.push_str(ELLIPSIS);
+            }
+        }
+        start += c.len_utf8();
+    }
+    out
+}
+
+/// ## Greedily wraps plain text into lines of at most `width` columns.
+///
+/// Words longer than `width` are kept whole on their own line.
+pub(crate) fn wrap_words(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && text_width(&line) + 1 + text_width(word) > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// ## Wraps styled text to `width` columns, keeping each line's style to itself.
+///
+/// Lines are broken between words, and existing line breaks are kept. The
+/// style active where a line breaks is closed at the end of that line and
+/// opened again at the start of the next, so backgrounds don't leak past the
+/// text and colors don't vanish on the following lines.
+///
+/// ### Arguments
+///
+/// * `text` - The text to wrap, styled or not
+/// * `width` - The maximum number of columns of a line
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::wrap_ansi;
+///
+/// let lines = wrap_ansi("\x1b[41mdisk almost full\x1b[0m", 11);
+///
+/// assert_eq!(lines, ["\x1b[41mdisk almost\x1b[0m", "\x1b[41mfull\x1b[0m"]);
+/// ```
+pub fn wrap_ansi(text: &str, width: usize) -> Vec<String> {
+    let mut style = StyleSpec::new();
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        for line in wrap_words(paragraph, width) {
+            let mut out = String::with_capacity(line.len());
+            let codes = style.sgr(ColorLevel::TrueColor);
+            if !codes.is_empty() {
+                out.push_str(&format!("{}[{}m", ESC, codes));
+            }
+            out.push_str(&line);
+
+            let mut start = 0;
+            while let Some(i) = line[start..].find(ESC) {
+                let end = sequence_end(&line, start + i);
+                let sequence = &line[start + i..end];
+                if let Some(params) = sequence
+                    .strip_prefix("\x1b[")
+                    .and_then(|s| s.strip_suffix('m'))
+                {
+                    apply_sgr(&mut style, params);
+                }
+                start = end;
+            }
+            if !style.sgr(ColorLevel::TrueColor).is_empty() {
+                out.push_str("\x1b[0m");
+            }
+            lines.push(out);
+        }
+    }
+    lines
+}
+
+/// ## Returns the number of terminal columns a text occupies.
+///
+/// Escape sequences take no columns, East Asian wide characters and emoji
+/// take two, and combining marks, zero-width characters and the emoji joined
+/// to another by a zero-width joiner take none.
+///
+/// ### Arguments
+///
+/// * `text` - The text to measure, styled or not
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{clw, visible_width};
+///
+/// assert_eq!(visible_width(&clw("ok").text_green().font_bold().to_string()), 2);
+/// assert_eq!(visible_width("日本語"), 6);
+/// assert_eq!(visible_width("cafe\u{301}"), 4);
+/// assert_eq!(visible_width("👩\u{200d}💻 dev"), 6);
+/// ```
+pub fn visible_width(text: &str) -> usize {
+    let text = match text.contains(ESC) {
+        true => Cow::Owned(strip_ansi(text)),
+        false => Cow::Borrowed(text),
+    };
+
+    let mut width = 0;
+    let mut joined = false;
+    for c in text.chars() {
+        if !joined {
+            width += char_width(c);
+        }
+        joined = c == '\u{200d}';
+    }
+    width
+}
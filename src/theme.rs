@@ -0,0 +1,550 @@
+//! Semantic styles, so applications can re-skin messages in one place.
+//!
+//! [`CLW::style`] looks a style up by name in the process-wide theme set with
+//! [`set_theme`]. [`CLW::success`], [`CLW::error`], [`CLW::warning`] and
+//! [`CLW::info`] are shorthands for the built-in names.
+//!
+//! ```
+//! use clwind::{clw, theme::{self, Theme}, Color, StyleSpec};
+//!
+//! theme::set_theme(Theme::new().error(StyleSpec::new().text(Color::Magenta)));
+//!
+//! println!("{}", clw("build failed").error());
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{OnceLock, RwLock};
+
+use crate::{clw, text_width, AnsiRenderer, Color, ColorLevel, Renderer, Style, StyleSpec, CLW};
+
+/// Named styles for semantic messages, such as `"error"` or `"heading"`.
+///
+/// With the `serde` feature, themes can be loaded from configuration files,
+/// as a table of names to `text`, `bg`, `underline` and `font` entries.
+///
+/// ```
+/// # #[cfg(feature = "serde")]
+/// # {
+/// use clwind::{theme::Theme, Color, Style, StyleSpec};
+///
+/// let config = r##"
+///     [error]
+///     text = "#ff5555"
+///     font = ["bold"]
+///
+///     [path]
+///     text = "blue-400"
+/// "##;
+/// let theme = Theme::new().merge(toml::from_str(config).unwrap());
+///
+/// assert_eq!(theme.get("error"), Some(&StyleSpec::new().text(Color::Rgb(255, 85, 85)).font(Style::Bold)));
+/// assert!(theme.get("warning").is_some());
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct Theme {
+    styles: BTreeMap<String, StyleSpec>,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::empty()
+            .success(StyleSpec::new().text(Color::Green))
+            .error(StyleSpec::new().text(Color::BrightRed).font(Style::Bold))
+            .warning(StyleSpec::new().text(Color::Yellow))
+            .info(StyleSpec::new().text(Color::Cyan))
+            .style(
+                "heading",
+                StyleSpec::new().font(Style::Bold).font(Style::Underline),
+            )
+            .style("muted", StyleSpec::new().font(Style::Dim))
+            .style(
+                "code",
+                StyleSpec::new()
+                    .text(Color::BrightWhite)
+                    .bg(Color::BrightBlack),
+            )
+            .style(
+                "key",
+                StyleSpec::new().font(Style::Reverse).font(Style::Bold),
+            )
+    }
+}
+
+impl Theme {
+    /// ## Creates the default theme: green success, bold bright red errors,
+    /// yellow warnings, cyan info, bold underlined headings, dim muted text,
+    /// code on a gray background and keys in bold reverse video.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// ## Creates a theme without any styles.
+    pub fn empty() -> Self {
+        Theme {
+            styles: BTreeMap::new(),
+        }
+    }
+
+    /// ## Sets the style for a name, replacing the previous one.
+    ///
+    /// ### Arguments
+    ///
+    /// * `name` - The semantic name, e.g. `"heading"`
+    /// * `spec` - The style used for that name
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{theme::Theme, Color, StyleSpec};
+    ///
+    /// let theme = Theme::new().style("path", StyleSpec::new().text(Color::Blue));
+    ///
+    /// assert_eq!(theme.get("path"), Some(&StyleSpec::new().text(Color::Blue)));
+    /// ```
+    pub fn style<S: Into<String>>(mut self, name: S, spec: StyleSpec) -> Self {
+        self.styles.insert(name.into(), spec);
+        self
+    }
+
+    /// ## Adds the styles of another theme, replacing those with the same name.
+    ///
+    /// ### Arguments
+    ///
+    /// * `other` - The theme whose styles take precedence
+    pub fn merge(mut self, other: Theme) -> Self {
+        self.styles.extend(other.styles);
+        self
+    }
+
+    /// ## Returns the style for a name, if the theme has one.
+    pub fn get(&self, name: &str) -> Option<&StyleSpec> {
+        self.styles.get(name)
+    }
+
+    /// ## Sets the style of success messages, named `"success"`.
+    pub fn success(self, spec: StyleSpec) -> Self {
+        self.style("success", spec)
+    }
+
+    /// ## Sets the style of error messages, named `"error"`.
+    pub fn error(self, spec: StyleSpec) -> Self {
+        self.style("error", spec)
+    }
+
+    /// ## Sets the style of warnings, named `"warning"`.
+    pub fn warning(self, spec: StyleSpec) -> Self {
+        self.style("warning", spec)
+    }
+
+    /// ## Sets the style of informational messages, named `"info"`.
+    pub fn info(self, spec: StyleSpec) -> Self {
+        self.style("info", spec)
+    }
+
+    /// ## Adjusts the styles for the quirks of a terminal's default colors.
+    ///
+    /// * The Windows console, whose PowerShell background is dark blue and
+    ///   which can't dim text, gets bright blue and magenta instead of the dark
+    ///   ones, and bright black instead of dim.
+    /// * macOS Terminal, whose bright black is barely visible on dark
+    ///   profiles, gets a lighter gray instead.
+    ///
+    /// ### Arguments
+    ///
+    /// * `terminal` - The terminal to adjust for, usually [`Terminal::detect`]
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{theme::{Terminal, Theme}, Color, Style, StyleSpec};
+    ///
+    /// let theme = Theme::new()
+    ///     .style("path", StyleSpec::new().text(Color::Blue))
+    ///     .compat(Terminal::WindowsConsole);
+    ///
+    /// assert_eq!(theme.get("path"), Some(&StyleSpec::new().text(Color::BrightBlue)));
+    /// assert_eq!(theme.get("muted"), Some(&StyleSpec::new().text(Color::BrightBlack)));
+    /// ```
+    pub fn compat(mut self, terminal: Terminal) -> Self {
+        for spec in self.styles.values_mut() {
+            spec.text = spec.text.map(|c| terminal.adjust(c));
+            spec.bg = spec.bg.map(|c| terminal.adjust(c));
+            spec.underline = spec.underline.map(|c| terminal.adjust(c));
+            if terminal == Terminal::WindowsConsole && spec.font.contains(&Style::Dim) {
+                spec.font.retain(|&s| s != Style::Dim);
+                spec.text = spec.text.or(Some(Color::BrightBlack));
+            }
+        }
+        self
+    }
+
+    /// ## Checks the theme for styles that may not read well on a target terminal.
+    ///
+    /// Reports text and background pairs with a contrast below
+    /// [`MIN_CONTRAST`], colors and underlines the color level can't show as
+    /// written, and styles whose names aren't in `used`. Pass an empty `used`
+    /// to skip that last check.
+    ///
+    /// ### Arguments
+    ///
+    /// * `level` - The colors of the terminals the theme targets
+    /// * `used` - The names the application looks up
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{theme::{Theme, ThemeIssue}, Color, ColorLevel, StyleSpec};
+    ///
+    /// let theme = Theme::empty()
+    ///     .error(StyleSpec::new().text(Color::Blue).bg(Color::Black))
+    ///     .style("path", StyleSpec::new().text(Color::Rgb(95, 135, 175)));
+    ///
+    /// let issues = theme.lint(ColorLevel::Ansi16, &["error"]);
+    ///
+    /// assert!(matches!(&issues[0], ThemeIssue::LowContrast { name, .. } if name == "error"));
+    /// assert_eq!(issues[1].to_string(), "path: text color Rgb(95, 135, 175) is shown as BrightBlack");
+    /// assert_eq!(issues[2], ThemeIssue::Unused { name: "path".to_string() });
+    /// ```
+    pub fn lint(&self, level: ColorLevel, used: &[&str]) -> Vec<ThemeIssue> {
+        let mut issues = Vec::new();
+        for (name, spec) in &self.styles {
+            let shown = |c: Option<Color>| c.and_then(|c| c.downgrade(level));
+            if let (Some(text), Some(bg)) = (shown(spec.text), shown(spec.bg)) {
+                let ratio = text.contrast(bg);
+                if ratio < MIN_CONTRAST {
+                    issues.push(ThemeIssue::LowContrast {
+                        name: name.clone(),
+                        ratio,
+                    });
+                }
+            }
+
+            let colors = [("text", spec.text), ("background", spec.bg)];
+            for (role, color) in colors.into_iter().chain([("underline", spec.underline)]) {
+                let Some(color) = color else { continue };
+                let shown_as = color.downgrade(level);
+                let extended = role == "underline" && level < ColorLevel::Ansi256;
+                if shown_as != Some(color) || extended {
+                    issues.push(ThemeIssue::Unsupported {
+                        name: name.clone(),
+                        detail: match shown_as {
+                            Some(c) if !extended => {
+                                format!("{} color {:?} is shown as {:?}", role, color, c)
+                            }
+                            _ => format!("{} color {:?} is not shown", role, color),
+                        },
+                    });
+                }
+            }
+
+            for &style in spec.font.iter() {
+                let underline = matches!(
+                    style,
+                    Style::DoubleUnderline
+                        | Style::CurlyUnderline
+                        | Style::DottedUnderline
+                        | Style::DashedUnderline
+                );
+                if underline && level < ColorLevel::Ansi256 {
+                    issues.push(ThemeIssue::Unsupported {
+                        name: name.clone(),
+                        detail: format!("{:?} may be shown as a plain underline", style),
+                    });
+                }
+            }
+
+            if !used.is_empty() && !used.contains(&name.as_str()) {
+                issues.push(ThemeIssue::Unused { name: name.clone() });
+            }
+        }
+        issues
+    }
+
+    /// ## Renders every style of the theme on a sample text, one per line.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::theme::Theme;
+    ///
+    /// println!("{}", Theme::new().preview());
+    /// ```
+    pub fn preview(&self) -> String {
+        self.preview_with(&AnsiRenderer)
+    }
+
+    /// ## Same as [`Theme::preview`], rendering the styles through the given renderer.
+    ///
+    /// ### Arguments
+    ///
+    /// * `renderer` - The backend to render with
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{theme::Theme, Color, StyleSpec, TestRenderer};
+    ///
+    /// let theme = Theme::empty()
+    ///     .error(StyleSpec::new().text(Color::Red))
+    ///     .style("path", StyleSpec::new().text(Color::Blue));
+    ///
+    /// assert_eq!(
+    ///     theme.preview_with(&TestRenderer),
+    ///     "error  [fg=Red]The quick brown fox 0123[/]\n\
+    ///      path   [fg=Blue]The quick brown fox 0123[/]"
+    /// );
+    /// ```
+    pub fn preview_with(&self, renderer: &dyn Renderer) -> String {
+        let width = self.styles.keys().map(|n| text_width(n)).max().unwrap_or(0);
+        self.styles
+            .iter()
+            .map(|(name, spec)| {
+                format!(
+                    "{:<width$}  {}",
+                    name,
+                    renderer.paint(PREVIEW_SAMPLE, spec),
+                    width = width
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// The contrast ratio below which [`Theme::lint`] reports a text and background pair.
+///
+/// This is the WCAG minimum for large text, which terminal text on a colored
+/// background tends to be read as.
+pub const MIN_CONTRAST: f32 = 3.0;
+
+/// The text styled by [`Theme::preview`].
+const PREVIEW_SAMPLE: &str = "The quick brown fox 0123";
+
+/// A problem found by [`Theme::lint`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum ThemeIssue {
+    /// The text and background colors of a style are hard to tell apart.
+    LowContrast { name: String, ratio: f32 },
+    /// A color or style isn't shown as written at the target color level.
+    Unsupported { name: String, detail: String },
+    /// A style the application never looks up.
+    Unused { name: String },
+}
+
+impl fmt::Display for ThemeIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThemeIssue::LowContrast { name, ratio } => write!(
+                f,
+                "{}: text and background contrast is {:.1}:1, below {}:1",
+                name, ratio, MIN_CONTRAST
+            ),
+            ThemeIssue::Unsupported { name, detail } => write!(f, "{}: {}", name, detail),
+            ThemeIssue::Unused { name } => write!(f, "{}: not used", name),
+        }
+    }
+}
+
+/// A terminal whose default colors need adjusting, see [`Theme::compat`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Terminal {
+    /// The classic Windows console host, including PowerShell's default window.
+    WindowsConsole,
+    /// The macOS Terminal app.
+    AppleTerminal,
+    /// Any other terminal, which needs no adjustments.
+    Other,
+}
+
+impl Terminal {
+    /// ## Detects the terminal from the environment.
+    ///
+    /// The Windows console is assumed on Windows outside Windows Terminal and
+    /// terminals that set `TERM`, such as those of MSYS2 or Git Bash.
+    pub fn detect() -> Self {
+        let term_program = std::env::var("TERM_PROGRAM").unwrap_or_default();
+        if term_program == "Apple_Terminal" {
+            return Terminal::AppleTerminal;
+        }
+
+        let windows_terminal = std::env::var_os("WT_SESSION").is_some();
+        match cfg!(windows) && !windows_terminal && std::env::var_os("TERM").is_none() {
+            true => Terminal::WindowsConsole,
+            false => Terminal::Other,
+        }
+    }
+
+    /// ## Returns the color to use instead of `color` on this terminal.
+    fn adjust(self, color: Color) -> Color {
+        match (self, color) {
+            (Terminal::WindowsConsole, Color::Blue) => Color::BrightBlue,
+            (Terminal::WindowsConsole, Color::Magenta) => Color::BrightMagenta,
+            (Terminal::AppleTerminal, Color::BrightBlack) => Color::Color256(245),
+            _ => color,
+        }
+    }
+}
+
+/// The theme set by [`set_theme`], `None` for the default one.
+static THEME: RwLock<Option<Theme>> = RwLock::new(None);
+
+/// ## Sets the theme used by the semantic helpers, for the whole process.
+///
+/// ### Arguments
+///
+/// * `theme` - The theme to use from now on
+pub fn set_theme(theme: Theme) {
+    *THEME.write().unwrap_or_else(|e| e.into_inner()) = Some(theme);
+}
+
+/// ## Sets the default theme, adjusted for the detected terminal.
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::theme;
+///
+/// theme::use_compat_theme();
+/// ```
+pub fn use_compat_theme() {
+    set_theme(Theme::new().compat(Terminal::detect()));
+}
+
+/// ## Styles a command or code snippet, padded and in the theme's `"code"` style.
+///
+/// ### Arguments
+///
+/// * `value` - The code, e.g. `"cargo build"`
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{clw, clw_code};
+///
+/// let hint = clw("Run ") + clw_code("cargo build") + " first.";
+///
+/// assert_eq!(hint.plain(), "Run  cargo build  first.");
+/// ```
+pub fn clw_code<T: fmt::Display>(value: T) -> CLW<'static> {
+    clw(format!(" {} ", value)).style("code")
+}
+
+/// ## Styles a keyboard key or shortcut, padded and in the theme's `"key"` style.
+///
+/// ### Arguments
+///
+/// * `value` - The key, e.g. `"Ctrl+C"`
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::{clw_key, RenderMode};
+///
+/// assert_eq!(clw_key("Ctrl+C").to_string_for(RenderMode::Test), "[Reverse,Bold] Ctrl+C [/]");
+/// ```
+pub fn clw_key<T: fmt::Display>(value: T) -> CLW<'static> {
+    clw(format!(" {} ", value)).style("key")
+}
+
+/// ## Goes back to the default theme.
+pub fn reset_theme() {
+    *THEME.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// ## Returns the theme currently in use.
+pub fn current() -> Theme {
+    THEME
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .unwrap_or_else(|| default_theme().clone())
+}
+
+/// ## Returns a style of the current theme, copying only that style.
+pub(crate) fn current_style(name: &str) -> Option<StyleSpec> {
+    match &*THEME.read().unwrap_or_else(|e| e.into_inner()) {
+        Some(theme) => theme.get(name).cloned(),
+        None => default_theme().get(name).cloned(),
+    }
+}
+
+/// ## Returns the default theme, built once.
+fn default_theme() -> &'static Theme {
+    static DEFAULT: OnceLock<Theme> = OnceLock::new();
+    DEFAULT.get_or_init(Theme::default)
+}
+
+impl CLW<'_> {
+    /// ## Styles the text as a success message, using the current theme.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{clw, RenderMode};
+    ///
+    /// assert_eq!(clw("done").success().to_string_for(RenderMode::Test), "[fg=Green]done[/]");
+    /// ```
+    pub fn success(self) -> Self {
+        self.style("success")
+    }
+
+    /// ## Styles the text as an error, using the current theme.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{clw, RenderMode};
+    ///
+    /// let failed = clw("failed").error();
+    ///
+    /// assert_eq!(failed.to_string_for(RenderMode::Test), "[fg=BrightRed,Bold]failed[/]");
+    /// ```
+    pub fn error(self) -> Self {
+        self.style("error")
+    }
+
+    /// ## Styles the text as a warning, using the current theme.
+    pub fn warning(self) -> Self {
+        self.style("warning")
+    }
+
+    /// ## Styles the text as an informational message, using the current theme.
+    pub fn info(self) -> Self {
+        self.style("info")
+    }
+
+    /// ## Applies the style the current theme has for a name.
+    ///
+    /// The theme's colors replace existing ones and its styles are added.
+    /// Names the theme doesn't know leave the text as it is.
+    ///
+    /// ### Arguments
+    ///
+    /// * `name` - The semantic name, e.g. `"heading"`
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{clw, RenderMode};
+    ///
+    /// let title = clw("Usage").style("heading");
+    ///
+    /// assert_eq!(title.to_string_for(RenderMode::Test), "[Bold,Underline]Usage[/]");
+    /// ```
+    pub fn style(mut self, name: &str) -> Self {
+        if let Some(spec) = current_style(name) {
+            self.spec.text = spec.text.or(self.spec.text);
+            self.spec.bg = spec.bg.or(self.spec.bg);
+            self.spec.underline = spec.underline.or(self.spec.underline);
+            self.spec.font.extend(spec.font.iter().copied());
+        }
+        self
+    }
+}
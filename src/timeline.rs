@@ -0,0 +1,235 @@
+//! Gantt-style timeline rendering for profilers and CI step visualizers.
+
+use std::fmt;
+use std::time::Duration;
+
+use crate::symbols::unicode_supported;
+use crate::{format, terminal_width, text_width, AnsiRenderer, Color, Renderer, Style, StyleSpec};
+
+/// A named span on the timeline.
+struct Span {
+    name: String,
+    start: Duration,
+    duration: Duration,
+    color: Color,
+}
+
+/// Renders named spans as colored bars on a shared time axis.
+#[derive(Default)]
+pub struct Timeline {
+    spans: Vec<Span>,
+    width: Option<usize>,
+}
+
+impl Timeline {
+    /// ## Creates an empty timeline.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::timeline::Timeline;
+    ///
+    /// let timeline = Timeline::new();
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// ## Adds a span to the timeline.
+    ///
+    /// ### Arguments
+    ///
+    /// * `name` - The label shown in front of the bar
+    /// * `start` - The offset of the span from the start of the timeline
+    /// * `duration` - How long the span lasted
+    /// * `color` - The background color of the bar
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use clwind::{timeline::Timeline, Color};
+    ///
+    /// let timeline = Timeline::new()
+    ///     .span("fetch", Duration::ZERO, Duration::from_millis(300), Color::Blue)
+    ///     .span("build", Duration::from_millis(300), Duration::from_secs(2), Color::Green);
+    /// ```
+    pub fn span<S: Into<String>>(
+        mut self,
+        name: S,
+        start: Duration,
+        duration: Duration,
+        color: Color,
+    ) -> Self {
+        self.spans.push(Span {
+            name: name.into(),
+            start,
+            duration,
+            color,
+        });
+        self
+    }
+
+    /// ## Sets the total width of the rendered timeline.
+    ///
+    /// Defaults to the width of the terminal.
+    ///
+    /// ### Arguments
+    ///
+    /// * `width` - The number of columns to fit the timeline in
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// ## Renders the timeline, one span per line followed by the time axis.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use clwind::{timeline::Timeline, Color};
+    ///
+    /// let rendered = Timeline::new()
+    ///     .span("test", Duration::ZERO, Duration::from_secs(1), Color::Cyan)
+    ///     .width(40)
+    ///     .render();
+    ///
+    /// assert!(rendered.starts_with("test "));
+    /// ```
+    pub fn render(&self) -> String {
+        self.render_with(&AnsiRenderer)
+    }
+
+    /// ## Renders the timeline through the given renderer.
+    ///
+    /// Bars are drawn as a background color, or with `█` (`#` without
+    /// Unicode) when the renderer leaves the background out, as with
+    /// [`PlainRenderer`](crate::PlainRenderer) or colors disabled.
+    ///
+    /// ### Arguments
+    ///
+    /// * `renderer` - The backend used to style the bars and labels
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use clwind::{timeline::Timeline, Color, HtmlRenderer};
+    ///
+    /// let html = Timeline::new()
+    ///     .span("<init>", Duration::ZERO, Duration::from_secs(1), Color::Cyan)
+    ///     .width(40)
+    ///     .render_with(&HtmlRenderer);
+    ///
+    /// assert!(html.starts_with("&lt;init&gt;"));
+    /// ```
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use clwind::{timeline::Timeline, with_capabilities, Capabilities, Color, PlainRenderer};
+    ///
+    /// let ascii = Capabilities::from_env(|_| None, false).unicode(false);
+    /// let plain = with_capabilities(ascii, || {
+    ///     Timeline::new()
+    ///         .span("fetch", Duration::ZERO, Duration::from_millis(300), Color::Blue)
+    ///         .width(20)
+    ///         .render_with(&PlainRenderer)
+    /// });
+    ///
+    /// assert_eq!(plain.lines().next(), Some("fetch ######## 300ms"));
+    /// ```
+    pub fn render_with(&self, renderer: &dyn Renderer) -> String {
+        let plain = StyleSpec::new();
+        let dim = StyleSpec::new().font(Style::Dim);
+        let width = self.width.unwrap_or_else(terminal_width);
+        let end = self
+            .spans
+            .iter()
+            .map(|s| s.start + s.duration)
+            .max()
+            .unwrap_or_default();
+
+        let durations: Vec<String> = self
+            .spans
+            .iter()
+            .map(|s| format::duration(s.duration))
+            .collect();
+        let label_width = self
+            .spans
+            .iter()
+            .map(|s| text_width(&s.name))
+            .max()
+            .unwrap_or(0);
+        let duration_width = durations.iter().map(|d| text_width(d)).max().unwrap_or(0);
+        let track = width
+            .saturating_sub(label_width + duration_width + 2)
+            .max(1);
+
+        let mut lines = Vec::with_capacity(self.spans.len() + 1);
+        for (span, duration) in self.spans.iter().zip(&durations) {
+            let (from, to) = bar_cells(span.start, span.duration, end, track);
+            let blank = " ".repeat(to - from);
+            let mut bar = renderer.paint(&blank, &StyleSpec::new().bg(span.color));
+            if bar == blank {
+                bar = bar_glyph().repeat(to - from);
+            }
+            lines.push(format!(
+                "{}{} {}{}{} {}",
+                renderer.paint(&span.name, &plain),
+                " ".repeat(label_width - text_width(&span.name)),
+                " ".repeat(from),
+                bar,
+                " ".repeat(track - to),
+                renderer.paint(duration, &plain),
+            ));
+        }
+
+        let origin = "0s";
+        let total = format::duration(end);
+        lines.push(format!(
+            "{} {}{}{}",
+            " ".repeat(label_width),
+            renderer.paint(origin, &dim),
+            " ".repeat(track.saturating_sub(text_width(origin) + text_width(&total))),
+            renderer.paint(&total, &dim),
+        ));
+
+        lines.join("\n")
+    }
+}
+
+impl fmt::Display for Timeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render())
+    }
+}
+
+/// ## Returns the character bars are drawn with when they have no background.
+fn bar_glyph() -> &'static str {
+    match unicode_supported() {
+        true => "█",
+        false => "#",
+    }
+}
+
+/// ## Maps a span to the `[from, to)` cells it covers on a track of `track` cells.
+///
+/// Non-empty spans always cover at least one cell so short steps stay visible.
+fn bar_cells(start: Duration, duration: Duration, end: Duration, track: usize) -> (usize, usize) {
+    if end.is_zero() {
+        return (0, 0);
+    }
+
+    let scale = track as f64 / end.as_secs_f64();
+    let from = ((start.as_secs_f64() * scale).floor() as usize).min(track);
+    let mut to = (((start + duration).as_secs_f64() * scale).round() as usize).min(track);
+    if !duration.is_zero() && to <= from {
+        to = (from + 1).min(track);
+        if to == from {
+            return (track - 1, track);
+        }
+    }
+
+    (from, to.max(from))
+}
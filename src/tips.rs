@@ -0,0 +1,130 @@
+//! Onboarding tips, each shown once per user.
+//!
+//! The ids of the tips already shown are kept in a state file, one per line,
+//! so tools don't each reinvent the storage.
+
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use crate::{clw, StyledString};
+
+/// A sequence of tips, shown one at a time in order.
+///
+/// ### Examples
+///
+/// ```
+/// use clwind::tips::Tips;
+///
+/// let state = std::env::temp_dir().join(format!("clwind-tips-{}", std::process::id()));
+/// let tips = Tips::new("mytool")
+///     .tip("alias", "Run `mytool alias` to add shell shortcuts.")
+///     .tip("config", "Settings live in ~/.config/mytool.toml.")
+///     .state_file(&state);
+///
+/// let first = tips.next().unwrap().unwrap();
+/// assert_eq!(first.plain(), "Tip: Run `mytool alias` to add shell shortcuts.");
+/// assert_eq!(tips.next().unwrap().unwrap().plain(), "Tip: Settings live in ~/.config/mytool.toml.");
+/// assert!(tips.next().unwrap().is_none());
+///
+/// tips.reset().unwrap();
+/// ```
+pub struct Tips {
+    app: String,
+    tips: Vec<(String, String)>,
+    state_file: Option<PathBuf>,
+}
+
+impl Tips {
+    /// ## Creates an empty sequence for an application.
+    ///
+    /// ### Arguments
+    ///
+    /// * `app` - The name of the application, used for the default state file
+    pub fn new<S: Into<String>>(app: S) -> Self {
+        Tips {
+            app: app.into(),
+            tips: Vec::new(),
+            state_file: None,
+        }
+    }
+
+    /// ## Adds a tip.
+    ///
+    /// ### Arguments
+    ///
+    /// * `id` - A stable id, recorded once the tip is shown
+    /// * `text` - The text of the tip
+    pub fn tip<I: Into<String>, S: Into<String>>(mut self, id: I, text: S) -> Self {
+        self.tips.push((id.into(), text.into()));
+        self
+    }
+
+    /// ## Sets the file recording the tips already shown.
+    ///
+    /// Defaults to `<app>/tips` in `$XDG_STATE_HOME`, `~/.local/state` or,
+    /// on Windows, `%LOCALAPPDATA%`.
+    pub fn state_file<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.state_file = Some(path.into());
+        self
+    }
+
+    /// ## Returns the next tip not shown yet, and records it as shown.
+    ///
+    /// Returns `None` once every tip was shown, or if there is nowhere to
+    /// record them, so tips are never repeated.
+    pub fn next(&self) -> io::Result<Option<StyledString>> {
+        let Some(path) = self.path() else {
+            return Ok(None);
+        };
+
+        let seen = self.seen()?;
+        let Some((id, text)) = self.tips.iter().find(|(id, _)| !seen.contains(id)) else {
+            return Ok(None);
+        };
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+        writeln!(file, "{}", id)?;
+
+        Ok(Some(clw("Tip:").info() + " " + text.as_str()))
+    }
+
+    /// ## Returns the ids of the tips already shown.
+    pub fn seen(&self) -> io::Result<HashSet<String>> {
+        let Some(path) = self.path() else {
+            return Ok(HashSet::new());
+        };
+
+        match fs::read_to_string(path) {
+            Ok(contents) => Ok(contents.lines().map(str::to_string).collect()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(HashSet::new()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// ## Forgets the tips already shown, by removing the state file.
+    pub fn reset(&self) -> io::Result<()> {
+        match self.path().map(fs::remove_file) {
+            Some(Err(e)) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    /// ## Returns the state file, if one is set or a state directory is known.
+    fn path(&self) -> Option<PathBuf> {
+        if let Some(path) = &self.state_file {
+            return Some(path.clone());
+        }
+
+        let var = |name| std::env::var_os(name).filter(|v| !v.is_empty());
+        let dir = var("XDG_STATE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| var("HOME").map(|home| PathBuf::from(home).join(".local/state")))
+            .or_else(|| var("LOCALAPPDATA").map(PathBuf::from))?;
+        Some(dir.join(&self.app).join("tips"))
+    }
+}
@@ -0,0 +1,561 @@
+//! A virtual terminal that interprets rendered output, for testing components.
+//!
+//! Text written to a [`VirtualTerminal`] lands in a grid of cells, each with
+//! the style that was active when it was written. Cursor movement and erase
+//! sequences are applied, so tests can assert on the final screen rather than
+//! on raw escape codes.
+//!
+//! ```
+//! use clwind::{clw, vt::VirtualTerminal, Color, ColorChoice};
+//!
+//! let error = clw("error").text_red().color_choice(ColorChoice::Always);
+//!
+//! let mut term = VirtualTerminal::new(20, 3);
+//! term.write(&error.to_string());
+//! term.write(": disk full");
+//!
+//! assert_eq!(term.line(0), "error: disk full");
+//! assert_eq!(term.cell(0, 0).style.text_color(), Some(Color::Red));
+//! assert_eq!(term.cell(0, 5).style.text_color(), None);
+//! ```
+//!
+//! For golden-file tests, [`VirtualTerminal::assert_golden`] compares the
+//! screen against a dump stored on disk and reports the cells that changed.
+//! Set `CLWIND_BLESS=1` to write the dumps instead of comparing.
+
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+use crate::ansi::{apply_sgr, sequence_end, ESC};
+use crate::render::spec_attrs;
+use crate::{char_width, StyleSpec};
+
+/// A character on the screen and the style it was written with.
+///
+/// A wide character takes two cells: the first holds it, and the second is a
+/// blank cell with a `width` of 0.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cell {
+    /// The character shown, a space for empty cells.
+    pub ch: char,
+    /// The zero-width characters written after `ch`, such as combining marks.
+    pub marks: String,
+    /// The columns the character takes: 1, 2 for wide characters, or 0 for
+    /// the second half of a wide character.
+    pub width: usize,
+    /// The style active when the character was written.
+    pub style: StyleSpec,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Cell {
+            ch: ' ',
+            marks: String::new(),
+            width: 1,
+            style: StyleSpec::new(),
+        }
+    }
+}
+
+/// A grid of styled cells that output is written into, as a terminal would.
+#[derive(Clone, Debug)]
+pub struct VirtualTerminal {
+    width: usize,
+    height: usize,
+    cells: Vec<Vec<Cell>>,
+    row: usize,
+    col: usize,
+    style: StyleSpec,
+    saved: (usize, usize, StyleSpec),
+    region: (usize, usize),
+}
+
+impl VirtualTerminal {
+    /// ## Creates an empty screen with the cursor at the top left.
+    ///
+    /// ### Arguments
+    ///
+    /// * `width` - The number of columns, raised to 1 if it is 0
+    /// * `height` - The number of rows, raised to 1 if it is 0
+    pub fn new(width: usize, height: usize) -> Self {
+        let (width, height) = (width.max(1), height.max(1));
+        VirtualTerminal {
+            width,
+            height,
+            cells: vec![vec![Cell::default(); width]; height],
+            row: 0,
+            col: 0,
+            style: StyleSpec::new(),
+            saved: (0, 0, StyleSpec::new()),
+            region: (0, height - 1),
+        }
+    }
+
+    /// ## Interprets output as a terminal would.
+    ///
+    /// Supports SGR styling, cursor movement (`A`, `B`, `C`, `D`, `G`, `H`),
+    /// erasing (`J`, `K`), scrolling regions (`r`), saving and restoring the
+    /// cursor (`ESC 7`, `ESC 8`), `\r`, `\n`, `\t` and backspace. Other
+    /// sequences, such as hyperlinks, are skipped. Wide characters take two
+    /// cells, and zero-width characters join the character before them.
+    ///
+    /// ### Arguments
+    ///
+    /// * `output` - The text to interpret
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{ansi::Csi, vt::VirtualTerminal};
+    ///
+    /// let mut term = VirtualTerminal::new(10, 2);
+    /// term.write("[1/2]\n");
+    /// term.write(&format!("{}\r{}", Csi::new('A').param(1), "[2/2]"));
+    ///
+    /// assert_eq!(term.screen(), "[2/2]\n");
+    /// ```
+    ///
+    /// A status line kept on the bottom row while the rows above it scroll:
+    ///
+    /// ```
+    /// use clwind::vt::VirtualTerminal;
+    ///
+    /// let mut term = VirtualTerminal::new(20, 3);
+    /// term.write("\x1b7\x1b[1;2r\x1b8");
+    /// for line in ["one", "two", "three"] {
+    ///     term.write(&format!("{}\n", line));
+    ///     term.write(&format!("\x1b7\x1b[3;1H\x1b[2Kbuilt {}\x1b8", line));
+    /// }
+    ///
+    /// assert_eq!(term.screen(), "three\n\nbuilt three\n");
+    /// ```
+    pub fn write(&mut self, output: &str) {
+        let mut i = 0;
+        while let Some(c) = output[i..].chars().next() {
+            if c == ESC {
+                let end = sequence_end(output, i);
+                self.escape(&output[i + c.len_utf8()..end]);
+                i = end;
+                continue;
+            }
+
+            i += c.len_utf8();
+            match c {
+                '\n' => self.line_feed(),
+                '\r' => self.col = 0,
+                '\t' => self.col = ((self.col / 8 + 1) * 8).min(self.width - 1),
+                '\x08' => self.col = self.col.saturating_sub(1),
+                c if c.is_control() => {}
+                c => self.put(c),
+            }
+        }
+    }
+
+    /// ## Returns the cell at the given position.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the position is outside of the screen.
+    pub fn cell(&self, row: usize, col: usize) -> &Cell {
+        &self.cells[row][col]
+    }
+
+    /// ## Returns the text of a row, without trailing spaces.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if `row` is outside of the screen.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::vt::VirtualTerminal;
+    ///
+    /// let mut term = VirtualTerminal::new(10, 1);
+    /// term.write("日本 cafe\u{301}");
+    ///
+    /// assert_eq!(term.line(0), "日本 cafe\u{301}");
+    /// assert_eq!(term.cursor(), (0, 9));
+    /// assert_eq!(term.cell(0, 1).width, 0);
+    /// ```
+    pub fn line(&self, row: usize) -> String {
+        let mut line = String::new();
+        for cell in self.cells[row].iter().filter(|cell| cell.width > 0) {
+            line.push(cell.ch);
+            line.push_str(&cell.marks);
+        }
+        line.trim_end().to_string()
+    }
+
+    /// ## Returns the text of the whole screen, without trailing blank lines.
+    pub fn screen(&self) -> String {
+        let lines: Vec<String> = (0..self.height).map(|row| self.line(row)).collect();
+        let last = lines
+            .iter()
+            .rposition(|l| !l.is_empty())
+            .map_or(0, |i| i + 1);
+        lines[..last].iter().map(|l| format!("{}\n", l)).collect()
+    }
+
+    /// ## Returns the cursor position as `(row, column)`.
+    pub fn cursor(&self) -> (usize, usize) {
+        (self.row, self.col)
+    }
+
+    /// ## Dumps the screen as readable text, with the style runs of each row.
+    ///
+    /// Every row is written as `row|text`, followed by an indented
+    /// `start..end attributes` line for each run of styled cells.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use clwind::{clw, vt::VirtualTerminal, ColorChoice};
+    ///
+    /// let mut term = VirtualTerminal::new(10, 1);
+    /// term.write(&clw("ok").text_green().color_choice(ColorChoice::Always).to_string());
+    /// term.write(" done");
+    ///
+    /// assert_eq!(term.to_golden(), "0|ok done\n  0..2 fg=Green\n");
+    /// ```
+    pub fn to_golden(&self) -> String {
+        let mut out = String::new();
+        for (row, cells) in self.cells.iter().enumerate() {
+            out.push_str(&format!("{}|{}\n", row, self.line(row)));
+            let mut start = 0;
+            while start < cells.len() {
+                let end = cells[start..]
+                    .iter()
+                    .position(|c| c.style != cells[start].style)
+                    .map_or(cells.len(), |n| start + n);
+                let attrs = spec_attrs(&cells[start].style);
+                if !attrs.is_empty() {
+                    out.push_str(&format!("  {}..{} {}\n", start, end, attrs));
+                }
+                start = end;
+            }
+        }
+        out
+    }
+
+    /// ## Writes the dump of [`to_golden`](Self::to_golden) to a file.
+    ///
+    /// ### Arguments
+    ///
+    /// * `path` - The golden file to write
+    pub fn render_to_golden<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        std::fs::write(path, self.to_golden())
+    }
+
+    /// ## Asserts that the screen matches a golden file.
+    ///
+    /// The file is written instead when it doesn't exist yet, or when the
+    /// `CLWIND_BLESS` environment variable is set.
+    ///
+    /// ### Arguments
+    ///
+    /// * `path` - The golden file to compare against
+    ///
+    /// ### Panics
+    ///
+    /// Panics with the list of changed cells if the screen differs, or if
+    /// the file can't be read or written.
+    ///
+    /// ### Examples
+    ///
+    /// ```
+    /// use std::panic;
+    /// use clwind::{clw, vt::VirtualTerminal, Color, ColorChoice};
+    ///
+    /// let screen = |color| {
+    ///     let mut term = VirtualTerminal::new(6, 1);
+    ///     let y = clw("y").text(color).color_choice(ColorChoice::Always);
+    ///     term.write(&format!("日x{}", y));
+    ///     term
+    /// };
+    /// let path = std::env::temp_dir().join("clwind-assert-golden.txt");
+    /// screen(Color::Red).render_to_golden(&path).unwrap();
+    ///
+    /// let error = panic::catch_unwind(|| screen(Color::Green).assert_golden(&path)).unwrap_err();
+    /// let message = error.downcast_ref::<String>().unwrap();
+    ///
+    /// assert!(message.ends_with("in 1 cells:\n  0:3 'y' [fg=Red] -> 'y' [fg=Green]"));
+    /// ```
+    pub fn assert_golden<P: AsRef<Path>>(&self, path: P) {
+        let path = path.as_ref();
+        let actual = self.to_golden();
+        if std::env::var_os("CLWIND_BLESS").is_some() || !path.exists() {
+            self.render_to_golden(path)
+                .unwrap_or_else(|e| panic!("cannot write {}: {}", path.display(), e));
+            return;
+        }
+
+        let expected = std::fs::read_to_string(path)
+            .unwrap_or_else(|e| panic!("cannot read {}: {}", path.display(), e));
+        if expected == actual {
+            return;
+        }
+
+        let (expected, actual) = (parse_golden(&expected), parse_golden(&actual));
+        let mut changes = Vec::new();
+        for row in 0..expected.len().max(actual.len()) {
+            let (a, b) = (row_cells(&expected, row), row_cells(&actual, row));
+            for col in 0..a.len().max(b.len()) {
+                let (a, b) = (cell_at(a, col), cell_at(b, col));
+                if a != b {
+                    changes.push(format!(
+                        "  {}:{} {} -> {}",
+                        row,
+                        col,
+                        describe(&a),
+                        describe(&b)
+                    ));
+                }
+            }
+        }
+        panic!(
+            "screen differs from {} in {} cells:\n{}",
+            path.display(),
+            changes.len(),
+            changes.join("\n")
+        );
+    }
+
+    /// ## Writes a character at the cursor, wrapping at the end of the row.
+    ///
+    /// Zero-width characters, and characters joined by a zero-width joiner,
+    /// are added to the previous cell instead.
+    fn put(&mut self, ch: char) {
+        let width = char_width(ch);
+        if let Some(cell) = self.previous_cell() {
+            if width == 0 || cell.marks.ends_with('\u{200d}') {
+                cell.marks.push(ch);
+                return;
+            }
+        }
+        if width == 0 {
+            return;
+        }
+
+        if self.col + width > self.width {
+            self.col = 0;
+            self.line_feed();
+        }
+        for col in self.col..(self.col + width).min(self.width) {
+            self.split_wide(col);
+        }
+        self.cells[self.row][self.col] = Cell {
+            ch,
+            marks: String::new(),
+            width,
+            style: self.style.clone(),
+        };
+        if width == 2 && self.col + 1 < self.width {
+            self.cells[self.row][self.col + 1] = Cell {
+                width: 0,
+                style: self.style.clone(),
+                ..Cell::default()
+            };
+        }
+        self.col += width;
+    }
+
+    /// ## Returns the cell holding the last character written on the row.
+    fn previous_cell(&mut self) -> Option<&mut Cell> {
+        let mut col = self.col.min(self.width).checked_sub(1)?;
+        if self.cells[self.row][col].width == 0 {
+            col = col.checked_sub(1)?;
+        }
+        Some(&mut self.cells[self.row][col])
+    }
+
+    /// ## Blanks the other half of a wide character about to be overwritten.
+    fn split_wide(&mut self, col: usize) {
+        let row = &mut self.cells[self.row];
+        match row[col].width {
+            0 if col > 0 => row[col - 1] = Cell::default(),
+            2 if col + 1 < row.len() => row[col + 1] = Cell::default(),
+            _ => {}
+        }
+    }
+
+    /// ## Moves the cursor down a row, scrolling the region at its bottom.
+    fn line_feed(&mut self) {
+        self.col = 0;
+        let (top, bottom) = self.region;
+        match self.row {
+            row if row == bottom => {
+                self.cells.remove(top);
+                self.cells.insert(bottom, vec![Cell::default(); self.width]);
+            }
+            row if row + 1 < self.height => self.row += 1,
+            _ => {}
+        }
+    }
+
+    /// ## Applies an escape sequence, given without its `ESC`.
+    fn escape(&mut self, sequence: &str) {
+        if let Some(body) = sequence.strip_prefix('[') {
+            if let Some(final_byte) = body
+                .chars()
+                .last()
+                .filter(|c| ('\x40'..='\x7e').contains(c))
+            {
+                self.csi(&body[..body.len() - 1], final_byte);
+            }
+            return;
+        }
+
+        match sequence {
+            "7" => self.saved = (self.row, self.col, self.style.clone()),
+            "8" => (self.row, self.col, self.style) = self.saved.clone(),
+            _ => {}
+        }
+    }
+
+    /// ## Applies a CSI sequence.
+    fn csi(&mut self, params: &str, final_byte: char) {
+        let numbers: Vec<usize> = params.split(';').map(|p| p.parse().unwrap_or(0)).collect();
+        let n = numbers.first().copied().unwrap_or(0).max(1);
+        match final_byte {
+            'm' => self.sgr(params),
+            'A' => self.row = self.row.saturating_sub(n),
+            'B' => self.row = (self.row + n).min(self.height - 1),
+            'C' => self.col = (self.col + n).min(self.width - 1),
+            'D' => self.col = self.col.saturating_sub(n).min(self.width - 1),
+            'G' => self.col = (n - 1).min(self.width - 1),
+            'H' | 'f' => {
+                let col = numbers.get(1).copied().unwrap_or(0).max(1);
+                self.row = (n - 1).min(self.height - 1);
+                self.col = (col - 1).min(self.width - 1);
+            }
+            'K' => {
+                let (from, to) = match numbers[0] {
+                    0 => (self.col, self.width),
+                    1 => (0, self.col + 1),
+                    _ => (0, self.width),
+                };
+                self.clear(self.row, from.min(self.width)..to.min(self.width));
+            }
+            'r' => {
+                let bottom = numbers.get(1).copied().filter(|&n| n > 0);
+                let bottom = bottom.unwrap_or(self.height).min(self.height) - 1;
+                if n - 1 < bottom {
+                    self.region = (n - 1, bottom);
+                    self.row = 0;
+                    self.col = 0;
+                }
+            }
+            'J' => {
+                let rows = match numbers[0] {
+                    0 => self.row + 1..self.height,
+                    1 => 0..self.row,
+                    _ => 0..self.height,
+                };
+                let (from, to) = match numbers[0] {
+                    0 => (self.col.min(self.width), self.width),
+                    1 => (0, (self.col + 1).min(self.width)),
+                    _ => (0, self.width),
+                };
+                self.clear(self.row, from..to);
+                for row in rows {
+                    self.clear(row, 0..self.width);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// ## Resets a range of cells in a row.
+    fn clear(&mut self, row: usize, cols: std::ops::Range<usize>) {
+        for cell in &mut self.cells[row][cols] {
+            *cell = Cell::default();
+        }
+    }
+
+    /// ## Updates the current style from SGR parameters.
+    fn sgr(&mut self, params: &str) {
+        apply_sgr(&mut self.style, params);
+    }
+}
+
+/// A cell read back from a golden dump, as its text and attributes.
+///
+/// The text holds a character and the zero-width characters joined to it, or
+/// is empty for the second half of a wide character.
+type GoldenCell = (String, String);
+
+/// ## Reads a golden dump back into rows of cells.
+fn parse_golden(dump: &str) -> Vec<Vec<GoldenCell>> {
+    let mut rows: Vec<Vec<GoldenCell>> = Vec::new();
+    for line in dump.lines() {
+        if let Some(run) = line.strip_prefix("  ") {
+            let (range, attrs) = run.split_once(' ').unwrap_or((run, ""));
+            let (start, end) = range.split_once("..").unwrap_or((range, range));
+            let (start, end) = (start.parse().unwrap_or(0), end.parse().unwrap_or(0));
+            if let Some(row) = rows.last_mut() {
+                if row.len() < end {
+                    row.resize(end, (" ".to_string(), String::new()));
+                }
+                for cell in &mut row[start.min(end)..end] {
+                    cell.1 = attrs.to_string();
+                }
+            }
+        } else if let Some((_, text)) = line.split_once('|') {
+            rows.push(golden_cells(text));
+        }
+    }
+    rows
+}
+
+/// ## Splits the text of a dumped row into cells, as the terminal laid it out.
+fn golden_cells(text: &str) -> Vec<GoldenCell> {
+    let mut cells: Vec<GoldenCell> = Vec::new();
+    let mut joined = false;
+    for c in text.chars() {
+        let width = char_width(c);
+        let previous = cells.iter_mut().rev().find(|(text, _)| !text.is_empty());
+        if let Some((previous, _)) = previous.filter(|_| width == 0 || joined) {
+            previous.push(c);
+        } else if width > 0 {
+            cells.push((c.to_string(), String::new()));
+            if width == 2 {
+                cells.push((String::new(), String::new()));
+            }
+        }
+        joined = c == '\u{200d}';
+    }
+    cells
+}
+
+/// ## Returns a row of parsed cells, empty when it doesn't exist.
+fn row_cells(rows: &[Vec<GoldenCell>], row: usize) -> &[GoldenCell] {
+    rows.get(row).map_or(&[], |r| r.as_slice())
+}
+
+/// ## Returns a parsed cell, blank when it doesn't exist.
+fn cell_at(row: &[GoldenCell], col: usize) -> GoldenCell {
+    row.get(col)
+        .cloned()
+        .unwrap_or_else(|| (" ".to_string(), String::new()))
+}
+
+/// ## Describes a parsed cell for the diff, e.g. `'a' [fg=Red]`.
+///
+/// The second half of a wide character is shown as `''`.
+fn describe((text, attrs): &GoldenCell) -> String {
+    let text = format!("'{}'", text.escape_debug());
+    match attrs.is_empty() {
+        true => text,
+        false => format!("{} [{}]", text, attrs),
+    }
+}
+
+impl fmt::Write for VirtualTerminal {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write(s);
+        Ok(())
+    }
+}